@@ -0,0 +1,208 @@
+//! Derive macros for [`clap-clap`].
+//!
+//! Use the macros re-exported from `clap_clap::ext::params`. See the
+//! documentation there for examples.
+//!
+//! [`clap-clap`]: https://docs.rs/clap-clap/latest/clap_clap/
+
+use proc_macro::{TokenStream, TokenTree};
+
+use crate::parse::{Field, Item, Kind};
+
+mod parse;
+
+const PARAMS: &str = "::clap_clap::ext::params";
+
+/// Derive `ParamSet` and `Default` for a struct of typed parameters.
+///
+/// Each field must be one of: `FloatParam`, `IntParam`, `BoolParam`, or
+/// `EnumParam<E>`, and can be configured with the attribute: `#[param(...)]`:
+///
+/// * `id = <u32>`: the stable parameter id. If omitted, the id is a hash of the
+///   field's name, and it changes when the field is renamed.
+/// * `name = <&str>`: the display name. Defaults to the field's name.
+/// * `module = <&str>`: the module path, e.g. `"Oscillators/Wavetable 1"`.
+/// * `range = <min>..=<max>`: the range of plain values.
+/// * `default = <value>`: the default value.
+/// * `unit = <&'static str>`: the unit to format the value with.
+/// * `format = <ValueFormat>`: the format of the value's text, e.g. `Decibels`.
+///   Only for `FloatParam` and `IntParam`.
+/// * `flags = <InfoFlags> | ...`: the parameter flags, e.g.
+///   `Automatable | Modulatable`.
+#[proc_macro_derive(Params, attributes(param))]
+pub fn derive_params(input: TokenStream) -> TokenStream {
+    expand(params(input))
+}
+
+/// Derive `ParamEnum` for an enum with unit variants.
+///
+/// The label of each variant is its name, unless set with the attribute:
+/// `#[param(name = <&str>)]`.
+#[proc_macro_derive(ParamEnum, attributes(param))]
+pub fn derive_param_enum(input: TokenStream) -> TokenStream {
+    expand(param_enum(input))
+}
+
+fn expand(code: Result<String, String>) -> TokenStream {
+    code.unwrap_or_else(|msg| format!("::core::compile_error!({msg:?});"))
+        .parse()
+        .expect("generated code should be valid tokens")
+}
+
+fn params(input: TokenStream) -> Result<String, String> {
+    let item = Item::parse(input)?;
+    let Kind::Struct = item.kind else {
+        return Err(format!("expected a struct: {}", item.name));
+    };
+    let fields = Field::parse_all(&item.body)?;
+
+    let mut ids = Vec::new();
+    let mut inits = String::new();
+    let mut params = String::new();
+    for (index, field) in fields.iter().enumerate() {
+        let ty = parse::to_string(&field.rest);
+        let mut id = None;
+        let mut name = format!("{:?}", field.name);
+        let mut builder = String::new();
+        for arg in field.args("param")? {
+            let value = arg.value();
+            match arg.key.as_str() {
+                "id" => id = Some(parse_id(&arg.value)?),
+                "name" => name = value,
+                "module" => builder += &format!(".module({value})"),
+                "range" => builder += &format!(".range({value})"),
+                "default" => builder += &format!(".default_value({value})"),
+                "unit" => builder += &format!(".unit({value})"),
+                "format" => builder += &format!(".format({value})"),
+                "flags" => builder += &format!(".flags({})", flags(&arg.value)),
+                key => return Err(format!("unknown parameter attribute: {key}")),
+            }
+        }
+
+        let id = id.unwrap_or_else(|| hash_id(&field.name));
+        if ids.contains(&id) {
+            return Err(format!("duplicate parameter id: {id}"));
+        }
+        ids.push(id);
+
+        inits += &format!(
+            "{field}: <{ty}>::new(\
+                ::clap_clap::id::ClapId::try_from({id}u32).unwrap(), {name}\
+            ){builder},",
+            field = field.name
+        );
+        params += &format!(
+            "{index}u32 => ::core::option::Option::Some(&self.{}),",
+            field.name
+        );
+    }
+
+    Ok(format!(
+        "impl ::core::default::Default for {name} {{
+            fn default() -> Self {{
+                Self {{ {inits} }}
+            }}
+        }}
+
+        impl {PARAMS}::ParamSet for {name} {{
+            fn count(&self) -> u32 {{
+                {count}
+            }}
+
+            fn param(&self, index: u32) -> ::core::option::Option<&dyn {PARAMS}::Param> {{
+                match index {{
+                    {params}
+                    _ => ::core::option::Option::None,
+                }}
+            }}
+        }}",
+        name = item.name,
+        count = fields.len(),
+    ))
+}
+
+fn param_enum(input: TokenStream) -> Result<String, String> {
+    let item = Item::parse(input)?;
+    let Kind::Enum = item.kind else {
+        return Err(format!("expected an enum: {}", item.name));
+    };
+    let variants = Field::parse_all(&item.body)?;
+    if variants.is_empty() {
+        return Err(format!("expected at least one variant: {}", item.name));
+    }
+
+    let mut list = String::new();
+    let mut indices = String::new();
+    let mut names = String::new();
+    for (index, variant) in variants.iter().enumerate() {
+        if matches!(variant.rest.first(), Some(TokenTree::Group(_))) {
+            return Err(format!("expected a unit variant: {}", variant.name));
+        }
+        let mut name = format!("{:?}", variant.name);
+        for arg in variant.args("param")? {
+            match arg.key.as_str() {
+                "name" => name = arg.value(),
+                key => return Err(format!("unknown variant attribute: {key}")),
+            }
+        }
+        let variant = &variant.name;
+        list += &format!("Self::{variant},");
+        indices += &format!("Self::{variant} => {index},");
+        names += &format!("Self::{variant} => {name},");
+    }
+
+    Ok(format!(
+        "impl {PARAMS}::ParamEnum for {name} {{
+            const VARIANTS: &'static [Self] = &[{list}];
+
+            fn index(&self) -> usize {{
+                match self {{ {indices} }}
+            }}
+
+            fn name(&self) -> &'static str {{
+                match self {{ {names} }}
+            }}
+        }}",
+        name = item.name,
+    ))
+}
+
+/// Parse an integer literal that is a valid `clap_id`.
+fn parse_id(tokens: &[TokenTree]) -> Result<u32, String> {
+    let literal = parse::to_string(tokens);
+    let number = literal
+        .strip_suffix("u32")
+        .or_else(|| literal.strip_suffix("u16"))
+        .or_else(|| literal.strip_suffix("u8"))
+        .unwrap_or(&literal)
+        .replace('_', "");
+    let id = match number.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => number.parse(),
+    }
+    .map_err(|_| format!("expected an integer literal as parameter id: {literal}"))?;
+    if id == u32::MAX {
+        return Err(format!("invalid parameter id: {literal}"));
+    }
+    Ok(id)
+}
+
+/// A stable id computed from the field's name with the FNV-1a hash.
+fn hash_id(name: &str) -> u32 {
+    let hash = name.bytes().fold(0x811c_9dc5u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    hash.min(u32::MAX - 1)
+}
+
+/// Flags separated with `|`. A single identifier is a variant of `InfoFlags`.
+fn flags(tokens: &[TokenTree]) -> String {
+    let mut flags = vec!["0u32".to_string()];
+    for flag in tokens.split(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '|')) {
+        match flag {
+            [TokenTree::Ident(ident)] => flags.push(format!("{PARAMS}::InfoFlags::{ident} as u32")),
+            flag => flags.push(format!("({}) as u32", parse::to_string(flag))),
+        }
+    }
+    flags.join(" | ")
+}
@@ -0,0 +1,187 @@
+//! Minimal parsing of derive input, enough for structs with named fields and
+//! enums with unit variants.
+
+use proc_macro::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+
+pub enum Kind {
+    Struct,
+    Enum,
+}
+
+/// The item that a macro is derived for.
+pub struct Item {
+    pub kind: Kind,
+    pub name: String,
+    pub body: Group,
+}
+
+impl Item {
+    pub fn parse(input: TokenStream) -> Result<Self, String> {
+        let mut tokens = input.into_iter().peekable();
+        let mut kind = None;
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(p) if p.as_char() == '#' => {
+                    tokens.next(); // The attribute's body.
+                }
+                TokenTree::Ident(ident) => match ident.to_string().as_str() {
+                    "pub" => {
+                        tokens.next_if(|t| is_group(t, Delimiter::Parenthesis));
+                    }
+                    "struct" => {
+                        kind = Some(Kind::Struct);
+                        break;
+                    }
+                    "enum" => {
+                        kind = Some(Kind::Enum);
+                        break;
+                    }
+                    "union" => return Err("unions are not supported".to_string()),
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        let kind = kind.ok_or("expected a struct or an enum")?;
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("expected a name".to_string()),
+        };
+        match tokens.next() {
+            Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => {
+                Ok(Self { kind, name, body })
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == '<' => {
+                Err(format!("generic types are not supported: {name}"))
+            }
+            _ => Err(format!("expected named fields or variants: {name}")),
+        }
+    }
+}
+
+/// A named struct field or an enum variant.
+pub struct Field {
+    pub attrs: Vec<TokenStream>,
+    pub name: String,
+    /// The field type, or the variant fields and discriminant.
+    pub rest: Vec<TokenTree>,
+}
+
+impl Field {
+    pub fn parse_all(body: &Group) -> Result<Vec<Self>, String> {
+        split(body.stream(), true)
+            .into_iter()
+            .map(Self::parse)
+            .collect()
+    }
+
+    fn parse(tokens: Vec<TokenTree>) -> Result<Self, String> {
+        let mut tokens = tokens.into_iter().peekable();
+        let mut attrs = Vec::new();
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '#' => match tokens.next() {
+                    Some(TokenTree::Group(group)) => attrs.push(group.stream()),
+                    _ => return Err("expected an attribute".to_string()),
+                },
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                    tokens.next_if(|t| is_group(t, Delimiter::Parenthesis));
+                }
+                Some(TokenTree::Ident(ident)) => {
+                    let name = ident.to_string();
+                    let mut rest: Vec<_> = tokens.collect();
+                    if matches!(rest.first(), Some(TokenTree::Punct(p)) if p.as_char() == ':') {
+                        rest.remove(0);
+                    }
+                    return Ok(Self { attrs, name, rest });
+                }
+                _ => return Err("expected a named field".to_string()),
+            }
+        }
+    }
+
+    /// The arguments of the attributes: `#[param(...)]`.
+    pub fn args(&self, attr: &str) -> Result<Vec<Arg>, String> {
+        let mut args = Vec::new();
+        for stream in &self.attrs {
+            let mut tokens = stream.clone().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(group)))
+                    if ident.to_string() == attr && group.delimiter() == Delimiter::Parenthesis =>
+                {
+                    for arg in split(group.stream(), false) {
+                        args.push(Arg::parse(arg, attr)?);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// An attribute argument: `key = value`.
+pub struct Arg {
+    pub key: String,
+    pub value: Vec<TokenTree>,
+}
+
+impl Arg {
+    fn parse(tokens: Vec<TokenTree>, attr: &str) -> Result<Self, String> {
+        let mut tokens = tokens.into_iter();
+        match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Ident(key)), Some(TokenTree::Punct(eq))) if eq.as_char() == '=' => {
+                let value: Vec<_> = tokens.collect();
+                if value.is_empty() {
+                    return Err(format!("missing value for: {key}"));
+                }
+                Ok(Self {
+                    key: key.to_string(),
+                    value,
+                })
+            }
+            _ => Err(format!("expected `key = value` in: #[{attr}(...)]")),
+        }
+    }
+
+    pub fn value(&self) -> String {
+        to_string(&self.value)
+    }
+}
+
+/// Split the tokens on commas.
+///
+/// With `generics`, the commas in between angle brackets are skipped, so that
+/// a type like: `Foo<A, B>` is not split.
+fn split(stream: TokenStream, generics: bool) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for token in stream {
+        if let TokenTree::Punct(p) = &token {
+            match p.as_char() {
+                ',' if depth == 0 => {
+                    parts.push(Vec::new());
+                    continue;
+                }
+                '<' if generics => depth += 1,
+                '>' if generics && !arrow => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            arrow = p.as_char() == '-' && p.spacing() == Spacing::Joint;
+        } else {
+            arrow = false;
+        }
+        parts.last_mut().unwrap().push(token);
+    }
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+fn is_group(token: &TokenTree, delimiter: Delimiter) -> bool {
+    matches!(token, TokenTree::Group(g) if g.delimiter() == delimiter)
+}
+
+pub fn to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
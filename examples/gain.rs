@@ -1,23 +1,20 @@
-use std::{
-    io::Write,
-    sync::{
-        Arc,
-        atomic::{AtomicU64, Ordering},
-    },
-};
+use std::{io::Write, sync::Arc};
 
-use clap_clap::prelude as clap;
+use clap_clap::{
+    ext::params::smoothing::{Smoother, Smoothing},
+    prelude as clap,
+};
 
 // A plugin must implement `Default` trait.  The plugin instance will be created
 // by the host with the call to `Gain::default()`.
 struct Gain {
-    gain: Arc<AtomicU64>,
+    params: Arc<clap::ParamStore>,
 }
 
 impl Default for Gain {
     fn default() -> Self {
         Self {
-            gain: Arc::new(AtomicU64::new(1.0f64.to_bits())),
+            params: Arc::new(clap::ParamStore::new([gain_info()])),
         }
     }
 }
@@ -32,6 +29,19 @@ impl clap::Extensions<Self> for Gain {
     }
 }
 
+fn gain_info() -> clap::ParamInfo {
+    clap::ParamInfo {
+        id: 0.into(),
+        flags: clap::params::InfoFlags::RequiresProcess as u32
+            | clap::params::InfoFlags::Automatable as u32,
+        name: "Gain".to_string(),
+        module: "gain".to_string(),
+        min_value: 0.0,
+        max_value: 2.0,
+        default_value: 1.0,
+    }
+}
+
 struct GainParam;
 
 impl clap::Params<Gain> for GainParam {
@@ -40,20 +50,11 @@ impl clap::Params<Gain> for GainParam {
     }
 
     fn get_info(_: &Gain, param_index: u32) -> Option<clap::ParamInfo> {
-        (param_index == 0).then(|| clap::ParamInfo {
-            id: 0.into(),
-            flags: clap::params::InfoFlags::RequiresProcess as u32
-                | clap::params::InfoFlags::Automatable as u32,
-            name: "Gain".to_string(),
-            module: "gain".to_string(),
-            min_value: 0.0,
-            max_value: 2.0,
-            default_value: 1.0,
-        })
+        (param_index == 0).then(gain_info)
     }
 
     fn get_value(plugin: &Gain, param_id: clap::ClapId) -> Option<f64> {
-        (param_id == 0.into()).then(|| f64::from_bits(plugin.gain.load(Ordering::Relaxed)))
+        plugin.params.get(param_id)
     }
 
     fn value_to_text(
@@ -73,13 +74,20 @@ impl clap::Params<Gain> for GainParam {
         Ok(param_value_text.parse()?)
     }
 
-    fn flush_inactive(_: &Gain, _: &clap::InputEvents, _: &clap::OutputEvents) {}
+    fn flush_inactive(
+        plugin: &Gain,
+        in_events: &clap::InputEvents,
+        out_events: &mut clap::OutputEvents,
+    ) {
+        plugin.params.flush(in_events, out_events);
+    }
 
     fn flush(
-        _: &<Gain as clap::Plugin>::AudioThread,
-        _: &clap::InputEvents,
-        _: &clap::OutputEvents,
+        audio_thread: &<Gain as clap::Plugin>::AudioThread,
+        in_events: &clap::InputEvents,
+        out_events: &mut clap::OutputEvents,
     ) {
+        audio_thread.params.flush(in_events, out_events);
     }
 }
 
@@ -104,22 +112,36 @@ impl clap::Plugin for Gain {
     }
 
     /// Start the audio thread.
-    fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<AudioThread, clap::Error> {
+    fn activate(&mut self, sample_rate: f64, _: u32, _: u32) -> Result<AudioThread, clap::Error> {
+        // Smooth out parameter changes in 10 ms.
+        let mut gain = Smoother::new(Smoothing::Linear, 0.01, sample_rate);
+        gain.reset(self.params.get(0.into()).unwrap_or(1.0));
         Ok(AudioThread {
-            gain: self.gain.clone(),
-            smoothed: Smooth::default(),
+            params: self.params.clone(),
+            gain,
         })
     }
 }
 
 struct AudioThread {
-    gain: Arc<AtomicU64>,
-    smoothed: Smooth,
+    params: Arc<clap::ParamStore>,
+    gain: Smoother,
+}
+
+impl AudioThread {
+    /// Retarget the smoother if the gain in the store has changed, e.g. by a
+    /// parameter event, or by a flush while the plugin wasn't processing.
+    fn update_gain(&mut self) {
+        let gain = self.params.get(0.into()).unwrap_or(1.0);
+        if gain != self.gain.target() {
+            self.gain.set_target(gain);
+        }
+    }
 }
 
 impl clap::AudioThread<Gain> for AudioThread {
     fn process(&mut self, process: &mut clap::Process) -> Result<clap::Status, clap::Error> {
-        let mut gain = f64::from_bits(self.gain.load(Ordering::Relaxed));
+        self.update_gain();
 
         let nframes = process.frames_count();
         let nev = process.in_events().size();
@@ -138,8 +160,8 @@ impl clap::AudioThread<Gain> for AudioThread {
                     }
 
                     if let Ok(param_value) = header.param_value() {
-                        gain = param_value.value();
-                        self.gain.store(gain.to_bits(), Ordering::Release);
+                        self.params.apply_param_value(&param_value);
+                        self.update_gain();
                     }
                 }
 
@@ -153,15 +175,14 @@ impl clap::AudioThread<Gain> for AudioThread {
 
             {
                 let i = i as usize;
-                let gain = gain as f32;
 
                 // Get the input signal from the main input port.
                 let in_l = process.audio_inputs(0).data32(0)[i];
                 let in_r = process.audio_inputs(0).data32(1)[i];
 
-                let smoothed = self.smoothed.tick(gain);
-                let out_l = in_l * smoothed;
-                let out_r = in_r * smoothed;
+                let gain = self.gain.tick() as f32;
+                let out_l = in_l * gain;
+                let out_r = in_r * gain;
 
                 // Write the audio signal to the main output port.
                 process.audio_outputs(0).data32(0)[i] = out_l;
@@ -176,29 +197,3 @@ impl clap::AudioThread<Gain> for AudioThread {
 
 // Export clap_entry symbols and build a plugin factory.
 clap::entry!(Gain);
-
-// A one-pole low pass filter to smooth out parameter changes.
-#[derive(Debug, Clone)]
-pub struct Smooth {
-    b0: f32,
-    a1: f32,
-    y1: f32,
-}
-
-impl Smooth {
-    fn tick(&mut self, sample: f32) -> f32 {
-        let y0 = sample * self.b0 - self.y1 * self.a1;
-        self.y1 = y0;
-        y0 * (1.0 + self.a1)
-    }
-}
-
-impl Default for Smooth {
-    fn default() -> Self {
-        Smooth {
-            b0: 1.0,
-            a1: -0.999,
-            y1: 0.0,
-        }
-    }
-}
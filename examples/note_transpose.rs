@@ -70,25 +70,22 @@ impl clap::AudioThread<Self> for Transpose {
         let in_events = process.in_events();
         let mut out_events = process.out_events();
 
-        for i in 0..in_events.size() {
-            let header = in_events.get(i);
-
-            if let Ok(note) = header.note() {
-                use clap::EventBuilder;
-                let n = note.update().key(note.key() + 7); // Transpose notes by a perfect fifth.
-                let _ = out_events.try_push(n.event());
-            }
-
-            if let Ok(note_expr) = header.note_expression() {
-                let _ = out_events.try_push(note_expr);
-            }
-
-            if let Ok(midi) = header.midi() {
-                let _ = out_events.try_push(midi);
-            }
-
-            if let Ok(midi2) = header.midi2() {
-                let _ = out_events.try_push(midi2);
+        for event in &in_events {
+            match event {
+                clap::EventRef::NoteOn(note)
+                | clap::EventRef::NoteOff(note)
+                | clap::EventRef::NoteChoke(note)
+                | clap::EventRef::NoteEnd(note) => {
+                    use clap::EventBuilder;
+                    let n = note.update().key(note.key() + 7); // Transpose notes by a perfect fifth.
+                    let _ = out_events.try_push(n.event());
+                }
+                clap::EventRef::NoteExpression(_)
+                | clap::EventRef::Midi(_)
+                | clap::EventRef::Midi2(_) => {
+                    let _ = out_events.try_push(event);
+                }
+                _ => {}
             }
         }
 
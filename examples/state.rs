@@ -1,10 +1,6 @@
 use std::{
     io::{Read, Write},
-    mem,
-    sync::{
-        Arc,
-        atomic::{AtomicU64, Ordering},
-    },
+    sync::Arc,
 };
 
 use clap_clap::prelude as clap;
@@ -16,21 +12,34 @@ const NUM_BYTES: usize = NUM_PARAMS * 8; // Parameters have type: f64.
 // by the host with the call to `State::default()`.
 struct Example {
     // Three independent parameters to save and load as the plugin's state.
-    state: Arc<[AtomicU64; NUM_PARAMS]>,
+    state: Arc<clap::ParamStore>,
 }
 
 impl Default for Example {
     fn default() -> Self {
+        let info = (0..NUM_PARAMS as u32).filter_map(param_info);
         Self {
-            state: Arc::new([
-                AtomicU64::new(0.0f64.to_bits()),
-                AtomicU64::new(0.0f64.to_bits()),
-                AtomicU64::new(0.0f64.to_bits()),
-            ]),
+            state: Arc::new(clap::ParamStore::new(info)),
         }
     }
 }
 
+fn param_info(param_index: u32) -> Option<clap::ParamInfo> {
+    (param_index < NUM_PARAMS as u32).then(|| {
+        clap::ParamInfo {
+            id: clap::ClapId::from(param_index as u16),
+            flags: clap::params::InfoFlags::RequiresProcess as u32
+                // Some DAWs, e.g. Bitwig, display only automatable parameters.
+                | clap::params::InfoFlags::Automatable as u32,
+            name: format!("Param {param_index}"),
+            module: format!("{param_index}/param"),
+            min_value: 0.0,
+            max_value: 1.0,
+            default_value: 0.0,
+        }
+    })
+}
+
 impl clap::Extensions<Self> for Example {
     fn params() -> Option<impl clap::Params<Self>> {
         Some(ExampleParams)
@@ -49,24 +58,11 @@ impl clap::Params<Example> for ExampleParams {
     }
 
     fn get_info(_: &Example, param_index: u32) -> Option<clap::ParamInfo> {
-        (param_index < NUM_PARAMS as u32).then(|| {
-            clap::ParamInfo {
-                id: clap::ClapId::from(param_index as u16),
-                flags: clap::params::InfoFlags::RequiresProcess as u32
-                    // Some DAWs, e.g. Bitwig, display only automatable parameters.
-                    | clap::params::InfoFlags::Automatable as u32,
-                name: format!("Param {param_index}"),
-                module: format!("{param_index}/param"),
-                min_value: 0.0,
-                max_value: 1.0,
-                default_value: 0.0,
-            }
-        })
+        param_info(param_index)
     }
 
     fn get_value(plugin: &Example, param_id: clap::ClapId) -> Option<f64> {
-        let id: usize = param_id.into();
-        (id < NUM_PARAMS).then(|| f64::from_bits(plugin.state[id].load(Ordering::Relaxed)))
+        plugin.state.get(param_id)
     }
 
     fn value_to_text(
@@ -86,13 +82,20 @@ impl clap::Params<Example> for ExampleParams {
         Ok(param_value_text.parse()?)
     }
 
-    fn flush_inactive(_: &Example, _: &clap::InputEvents, _: &clap::OutputEvents) {}
+    fn flush_inactive(
+        plugin: &Example,
+        in_events: &clap::InputEvents,
+        out_events: &mut clap::OutputEvents,
+    ) {
+        plugin.state.flush(in_events, out_events);
+    }
 
     fn flush(
-        _: &<Example as clap::Plugin>::AudioThread,
-        _: &clap::InputEvents,
-        _: &clap::OutputEvents,
+        audio_thread: &<Example as clap::Plugin>::AudioThread,
+        in_events: &clap::InputEvents,
+        out_events: &mut clap::OutputEvents,
     ) {
+        audio_thread.state.flush(in_events, out_events);
     }
 }
 
@@ -100,9 +103,11 @@ struct ExampleState;
 
 impl clap::State<Example> for ExampleState {
     fn save(plugin: &Example, stream: &mut clap::OStream) -> Result<(), clap::Error> {
-        let buf: [u64; NUM_PARAMS] =
-            std::array::from_fn(|i| plugin.state[i].load(Ordering::Acquire));
-        let buf: [u8; NUM_BYTES] = unsafe { mem::transmute(buf) };
+        let buf: Vec<u8> = plugin
+            .state
+            .iter()
+            .flat_map(|(_, value)| value.to_le_bytes())
+            .collect();
         stream.write_all(&buf).map_err(Into::into)
     }
 
@@ -110,9 +115,9 @@ impl clap::State<Example> for ExampleState {
         let mut buf: [u8; NUM_BYTES] = [0; NUM_BYTES];
         stream.read_exact(&mut buf)?;
 
-        let buf: [u64; NUM_PARAMS] = unsafe { mem::transmute(buf) };
-        for i in 0..NUM_PARAMS {
-            plugin.state[i].store(buf[i], Ordering::Release);
+        for ((id, _), bytes) in plugin.state.iter().zip(buf.chunks_exact(8)) {
+            let value = f64::from_le_bytes(bytes.try_into().unwrap());
+            plugin.state.set(id, value)?;
         }
 
         Ok(())
@@ -148,25 +153,12 @@ impl clap::Plugin for Example {
 }
 
 struct AudioThread {
-    state: Arc<[AtomicU64; NUM_PARAMS]>,
+    state: Arc<clap::ParamStore>,
 }
 
 impl clap::AudioThread<Example> for AudioThread {
     fn process(&mut self, process: &mut clap::Process) -> Result<clap::Status, clap::Error> {
-        let in_events = process.in_events();
-
-        for i in 0..in_events.size() {
-            let header = in_events.get(i);
-
-            if let Ok(param) = header.param_value() {
-                let value = param.value();
-                let id: usize = param.param_id().into();
-
-                if id < NUM_PARAMS {
-                    self.state[id].store(value.to_bits(), Ordering::Release);
-                }
-            }
-        }
+        self.state.apply(&process.in_events());
         Ok(clap::Continue)
     }
 }
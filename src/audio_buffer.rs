@@ -6,6 +6,8 @@ use crate::{ffi::clap_audio_buffer, prelude::Process};
 pub struct AudioBuffer<'a> {
     process: &'a Process,
     clap_audio_buffer: *const clap_audio_buffer,
+    offset: u32,
+    frames_count: u32,
 }
 
 impl<'a> AudioBuffer<'a> {
@@ -20,6 +22,26 @@ impl<'a> AudioBuffer<'a> {
         Self {
             process,
             clap_audio_buffer,
+            offset: 0,
+            frames_count: process.frames_count(),
+        }
+    }
+
+    /// Restrict the buffer to the frames: `offset..offset + frames_count`.
+    ///
+    /// # Safety
+    ///
+    /// The range must lie within the current frame range of the buffer.
+    pub(crate) const unsafe fn slice_frames_unchecked(
+        self,
+        offset: u32,
+        frames_count: u32,
+    ) -> Self {
+        debug_assert!(offset + frames_count <= self.frames_count);
+        Self {
+            offset: self.offset + offset,
+            frames_count,
+            ..self
         }
     }
 
@@ -40,8 +62,11 @@ impl<'a> AudioBuffer<'a> {
 
         debug_assert!((self.process.frames_count() as u64) < usize::MAX as u64);
         // SAFETY: The CLAP host guarantees that the channel is at
-        // least process.frames_count() long.
-        unsafe { &*slice_from_raw_parts(chan, self.process.frames_count() as usize) }
+        // least process.frames_count() long, and by construction the frame range
+        // of the buffer lies within it.
+        unsafe {
+            &*slice_from_raw_parts(chan.add(self.offset as usize), self.frames_count as usize)
+        }
     }
 
     /// # Panic
@@ -69,8 +94,11 @@ impl<'a> AudioBuffer<'a> {
 
         debug_assert!((self.process.frames_count() as u64) < usize::MAX as u64);
         // SAFETY: The CLAP host guarantees that the channel is at
-        // least process.frames_count() long.
-        unsafe { &*slice_from_raw_parts(chan, self.process.frames_count() as usize) }
+        // least process.frames_count() long, and by construction the frame range
+        // of the buffer lies within it.
+        unsafe {
+            &*slice_from_raw_parts(chan.add(self.offset as usize), self.frames_count as usize)
+        }
     }
 
     /// # Panic
@@ -87,6 +115,11 @@ impl<'a> AudioBuffer<'a> {
         unsafe { self.data64_unchecked(channel) }
     }
 
+    /// The number of frames in each channel.
+    pub const fn frames_count(&self) -> u32 {
+        self.frames_count
+    }
+
     pub const fn channel_count(&self) -> u32 {
         self.clap_audio_buffer().channel_count
     }
@@ -104,6 +137,8 @@ impl<'a> AudioBuffer<'a> {
 pub struct AudioBufferMut<'a> {
     process: &'a mut Process,
     clap_audio_buffer: NonNull<clap_audio_buffer>,
+    offset: u32,
+    frames_count: u32,
 }
 
 impl<'a> AudioBufferMut<'a> {
@@ -114,9 +149,30 @@ impl<'a> AudioBufferMut<'a> {
         process: &'a mut Process,
         clap_audio_buffer: NonNull<clap_audio_buffer>,
     ) -> Self {
+        let frames_count = process.frames_count();
         Self {
             process,
             clap_audio_buffer,
+            offset: 0,
+            frames_count,
+        }
+    }
+
+    /// Restrict the buffer to the frames: `offset..offset + frames_count`.
+    ///
+    /// # Safety
+    ///
+    /// The range must lie within the current frame range of the buffer.
+    pub(crate) const unsafe fn slice_frames_unchecked(
+        self,
+        offset: u32,
+        frames_count: u32,
+    ) -> Self {
+        debug_assert!(offset + frames_count <= self.frames_count);
+        Self {
+            offset: self.offset + offset,
+            frames_count,
+            ..self
         }
     }
 
@@ -144,8 +200,14 @@ impl<'a> AudioBufferMut<'a> {
 
         debug_assert!((self.process.frames_count() as u64) < usize::MAX as u64);
         // SAFETY: The CLAP host guarantees that the channel is at
-        // least process.frames_count() long.
-        unsafe { &mut *slice_from_raw_parts_mut(chan, self.process.frames_count() as usize) }
+        // least process.frames_count() long, and by construction the frame range
+        // of the buffer lies within it.
+        unsafe {
+            &mut *slice_from_raw_parts_mut(
+                chan.add(self.offset as usize),
+                self.frames_count as usize,
+            )
+        }
     }
 
     /// # Panic
@@ -173,8 +235,14 @@ impl<'a> AudioBufferMut<'a> {
 
         debug_assert!((self.process.frames_count() as u64) < usize::MAX as u64);
         // SAFETY: The CLAP host guarantees that the channel is at
-        // least process.frames_count() long.
-        unsafe { &mut *slice_from_raw_parts_mut(chan, self.process.frames_count() as usize) }
+        // least process.frames_count() long, and by construction the frame range
+        // of the buffer lies within it.
+        unsafe {
+            &mut *slice_from_raw_parts_mut(
+                chan.add(self.offset as usize),
+                self.frames_count as usize,
+            )
+        }
     }
 
     /// # Panic
@@ -191,6 +259,11 @@ impl<'a> AudioBufferMut<'a> {
         unsafe { self.data64_unchecked(channel) }
     }
 
+    /// The number of frames in each channel.
+    pub const fn frames_count(&self) -> u32 {
+        self.frames_count
+    }
+
     pub const fn channel_count(&self) -> u32 {
         self.clap_audio_buffer().channel_count
     }
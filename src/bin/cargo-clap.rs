@@ -0,0 +1,379 @@
+//! Bundle CLAP plugins built with Cargo.
+//!
+//! Install with `cargo install clap-clap`, and use as a Cargo subcommand:
+//!
+//! ```text
+//! cargo clap bundle [--example <NAME>] [--release] [--install] [--out-dir <DIR>]
+//!                   [-- <CARGO BUILD ARGS>...]
+//! cargo clap info <FILE.clap>...
+//! ```
+//!
+//! The command: `bundle` builds the cdylib target (the package's library, or
+//! the given example), copies it to `<name>.clap` next to the compiled library
+//! (or into `--out-dir`), and prints the plugin descriptors that it finds by
+//! calling the exported `clap_entry`. With `--install`, the bundle is also
+//! copied to the user's CLAP directory.
+
+use std::{
+    env,
+    ffi::OsString,
+    fmt::{Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+};
+
+use clap_clap::hosting::{self, Bundle};
+
+const USAGE: &str = "\
+Bundle CLAP plugins built with Cargo.
+
+Usage:
+    cargo clap bundle [OPTIONS] [-- <CARGO BUILD ARGS>...]
+    cargo clap info <FILE.clap>...
+
+Options for `bundle`:
+    --example <NAME>    Bundle the example instead of the package's library
+    -p, --package <SPEC>
+                        Package to build
+    --release           Build with the release profile
+    --install           Copy the bundle into the user's CLAP directory
+    --out-dir <DIR>     Directory to put the bundle in
+    -h, --help          Print help
+";
+
+fn main() -> ExitCode {
+    let mut args: Vec<OsString> = env::args_os().skip(1).collect();
+    // Invoked as a Cargo subcommand: `cargo clap ...` runs `cargo-clap clap ...`
+    if args.first().is_some_and(|arg| arg == "clap") {
+        args.remove(0);
+    }
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<OsString>) -> Result<(), Error> {
+    let mut args = args.into_iter();
+    match args.next().as_ref().and_then(|arg| arg.to_str()) {
+        Some("bundle") => bundle(BundleArgs::parse(args)?),
+        Some("info") => {
+            let paths: Vec<_> = args.map(PathBuf::from).collect();
+            if paths.is_empty() {
+                return Err(Error::Usage("missing file to inspect".to_string()));
+            }
+            paths.iter().try_for_each(|path| info(path))
+        }
+        Some("-h" | "--help" | "help") => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        Some(cmd) => Err(Error::Usage(format!("unknown command: {cmd}"))),
+        None => Err(Error::Usage("missing command".to_string())),
+    }
+}
+
+#[derive(Debug, Default)]
+struct BundleArgs {
+    example: Option<String>,
+    package: Option<String>,
+    release: bool,
+    install: bool,
+    out_dir: Option<PathBuf>,
+    cargo_args: Vec<OsString>,
+}
+
+impl BundleArgs {
+    fn parse(mut args: impl Iterator<Item = OsString>) -> Result<Self, Error> {
+        let mut parsed = Self::default();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| Error::Usage(format!("missing value for: {name}")))
+            };
+            match arg.to_str() {
+                Some("--example") => parsed.example = Some(string(value("--example")?)?),
+                Some("-p" | "--package") => parsed.package = Some(string(value("--package")?)?),
+                Some("--release") => parsed.release = true,
+                Some("--install") => parsed.install = true,
+                Some("--out-dir") => parsed.out_dir = Some(value("--out-dir")?.into()),
+                Some("-h" | "--help") => {
+                    print!("{USAGE}");
+                    std::process::exit(0);
+                }
+                Some("--") => {
+                    parsed.cargo_args.extend(args);
+                    break;
+                }
+                _ => {
+                    return Err(Error::Usage(format!(
+                        "unknown argument: {}",
+                        arg.to_string_lossy()
+                    )));
+                }
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn string(arg: OsString) -> Result<String, Error> {
+    arg.into_string()
+        .map_err(|arg| Error::Usage(format!("invalid UTF-8: {}", arg.to_string_lossy())))
+}
+
+fn bundle(args: BundleArgs) -> Result<(), Error> {
+    let (name, library) = build(&args)?;
+
+    let out_dir = match &args.out_dir {
+        Some(dir) => dir.clone(),
+        None => library.parent().map(Path::to_path_buf).unwrap_or_default(),
+    };
+    let bundle = out_dir.join(format!("{name}.clap"));
+    fs::create_dir_all(&out_dir)?;
+    make_bundle(&name, &library, &bundle)?;
+    eprintln!("    Bundled {}", bundle.display());
+
+    info(&bundle)?;
+
+    if args.install {
+        let dir = install_dir()?;
+        let installed = dir.join(format!("{name}.clap"));
+        fs::create_dir_all(&dir)?;
+        make_bundle(&name, &library, &installed)?;
+        eprintln!("  Installed {}", installed.display());
+    }
+    Ok(())
+}
+
+/// Build the cdylib and return the name of the target and the path to the
+/// library.
+fn build(args: &BundleArgs) -> Result<(String, PathBuf), Error> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut cmd = Command::new(cargo);
+    cmd.args(["build", "--message-format=json-render-diagnostics"]);
+    match &args.example {
+        Some(example) => cmd.args(["--example", example]),
+        None => cmd.arg("--lib"),
+    };
+    if let Some(package) = &args.package {
+        cmd.args(["--package", package]);
+    }
+    if args.release {
+        cmd.arg("--release");
+    }
+    cmd.args(&args.cargo_args);
+
+    let output = cmd.stderr(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(Error::Build);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut artifacts = stdout
+        .lines()
+        .filter_map(cdylib_artifact)
+        .filter(|(name, _)| args.example.as_ref().is_none_or(|example| name == example));
+    match (artifacts.next(), artifacts.next()) {
+        (Some(artifact), None) => Ok(artifact),
+        (None, _) => Err(Error::NoCdylib),
+        (Some(_), Some(_)) => Err(Error::Usage(
+            "more than one cdylib built, select one with: --package".to_string(),
+        )),
+    }
+}
+
+/// Extract the target name and the path to the library from a Cargo message
+/// about a compiled cdylib.
+fn cdylib_artifact(message: &str) -> Option<(String, PathBuf)> {
+    if !message.contains(r#""reason":"compiler-artifact""#) {
+        return None;
+    }
+    let target = &message[message.find(r#""target":{"#)?..];
+    let crate_types = json_strings(target, "crate_types")?;
+    if !crate_types.iter().any(|t| t == "cdylib") {
+        return None;
+    }
+    let name = json_string(target, "name")?;
+    let library = json_strings(message, "filenames")?
+        .into_iter()
+        .find(|file| file.ends_with(env::consts::DLL_SUFFIX))?;
+    Some((name, library.into()))
+}
+
+/// The first string value of the key, e.g. `"key":"value"`.
+fn json_string(json: &str, key: &str) -> Option<String> {
+    let start = json.find(&format!(r#""{key}":""#))? + key.len() + 4;
+    parse_json_string(&json[start..]).map(|(s, _)| s)
+}
+
+/// The first array of strings of the key, e.g. `"key":["a","b"]`.
+fn json_strings(json: &str, key: &str) -> Option<Vec<String>> {
+    let start = json.find(&format!(r#""{key}":["#))? + key.len() + 4;
+    let mut rest = &json[start..];
+    let mut strings = Vec::new();
+    loop {
+        match rest.chars().next()? {
+            ']' => return Some(strings),
+            ',' => rest = &rest[1..],
+            '"' => {
+                let (s, len) = parse_json_string(&rest[1..])?;
+                strings.push(s);
+                rest = &rest[1 + len..];
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Parse a JSON string up to and including the closing quote. Return the
+/// string and the number of bytes parsed.
+fn parse_json_string(json: &str) -> Option<(String, usize)> {
+    let mut s = String::new();
+    let mut chars = json.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((s, i + 1)),
+            '\\' => match chars.next()?.1 {
+                'n' => s.push('\n'),
+                't' => s.push('\t'),
+                'r' => s.push('\r'),
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                'u' => {
+                    let hex: String = (0..4)
+                        .filter_map(|_| chars.next().map(|(_, c)| c))
+                        .collect();
+                    s.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                c => s.push(c),
+            },
+            c => s.push(c),
+        }
+    }
+    None
+}
+
+/// Copy the library into a CLAP bundle.
+///
+/// On macOS, the bundle is a directory with the library and `Info.plist`.
+/// Elsewhere, it is the library itself, renamed.
+fn make_bundle(name: &str, library: &Path, bundle: &Path) -> Result<(), Error> {
+    if cfg!(target_os = "macos") {
+        let contents = bundle.join("Contents");
+        fs::create_dir_all(contents.join("MacOS"))?;
+        fs::copy(library, contents.join("MacOS").join(name))?;
+        fs::write(contents.join("Info.plist"), info_plist(name))?;
+        fs::write(contents.join("PkgInfo"), "BNDL????")?;
+    } else {
+        fs::copy(library, bundle)?;
+    }
+    Ok(())
+}
+
+fn info_plist(name: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleDevelopmentRegion</key>
+    <string>English</string>
+    <key>CFBundleExecutable</key>
+    <string>{name}</string>
+    <key>CFBundleIdentifier</key>
+    <string>{name}</string>
+    <key>CFBundleName</key>
+    <string>{name}</string>
+    <key>CFBundlePackageType</key>
+    <string>BNDL</string>
+    <key>CFBundleSignature</key>
+    <string>????</string>
+</dict>
+</plist>
+"#
+    )
+}
+
+/// The directory where CLAP hosts look for the user's plugins.
+fn install_dir() -> Result<PathBuf, Error> {
+    let var = |name| env::var_os(name).ok_or(Error::Env(name));
+    Ok(if cfg!(target_os = "windows") {
+        PathBuf::from(var("LOCALAPPDATA")?).join(r"Programs\Common\CLAP")
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(var("HOME")?).join("Library/Audio/Plug-Ins/CLAP")
+    } else {
+        PathBuf::from(var("HOME")?).join(".clap")
+    })
+}
+
+/// Print the descriptors of the plugins in the bundle.
+fn info(path: &Path) -> Result<(), Error> {
+    let bundle = Bundle::load(path)?;
+    let (major, minor, revision) = {
+        let version = bundle.clap_version();
+        (version.major, version.minor, version.revision)
+    };
+    println!("{} (CLAP {major}.{minor}.{revision})", path.display());
+    for descriptor in bundle.descriptors() {
+        println!("  {}", descriptor.id);
+        println!("    name: {}", descriptor.name);
+        for (key, value) in [
+            ("vendor", &descriptor.vendor),
+            ("version", &descriptor.version),
+            ("url", &descriptor.url),
+            ("description", &descriptor.description),
+        ] {
+            if !value.is_empty() {
+                println!("    {key}: {value}");
+            }
+        }
+        if !descriptor.features.is_empty() {
+            println!("    features: {}", descriptor.features.join(", "));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+enum Error {
+    Build,
+    Env(&'static str),
+    Hosting(hosting::Error),
+    IO(io::Error),
+    NoCdylib,
+    Usage(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Build => write!(f, "cargo build failed"),
+            Error::Env(name) => write!(f, "environment variable not set: {name}"),
+            Error::Hosting(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "I/O: {e}"),
+            Error::NoCdylib => write!(
+                f,
+                "no cdylib built; set `crate-type = [\"cdylib\"]` for the target"
+            ),
+            Error::Usage(msg) => write!(f, "{msg}\n\n{USAGE}"),
+        }
+    }
+}
+
+impl From<hosting::Error> for Error {
+    fn from(value: hosting::Error) -> Self {
+        Self::Hosting(value)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::IO(value)
+    }
+}
@@ -0,0 +1,55 @@
+use crate::ffi::clap_color;
+
+/// A type that corresponds to CLAP's `clap_color`.
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::color::Color;
+/// let color = Color::rgb(0xff, 0x80, 0x00);
+///
+/// assert_eq!(color.alpha, 0xff);
+/// assert_ne!(color, Color::TRANSPARENT);
+/// ```
+#[derive(Default, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Color {
+    pub alpha: u8,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub const TRANSPARENT: Self = Self::new(0, 0, 0, 0);
+
+    pub const fn new(alpha: u8, red: u8, green: u8, blue: u8) -> Self {
+        Self {
+            alpha,
+            red,
+            green,
+            blue,
+        }
+    }
+
+    /// Create an opaque color.
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::new(u8::MAX, red, green, blue)
+    }
+}
+
+impl From<clap_color> for Color {
+    fn from(value: clap_color) -> Self {
+        Self::new(value.alpha, value.red, value.green, value.blue)
+    }
+}
+
+impl From<Color> for clap_color {
+    fn from(value: Color) -> Self {
+        Self {
+            alpha: value.alpha,
+            red: value.red,
+            green: value.green,
+            blue: value.blue,
+        }
+    }
+}
@@ -2,30 +2,37 @@
 
 use std::{
     fmt::{Display, Formatter},
+    marker::PhantomData,
     mem,
-    ptr::{null_mut, slice_from_raw_parts},
+    ptr::{null, null_mut, slice_from_raw_parts},
 };
 
 use crate::{
     ffi::{
         CLAP_CORE_EVENT_SPACE_ID, CLAP_EVENT_DONT_RECORD, CLAP_EVENT_IS_LIVE, CLAP_EVENT_MIDI,
-        CLAP_EVENT_MIDI2, CLAP_EVENT_NOTE_CHOKE, CLAP_EVENT_NOTE_END, CLAP_EVENT_NOTE_EXPRESSION,
-        CLAP_EVENT_NOTE_OFF, CLAP_EVENT_NOTE_ON, CLAP_EVENT_PARAM_MOD, CLAP_EVENT_PARAM_VALUE,
-        CLAP_EVENT_TRANSPORT, CLAP_NOTE_EXPRESSION_BRIGHTNESS, CLAP_NOTE_EXPRESSION_EXPRESSION,
-        CLAP_NOTE_EXPRESSION_PAN, CLAP_NOTE_EXPRESSION_PRESSURE, CLAP_NOTE_EXPRESSION_TUNING,
-        CLAP_NOTE_EXPRESSION_VIBRATO, CLAP_NOTE_EXPRESSION_VOLUME,
+        CLAP_EVENT_MIDI_SYSEX, CLAP_EVENT_MIDI2, CLAP_EVENT_NOTE_CHOKE, CLAP_EVENT_NOTE_END,
+        CLAP_EVENT_NOTE_EXPRESSION, CLAP_EVENT_NOTE_OFF, CLAP_EVENT_NOTE_ON,
+        CLAP_EVENT_PARAM_GESTURE_BEGIN, CLAP_EVENT_PARAM_GESTURE_END, CLAP_EVENT_PARAM_MOD,
+        CLAP_EVENT_PARAM_VALUE, CLAP_EVENT_TRANSPORT, CLAP_NOTE_EXPRESSION_BRIGHTNESS,
+        CLAP_NOTE_EXPRESSION_EXPRESSION, CLAP_NOTE_EXPRESSION_PAN, CLAP_NOTE_EXPRESSION_PRESSURE,
+        CLAP_NOTE_EXPRESSION_TUNING, CLAP_NOTE_EXPRESSION_VIBRATO, CLAP_NOTE_EXPRESSION_VOLUME,
         CLAP_TRANSPORT_HAS_BEATS_TIMELINE, CLAP_TRANSPORT_HAS_SECONDS_TIMELINE,
         CLAP_TRANSPORT_HAS_TEMPO, CLAP_TRANSPORT_HAS_TIME_SIGNATURE, CLAP_TRANSPORT_IS_LOOP_ACTIVE,
         CLAP_TRANSPORT_IS_PLAYING, CLAP_TRANSPORT_IS_RECORDING, CLAP_TRANSPORT_IS_WITHIN_PRE_ROLL,
-        clap_event_header, clap_event_midi, clap_event_midi2, clap_event_note,
-        clap_event_note_expression, clap_event_param_mod, clap_event_param_value,
-        clap_event_transport, clap_input_events, clap_note_expression, clap_output_events,
+        clap_event_header, clap_event_midi, clap_event_midi_sysex, clap_event_midi2,
+        clap_event_note, clap_event_note_expression, clap_event_param_gesture,
+        clap_event_param_mod, clap_event_param_value, clap_event_transport, clap_input_events,
+        clap_note_expression, clap_output_events,
     },
     fixedpoint::{BeatTime, SecTime},
     id::ClapId,
     impl_flags_u32,
 };
 
+mod queue;
+
+pub use queue::{DEFAULT_CAPACITY, DEFAULT_MAX_EVENTS, EventQueue};
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u32)]
 pub enum EventFlags {
@@ -41,6 +48,45 @@ pub enum EventFlags {
 
 impl_flags_u32!(EventFlags);
 
+/// Identifies an event space.
+///
+/// Events from spaces other than the core one have their ids assigned by the
+/// host, see: [`HostEventRegistry`].
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::events::EventSpaceId;
+/// assert_eq!(u16::from(EventSpaceId::CORE), 0);
+/// assert_eq!(EventSpaceId::try_from(12).map(u16::from), Ok(12));
+/// assert!(EventSpaceId::try_from(u16::MAX).is_err());
+/// ```
+///
+/// [`HostEventRegistry`]: crate::ext::event_registry::HostEventRegistry
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct EventSpaceId(u16);
+
+impl EventSpaceId {
+    /// The space of the events defined by CLAP.
+    pub const CORE: Self = Self(CLAP_CORE_EVENT_SPACE_ID);
+}
+
+impl TryFrom<u16> for EventSpaceId {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        (value != u16::MAX)
+            .then_some(Self(value))
+            .ok_or(Error::InvalidSpaceId)
+    }
+}
+
+impl From<EventSpaceId> for u16 {
+    fn from(value: EventSpaceId) -> Self {
+        value.0
+    }
+}
+
 macro_rules! impl_event_cast_methods {
     ($name:tt, $name_unchecked:tt, $type:ty, $cast_type:ty, $clap_id:ident $(,)?) => {
         /// # Safety
@@ -132,11 +178,11 @@ impl Header {
     /// The caller must ensure that this `Header` has correct
     /// size and type to contain the header and the payload of event of the
     /// returned type: `note`.
-    pub const unsafe fn note_unchecked(&self) -> Note {
+    pub const unsafe fn note_unchecked(&self) -> Note<'_> {
         unsafe { Note::new_unchecked(self) }
     }
 
-    pub const fn note(&self) -> Result<Note, Error> {
+    pub const fn note(&self) -> Result<Note<'_>, Error> {
         if self.r#type() != CLAP_EVENT_NOTE_ON as u16
             && self.r#type() != CLAP_EVENT_NOTE_OFF as u16
             && self.r#type() != CLAP_EVENT_NOTE_CHOKE as u16
@@ -152,10 +198,31 @@ impl Header {
         Ok(unsafe { Note::new_unchecked(self) })
     }
 
+    /// Cast to an event with a custom payload.
+    ///
+    /// The event must belong to the space `space_id` and have the type
+    /// `T::TYPE`. Events from the core space are never cast to custom ones.
+    pub const fn custom<T: CustomPayload>(
+        &self,
+        space_id: EventSpaceId,
+    ) -> Result<Custom<'_, T>, Error> {
+        if self.space_id() != space_id.0 || space_id.0 == CLAP_CORE_EVENT_SPACE_ID {
+            return Err(Error::OtherSpace(self.space_id()));
+        }
+        if self.r#type() != T::TYPE {
+            return Err(Error::OtherType(self.r#type()));
+        }
+        if self.size() != size_of::<clap_event_custom<T>>() as u32 {
+            return Err(Error::PayloadSize(self.size()));
+        }
+        // SAFETY: We just checked if `self` is an event of type to be cast to.
+        Ok(unsafe { Custom::new_unchecked(self) })
+    }
+
     impl_event_cast_methods!(
         note_expression,
         note_expression_unchecked,
-        NoteExpression,
+        NoteExpression<'_>,
         clap_event_note_expression,
         CLAP_EVENT_NOTE_EXPRESSION
     );
@@ -163,7 +230,7 @@ impl Header {
     impl_event_cast_methods!(
         param_value,
         param_value_unchecked,
-        ParamValue,
+        ParamValue<'_>,
         clap_event_param_value,
         CLAP_EVENT_PARAM_VALUE
     );
@@ -171,25 +238,61 @@ impl Header {
     impl_event_cast_methods!(
         param_mod,
         param_mod_unchecked,
-        ParamMod,
+        ParamMod<'_>,
         clap_event_param_mod,
         CLAP_EVENT_PARAM_MOD
     );
 
+    /// # Safety
+    /// The caller must ensure that this `Header` has correct
+    /// size and type to contain the header and the payload of event of the
+    /// returned type: `param_gesture`.
+    pub const unsafe fn param_gesture_unchecked(&self) -> ParamGesture<'_> {
+        unsafe { ParamGesture::new_unchecked(self) }
+    }
+
+    pub const fn param_gesture(&self) -> Result<ParamGesture<'_>, Error> {
+        if self.r#type() != CLAP_EVENT_PARAM_GESTURE_BEGIN as u16
+            && self.r#type() != CLAP_EVENT_PARAM_GESTURE_END as u16
+        {
+            return Err(Error::OtherType(self.r#type()));
+        }
+
+        if self.size() != size_of::<clap_event_param_gesture>() as u32 {
+            return Err(Error::PayloadSize(self.size()));
+        }
+        // SAFETY: We just checked if `self` is an event of type to be cast to.
+        Ok(unsafe { ParamGesture::new_unchecked(self) })
+    }
+
     impl_event_cast_methods!(
         transport,
         transport_unchecked,
-        Transport,
+        Transport<'_>,
         clap_event_transport,
         CLAP_EVENT_TRANSPORT
     );
 
-    impl_event_cast_methods!(midi, midi_unchecked, Midi, clap_event_midi, CLAP_EVENT_MIDI);
+    impl_event_cast_methods!(
+        midi,
+        midi_unchecked,
+        Midi<'_>,
+        clap_event_midi,
+        CLAP_EVENT_MIDI
+    );
+
+    impl_event_cast_methods!(
+        midi_sysex,
+        midi_sysex_unchecked,
+        MidiSysex<'_>,
+        clap_event_midi_sysex,
+        CLAP_EVENT_MIDI_SYSEX
+    );
 
     impl_event_cast_methods!(
         midi2,
         midi2_unchecked,
-        Midi2,
+        Midi2<'_>,
         clap_event_midi2,
         CLAP_EVENT_MIDI2
     );
@@ -730,6 +833,141 @@ impl From<clap_event_param_mod> for ParamModBuilder {
 
 impl_event_builder!(ParamModBuilder, ParamMod<'a>, param_mod_unchecked);
 
+/// Indicates that the user started or finished adjusting a parameter.
+///
+/// The host may use these to group the parameter changes into a single
+/// automation gesture, and to show the touch state of a knob.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(u16)]
+pub enum GestureKind {
+    Begin = CLAP_EVENT_PARAM_GESTURE_BEGIN as u16,
+    End = CLAP_EVENT_PARAM_GESTURE_END as u16,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ParamGesture<'a> {
+    header: &'a Header,
+    pub kind: GestureKind,
+}
+
+impl<'a> ParamGesture<'a> {
+    /// # Safety
+    ///
+    /// The `header` must be a header of type: `clap_event_param_gesture`.
+    pub const unsafe fn new_unchecked(header: &'a Header) -> Self {
+        // SAFETY: By the constructor safety requirements, the cast is sound,
+        // because GestureKind is repr(u16) and it is a valid gesture event.
+        let kind: GestureKind = unsafe { mem::transmute(header.r#type()) };
+
+        Self { header, kind }
+    }
+
+    const fn as_clap_event_param_gesture(&self) -> &clap_event_param_gesture {
+        // SAFETY: By construction, this cast is safe.
+        unsafe { self.header.cast_unchecked() }
+    }
+
+    pub fn param_id(&self) -> ClapId {
+        self.as_clap_event_param_gesture()
+            .param_id
+            .try_into()
+            .unwrap_or(ClapId::invalid_id())
+    }
+
+    /// # Example
+    ///
+    /// ```rust
+    /// # use clap_clap::events::{Event, EventBuilder, GestureKind, ParamGesture};
+    /// # use clap_clap::id::ClapId;
+    /// let gesture = ParamGesture::build(GestureKind::Begin)
+    ///     .param_id(ClapId::from(7))
+    ///     .time(3);
+    /// let event = gesture.event();
+    ///
+    /// assert_eq!(event.kind, GestureKind::Begin);
+    /// assert_eq!(event.param_id(), ClapId::from(7));
+    /// assert_eq!(event.header().time(), 3);
+    /// ```
+    pub const fn build(kind: GestureKind) -> ParamGestureBuilder {
+        ParamGestureBuilder::new(kind)
+    }
+
+    /// # Example
+    ///
+    /// ```rust
+    /// # use clap_clap::events::{Event, EventBuilder, GestureKind, ParamGesture};
+    /// # use clap_clap::id::ClapId;
+    /// let begin = ParamGesture::build(GestureKind::Begin).param_id(ClapId::from(7));
+    /// let end = begin.event().update().kind(GestureKind::End);
+    ///
+    /// assert_eq!(end.event().kind, GestureKind::End);
+    /// assert_eq!(end.event().param_id(), ClapId::from(7));
+    /// ```
+    pub fn update(&self) -> ParamGestureBuilder {
+        ParamGestureBuilder::with_param_gesture(self)
+    }
+}
+
+impl Event for ParamGesture<'_> {
+    fn header(&self) -> &Header {
+        self.header
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ParamGestureBuilder(clap_event_param_gesture);
+
+impl ParamGestureBuilder {
+    pub const fn new(kind: GestureKind) -> Self {
+        Self(clap_event_param_gesture {
+            header: clap_event_header {
+                size: size_of::<clap_event_param_gesture>() as u32,
+                time: 0,
+                space_id: CLAP_CORE_EVENT_SPACE_ID,
+                r#type: kind as u16,
+                flags: 0,
+            },
+            param_id: 0,
+        })
+    }
+
+    pub fn with_param_gesture(param_gesture: &ParamGesture<'_>) -> Self {
+        // SAFETY: ParamGesture constructor guarantees that this cast is safe, and we
+        // can copy the object of type: `clap_event_param_gesture`.
+        Self(*unsafe { param_gesture.header().cast_unchecked() })
+    }
+
+    pub fn param_id(self, value: ClapId) -> Self {
+        let mut builder = self;
+        builder.0.param_id = value.into();
+        builder
+    }
+
+    pub const fn kind(self, value: GestureKind) -> Self {
+        let mut build = self;
+        build.0.header.r#type = value as u16;
+        build
+    }
+}
+
+impl Default for ParamGestureBuilder {
+    fn default() -> Self {
+        Self::new(GestureKind::Begin)
+    }
+}
+
+impl From<clap_event_param_gesture> for ParamGestureBuilder {
+    fn from(value: clap_event_param_gesture) -> Self {
+        Self(value)
+    }
+}
+
+impl_event_builder!(
+    ParamGestureBuilder,
+    ParamGesture<'a>,
+    param_gesture_unchecked
+);
+
 /// Transport flags.
 ///
 /// # Example
@@ -994,6 +1232,149 @@ impl From<clap_event_midi> for MidiBuilder {
 
 impl_event_builder!(MidiBuilder, Midi<'a>, midi_unchecked);
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MidiSysex<'a> {
+    header: &'a Header,
+}
+
+impl<'a> MidiSysex<'a> {
+    /// # Safety
+    ///
+    /// The `header` must be a header of type: `clap_event_midi_sysex`, and
+    /// its field `buffer` must point to `size` bytes that stay valid for the
+    /// lifetime `'a`.
+    pub const unsafe fn new_unchecked(header: &'a Header) -> Self {
+        Self { header }
+    }
+
+    const fn as_clap_event_midi_sysex(&self) -> &clap_event_midi_sysex {
+        // SAFETY: By construction, this cast is safe.
+        unsafe { self.header.cast_unchecked() }
+    }
+
+    impl_event_const_getter!(port_index, as_clap_event_midi_sysex, u16);
+
+    pub const fn buffer(&self) -> &'a [u8] {
+        // SAFETY: By construction, this cast is safe.
+        let event: &'a clap_event_midi_sysex = unsafe { self.header.cast_unchecked() };
+        if event.buffer.is_null() || event.size == 0 {
+            return &[];
+        }
+        // SAFETY: By construction, the buffer holds `size` bytes valid for 'a.
+        unsafe { &*slice_from_raw_parts(event.buffer, event.size as usize) }
+    }
+
+    /// # Example
+    ///
+    /// ```rust
+    /// # use clap_clap::events::{Event, EventBuilder, MidiSysex};
+    /// let sysex = MidiSysex::build()
+    ///     .port_index(1)
+    ///     .buffer([0xf0, 0x7e, 0x7f, 0x06, 0x01, 0xf7])
+    ///     .time(3);
+    /// let event = sysex.event();
+    ///
+    /// assert_eq!(event.port_index(), 1);
+    /// assert_eq!(event.buffer(), &[0xf0, 0x7e, 0x7f, 0x06, 0x01, 0xf7]);
+    /// assert_eq!(event.header().time(), 3);
+    /// ```
+    pub const fn build() -> MidiSysexBuilder {
+        MidiSysexBuilder::new()
+    }
+
+    /// # Example
+    ///
+    /// ```rust
+    /// # use clap_clap::events::{Event, EventBuilder, MidiSysex};
+    /// let sysex = MidiSysex::build().port_index(1).buffer(vec![0xf0, 0xf7]);
+    /// let event = sysex.event();
+    ///
+    /// let other_sysex = event.update().buffer([0xf0, 0x01, 0xf7]);
+    /// let other_event = other_sysex.event();
+    ///
+    /// assert_eq!(event.buffer(), &[0xf0, 0xf7]);
+    ///
+    /// assert_eq!(other_event.port_index(), 1);
+    /// assert_eq!(other_event.buffer(), &[0xf0, 0x01, 0xf7]);
+    /// ```
+    pub fn update(&self) -> MidiSysexBuilder {
+        MidiSysexBuilder::with_midi_sysex(self)
+    }
+}
+
+impl Event for MidiSysex<'_> {
+    fn header(&self) -> &Header {
+        self.header
+    }
+}
+
+/// A builder that owns the SysEx payload.
+///
+/// The event returned by [`EventBuilder::event()`] borrows the builder, so the
+/// payload stays alive while the event is pushed with
+/// [`OutputEvents::try_push()`].
+#[derive(Debug)]
+pub struct MidiSysexBuilder(clap_event_midi_sysex, Vec<u8>);
+
+impl MidiSysexBuilder {
+    pub const fn new() -> Self {
+        Self(
+            clap_event_midi_sysex {
+                header: clap_event_header {
+                    size: size_of::<clap_event_midi_sysex>() as u32,
+                    time: 0,
+                    space_id: CLAP_CORE_EVENT_SPACE_ID,
+                    r#type: CLAP_EVENT_MIDI_SYSEX as u16,
+                    flags: 0,
+                },
+                port_index: 0,
+                buffer: null(),
+                size: 0,
+            },
+            Vec::new(),
+        )
+    }
+
+    pub fn with_midi_sysex(midi_sysex: &MidiSysex<'_>) -> Self {
+        // SAFETY: MidiSysex constructor guarantees that this cast is safe, and we can
+        // copy the object of type: `clap_event_midi_sysex`.
+        Self(*unsafe { midi_sysex.header().cast_unchecked() }, Vec::new())
+            .buffer(midi_sysex.buffer())
+    }
+
+    impl_event_builder_setter!(port_index, u16);
+
+    /// Set the SysEx payload.
+    ///
+    /// # Panic
+    ///
+    /// Panic if the payload is longer than `u32::MAX` bytes.
+    #[must_use]
+    pub fn buffer(self, value: impl Into<Vec<u8>>) -> Self {
+        let mut build = self;
+        build.1 = value.into();
+        build.0.size = u32::try_from(build.1.len()).expect("buffer too long");
+        // The pointer stays valid when the builder is moved, since it points
+        // to the heap allocation owned by the Vec.
+        build.0.buffer = build.1.as_ptr();
+        build
+    }
+}
+
+impl Clone for MidiSysexBuilder {
+    fn clone(&self) -> Self {
+        Self(self.0, Vec::new()).buffer(self.1.clone())
+    }
+}
+
+impl Default for MidiSysexBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_event_builder!(MidiSysexBuilder, MidiSysex<'a>, midi_sysex_unchecked);
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Midi2<'a> {
     header: &'a Header,
@@ -1101,6 +1482,173 @@ impl From<clap_event_midi2> for Midi2Builder {
 
 impl_event_builder!(Midi2Builder, Midi2<'a>, midi2_unchecked);
 
+/// A payload of an event from a custom event space.
+///
+/// # Safety
+///
+/// The implementor must be `#[repr(C)]`, and every bit pattern of the size of
+/// the type must be a valid value of it (e.g. integers, floats and arrays
+/// thereof). Events are exchanged as raw bytes through the host, possibly with
+/// other plugins, so no other invariants can be relied upon.
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::events::{CustomPayload, Custom, Event, EventBuilder, EventSpaceId};
+/// #[derive(Debug, Copy, Clone, PartialEq)]
+/// #[repr(C)]
+/// struct Cue {
+///     number: u32,
+///     gain: f32,
+/// }
+///
+/// unsafe impl CustomPayload for Cue {
+///     const TYPE: u16 = 1;
+/// }
+///
+/// let space_id = EventSpaceId::try_from(42).unwrap();
+/// let cue = Custom::build(space_id, Cue { number: 3, gain: 0.5 }).time(7);
+///
+/// let built = cue.event();
+/// let event = built.header().custom::<Cue>(space_id).unwrap();
+/// assert_eq!(event.payload().number, 3);
+/// assert_eq!(event.header().time(), 7);
+/// assert!(built.header().custom::<Cue>(EventSpaceId::CORE).is_err());
+/// ```
+pub unsafe trait CustomPayload: Copy + 'static {
+    /// The event type within the event space.
+    const TYPE: u16;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+struct clap_event_custom<T> {
+    header: clap_event_header,
+    payload: T,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Custom<'a, T> {
+    header: &'a Header,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: CustomPayload> Custom<'a, T> {
+    /// # Safety
+    ///
+    /// The `header` must be a header of an event of the same size and layout
+    /// as: `clap_event_header` followed by the payload `T`.
+    pub const unsafe fn new_unchecked(header: &'a Header) -> Self {
+        Self {
+            header,
+            _marker: PhantomData,
+        }
+    }
+
+    const fn as_clap_event_custom(&self) -> &clap_event_custom<T> {
+        // SAFETY: By construction, this cast is safe.
+        unsafe { self.header.cast_unchecked() }
+    }
+
+    pub const fn payload(&self) -> &T {
+        &self.as_clap_event_custom().payload
+    }
+
+    pub const fn build(space_id: EventSpaceId, payload: T) -> CustomBuilder<T> {
+        CustomBuilder::new(space_id, payload)
+    }
+
+    pub fn update(&self) -> CustomBuilder<T> {
+        CustomBuilder::with_custom(self)
+    }
+}
+
+impl<T> Event for Custom<'_, T> {
+    fn header(&self) -> &Header {
+        self.header
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CustomBuilder<T>(clap_event_custom<T>);
+
+impl<T: CustomPayload> CustomBuilder<T> {
+    /// # Panic
+    ///
+    /// Panic if `space_id` is the core event space.
+    pub const fn new(space_id: EventSpaceId, payload: T) -> Self {
+        assert!(
+            space_id.0 != CLAP_CORE_EVENT_SPACE_ID,
+            "custom event in core space"
+        );
+        Self(clap_event_custom {
+            header: clap_event_header {
+                size: size_of::<clap_event_custom<T>>() as u32,
+                time: 0,
+                space_id: space_id.0,
+                r#type: T::TYPE,
+                flags: 0,
+            },
+            payload,
+        })
+    }
+
+    pub fn with_custom(custom: &Custom<'_, T>) -> Self {
+        // SAFETY: Custom constructor guarantees that this cast is safe, and we can
+        // copy the object, since the payload is Copy.
+        Self(*unsafe { custom.header().cast_unchecked() })
+    }
+
+    #[must_use]
+    pub const fn payload(self, value: T) -> Self {
+        let mut build = self;
+        build.0.payload = value;
+        build
+    }
+}
+
+impl<T: CustomPayload> EventBuilder for CustomBuilder<T> {
+    type Event<'a>
+        = Custom<'a, T>
+    where
+        Self: 'a;
+
+    fn time(self, value: u32) -> Self {
+        let mut build = self;
+        build.0.header.time = value;
+        build
+    }
+
+    /// The event space of a custom event must not be changed to the core
+    /// space, since the payload would then be interpreted as a core event.
+    ///
+    /// # Panic
+    ///
+    /// Panic if `value` is the id of the core event space.
+    fn space_id(self, value: u16) -> Self {
+        assert_ne!(
+            value, CLAP_CORE_EVENT_SPACE_ID,
+            "custom event in core space"
+        );
+        let mut build = self;
+        build.0.header.space_id = value;
+        build
+    }
+
+    fn flags(self, value: u32) -> Self {
+        let mut build = self;
+        build.0.header.flags = value;
+        build
+    }
+
+    fn event(&self) -> Self::Event<'_> {
+        // SAFETY: By construction, `self.header` is a valid header of the event type.
+        let header = unsafe { Header::new_unchecked(&self.0.header) };
+        unsafe { Custom::new_unchecked(header) }
+    }
+}
+
 pub struct InputEvents<'a>(&'a clap_input_events);
 
 impl<'a> InputEvents<'a> {
@@ -1120,7 +1668,7 @@ impl<'a> InputEvents<'a> {
     /// # Safety
     ///
     /// The value of `index` must be less than `self.size()`.
-    pub unsafe fn get_unchecked(&self, index: u32) -> &Header {
+    pub unsafe fn get_unchecked(&self, index: u32) -> &'a Header {
         // SAFETY: By construction, the pointer `self.get` is Some.
         let header = unsafe { &*self.0.get.unwrap()(self.0, index) };
         unsafe { Header::new_unchecked(header) }
@@ -1129,10 +1677,128 @@ impl<'a> InputEvents<'a> {
     /// # Panic
     ///
     /// Panic if `index` greater or equal than `self.size()`.
-    pub fn get(&self, index: u32) -> &Header {
+    pub fn get(&self, index: u32) -> &'a Header {
         assert!(index < self.size(), "index out of bounds");
         unsafe { self.get_unchecked(index) }
     }
+
+    /// Iterate over the events in the list, cast to their concrete types.
+    ///
+    /// See [`EventRef`] for how the events are classified.
+    pub fn iter(&self) -> Iter<'_, 'a> {
+        Iter {
+            events: self,
+            index: 0,
+            size: self.size(),
+        }
+    }
+}
+
+impl<'b, 'a> IntoIterator for &'b InputEvents<'a> {
+    type Item = EventRef<'b>;
+    type IntoIter = Iter<'b, 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the events of [`InputEvents`].
+pub struct Iter<'b, 'a> {
+    events: &'b InputEvents<'a>,
+    index: u32,
+    size: u32,
+}
+
+impl<'b> Iterator for Iter<'b, '_> {
+    type Item = EventRef<'b>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.size {
+            return None;
+        }
+        // SAFETY: We just checked that the index is less than the list size.
+        let header = unsafe { self.events.get_unchecked(self.index) };
+        self.index += 1;
+        Some(header.into())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.size - self.index) as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_, '_> {}
+
+/// An event cast to its concrete type.
+///
+/// Only events from the core event space (`CLAP_CORE_EVENT_SPACE_ID`) are
+/// cast. Events from other spaces, events of unknown type, and events whose
+/// size does not match their type are returned as [`EventRef::Unknown`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EventRef<'a> {
+    NoteOn(Note<'a>),
+    NoteOff(Note<'a>),
+    NoteChoke(Note<'a>),
+    NoteEnd(Note<'a>),
+    NoteExpression(NoteExpression<'a>),
+    ParamValue(ParamValue<'a>),
+    ParamMod(ParamMod<'a>),
+    ParamGestureBegin(ParamGesture<'a>),
+    ParamGestureEnd(ParamGesture<'a>),
+    Transport(Transport<'a>),
+    Midi(Midi<'a>),
+    MidiSysex(MidiSysex<'a>),
+    Midi2(Midi2<'a>),
+    Unknown(&'a Header),
+}
+
+impl<'a> From<&'a Header> for EventRef<'a> {
+    fn from(header: &'a Header) -> Self {
+        if header.space_id() != CLAP_CORE_EVENT_SPACE_ID {
+            return Self::Unknown(header);
+        }
+
+        let event = match header.r#type() as u32 {
+            CLAP_EVENT_NOTE_ON => header.note().map(Self::NoteOn),
+            CLAP_EVENT_NOTE_OFF => header.note().map(Self::NoteOff),
+            CLAP_EVENT_NOTE_CHOKE => header.note().map(Self::NoteChoke),
+            CLAP_EVENT_NOTE_END => header.note().map(Self::NoteEnd),
+            CLAP_EVENT_NOTE_EXPRESSION => header.note_expression().map(Self::NoteExpression),
+            CLAP_EVENT_PARAM_VALUE => header.param_value().map(Self::ParamValue),
+            CLAP_EVENT_PARAM_MOD => header.param_mod().map(Self::ParamMod),
+            CLAP_EVENT_PARAM_GESTURE_BEGIN => header.param_gesture().map(Self::ParamGestureBegin),
+            CLAP_EVENT_PARAM_GESTURE_END => header.param_gesture().map(Self::ParamGestureEnd),
+            CLAP_EVENT_TRANSPORT => header.transport().map(Self::Transport),
+            CLAP_EVENT_MIDI => header.midi().map(Self::Midi),
+            CLAP_EVENT_MIDI_SYSEX => header.midi_sysex().map(Self::MidiSysex),
+            CLAP_EVENT_MIDI2 => header.midi2().map(Self::Midi2),
+            _ => Err(Error::OtherType(header.r#type())),
+        };
+
+        event.unwrap_or(Self::Unknown(header))
+    }
+}
+
+impl Event for EventRef<'_> {
+    fn header(&self) -> &Header {
+        match self {
+            EventRef::NoteOn(e)
+            | EventRef::NoteOff(e)
+            | EventRef::NoteChoke(e)
+            | EventRef::NoteEnd(e) => e.header,
+            EventRef::NoteExpression(e) => e.header,
+            EventRef::ParamValue(e) => e.header,
+            EventRef::ParamMod(e) => e.header,
+            EventRef::ParamGestureBegin(e) | EventRef::ParamGestureEnd(e) => e.header,
+            EventRef::Transport(e) => e.header,
+            EventRef::Midi(e) => e.header,
+            EventRef::MidiSysex(e) => e.header,
+            EventRef::Midi2(e) => e.header,
+            EventRef::Unknown(header) => header,
+        }
+    }
 }
 
 pub struct OutputEvents<'a> {
@@ -1179,10 +1845,13 @@ impl<'a> OutputEvents<'a> {
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Error {
+    InvalidSpaceId,
+    OtherSpace(u16),
     OtherType(u16),
     PayloadSize(u32),
     TryPush,
     OutOfOrder { last_time: u32 },
+    QueueFull,
 }
 
 impl Display for Error {
@@ -1191,6 +1860,8 @@ impl Display for Error {
             Error::PayloadSize(size) => {
                 write!(f, "payload size for the defined event type: {size}")
             }
+            Error::InvalidSpaceId => write!(f, "invalid event space id"),
+            Error::OtherSpace(id) => write!(f, "other event space, id: {id}"),
             Error::OtherType(id) => write!(f, "other type, id: {id}"),
 
             Error::TryPush => write!(f, "pushing event failed"),
@@ -1198,6 +1869,7 @@ impl Display for Error {
             Error::OutOfOrder { last_time } => {
                 write!(f, "event out of order, last event's time: {last_time}")
             }
+            Error::QueueFull => write!(f, "event queue is full"),
         }
     }
 }
@@ -0,0 +1,407 @@
+use std::{
+    ffi::c_void,
+    ptr::{NonNull, copy_nonoverlapping, null},
+};
+
+use crate::{
+    events::{Error, Event, EventRef, Header, InputEvents, OutputEvents},
+    ffi::{
+        CLAP_CORE_EVENT_SPACE_ID, CLAP_EVENT_MIDI_SYSEX, clap_event_header, clap_event_midi_sysex,
+        clap_input_events, clap_output_events,
+    },
+};
+
+/// The default capacity of the queue's arena in bytes.
+pub const DEFAULT_CAPACITY: usize = 64 * 1024;
+
+/// The default maximal number of events in the queue.
+pub const DEFAULT_MAX_EVENTS: usize = 1024;
+
+const WORD_SIZE: usize = size_of::<u64>();
+
+const fn words_for(bytes: usize) -> usize {
+    bytes.div_ceil(WORD_SIZE)
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Entry {
+    time: u32,
+    offset: usize,
+    len: usize,
+}
+
+struct State {
+    arena: Vec<u64>,
+    capacity: usize,
+    entries: Vec<Entry>,
+    max_events: usize,
+    /// The time of the last event pushed to the output list.
+    last_output_time: Option<u32>,
+    /// The number of events pushed to the output list.
+    output_count: usize,
+    /// The position of the first event pushed to the output list out of order.
+    unordered_output: Option<usize>,
+}
+
+impl State {
+    fn header(&self, entry: &Entry) -> &Header {
+        let header = self.arena[entry.offset..]
+            .as_ptr()
+            .cast::<clap_event_header>();
+        // SAFETY: Each entry points to a copy of a valid event that was pushed
+        // into the arena. The arena is aligned to 8 bytes.
+        unsafe { Header::new_unchecked(&*header) }
+    }
+
+    fn try_push(&mut self, header: &Header, time: u32) -> Result<(), Error> {
+        let size = header.size() as usize;
+        let sysex = (header.space_id() == CLAP_CORE_EVENT_SPACE_ID
+            && header.r#type() as u32 == CLAP_EVENT_MIDI_SYSEX)
+            .then(|| header.midi_sysex().ok())
+            .flatten()
+            .map(|midi_sysex| midi_sysex.buffer());
+
+        let event_len = words_for(size);
+        let len = event_len + sysex.map_or(0, |buffer| words_for(buffer.len()));
+        if self.entries.len() >= self.max_events || self.arena.len() + len > self.capacity {
+            return Err(Error::QueueFull);
+        }
+
+        let offset = self.arena.len();
+        // The arena never grows past its initial capacity, so this doesn't
+        // reallocate.
+        self.arena.resize(offset + len, 0);
+        let dst = self.arena[offset..].as_mut_ptr();
+        // SAFETY: We just reserved `len` words, and the source event is `size`
+        // bytes long. The arena and the event don't overlap.
+        unsafe { copy_nonoverlapping(header.to_bytes().as_ptr(), dst.cast::<u8>(), size) };
+
+        // SAFETY: The arena now holds a copy of a valid event header.
+        unsafe { (*dst.cast::<clap_event_header>()).time = time };
+
+        if let Some(buffer) = sysex {
+            // SAFETY: The buffer is stored just after the event, within the
+            // reserved words.
+            let buffer_dst = unsafe { dst.add(event_len) }.cast::<u8>();
+            unsafe { copy_nonoverlapping(buffer.as_ptr(), buffer_dst, buffer.len()) };
+
+            // SAFETY: We checked that the event is a MIDI SysEx event of the
+            // correct size.
+            let event = unsafe { &mut *dst.cast::<clap_event_midi_sysex>() };
+            event.buffer = if buffer.is_empty() {
+                null()
+            } else {
+                buffer_dst
+            };
+        }
+
+        let index = self.entries.partition_point(|entry| entry.time <= time);
+        self.entries.insert(index, Entry { time, offset, len });
+        Ok(())
+    }
+}
+
+/// An owned, time-ordered list of events.
+///
+/// The events are copied into an arena allocated once, when the queue is
+/// created. Pushing, merging and clearing the queue never reallocate. If an
+/// event doesn't fit into the arena, or the queue already holds the maximal
+/// number of events, pushing it fails with [`Error::QueueFull`].
+///
+/// Events are kept sorted by their time. Events with equal time keep the
+/// order in which they were pushed. The buffer of a MIDI SysEx event is copied
+/// into the arena together with the event.
+///
+/// The queue implements both: `clap_input_events` and `clap_output_events`,
+/// and so it can be passed as the input or output event list of a
+/// [`Process`](crate::process::Process).
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::events::{Event, EventBuilder, EventQueue, Note, NoteKind};
+/// let mut queue = EventQueue::new();
+///
+/// queue.try_push(Note::build(NoteKind::Off).key(60).time(32).event()).unwrap();
+/// queue.try_push(Note::build(NoteKind::On).key(60).time(0).event()).unwrap();
+///
+/// let times: Vec<_> = queue.iter().map(|event| event.header().time()).collect();
+/// assert_eq!(times, [0, 32]);
+/// ```
+pub struct EventQueue {
+    state: NonNull<State>,
+    clap_input_events: clap_input_events,
+    clap_output_events: clap_output_events,
+}
+
+// SAFETY: The queue owns its state. The pointers held by the vtables point to
+// it and are used only through the queue's methods.
+unsafe impl Send for EventQueue {}
+unsafe impl Sync for EventQueue {}
+
+impl EventQueue {
+    /// Create a queue with the default capacity.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, DEFAULT_MAX_EVENTS)
+    }
+
+    /// Create a queue that can hold up to `max_events` events, and `capacity`
+    /// bytes of event data.
+    ///
+    /// Each event (and each SysEx buffer) takes up its size rounded up to a
+    /// multiple of 8 bytes.
+    pub fn with_capacity(capacity: usize, max_events: usize) -> Self {
+        let capacity = words_for(capacity);
+        let state = Box::new(State {
+            arena: Vec::with_capacity(capacity),
+            capacity,
+            entries: Vec::with_capacity(max_events),
+            max_events,
+            last_output_time: None,
+            output_count: 0,
+            unordered_output: None,
+        });
+        let state = NonNull::from(Box::leak(state));
+        let ctx = state.as_ptr().cast::<c_void>();
+
+        Self {
+            state,
+            clap_input_events: clap_input_events {
+                ctx,
+                size: Some(ffi::size),
+                get: Some(ffi::get),
+            },
+            clap_output_events: clap_output_events {
+                ctx,
+                try_push: Some(ffi::try_push),
+            },
+        }
+    }
+
+    const fn state(&self) -> &State {
+        // SAFETY: The state is valid for the lifetime of self.
+        unsafe { self.state.as_ref() }
+    }
+
+    const fn state_mut(&mut self) -> &mut State {
+        // SAFETY: The state is valid for the lifetime of self, and we have
+        // exclusive access to it.
+        unsafe { self.state.as_mut() }
+    }
+
+    /// The number of events in the queue.
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The capacity of the arena in bytes.
+    pub fn capacity(&self) -> usize {
+        self.state().capacity * WORD_SIZE
+    }
+
+    /// The maximal number of events in the queue.
+    pub fn max_events(&self) -> usize {
+        self.state().max_events
+    }
+
+    /// Remove all events. The allocated memory is kept for reuse.
+    pub fn clear(&mut self) {
+        let state = self.state_mut();
+        state.arena.clear();
+        state.entries.clear();
+        state.last_output_time = None;
+        state.output_count = 0;
+        state.unordered_output = None;
+    }
+
+    /// The position, in push order, of the first event pushed to the
+    /// [output list] with time less than the time of the event pushed before
+    /// it.
+    ///
+    /// The queue sorts the events, and so this is the only trace of a plugin
+    /// that doesn't push its output events in order.  Clearing the queue
+    /// resets it.
+    ///
+    /// [output list]: Self::output_events
+    pub fn unordered_output(&self) -> Option<usize> {
+        self.state().unordered_output
+    }
+
+    /// Copy the event into the queue.
+    ///
+    /// Unlike [`OutputEvents::try_push()`], events can be pushed in any
+    /// order. The event is inserted after all events with time less or equal
+    /// to the time of the event.
+    pub fn try_push(&mut self, event: impl Event) -> Result<(), Error> {
+        let header = event.header();
+        self.state_mut().try_push(header, header.time())
+    }
+
+    /// Copy the event into the queue, setting its time to `time`.
+    ///
+    /// The event itself is left unchanged.
+    pub fn try_push_at(&mut self, event: impl Event, time: u32) -> Result<(), Error> {
+        self.state_mut().try_push(event.header(), time)
+    }
+
+    /// Copy all events from `other` into the queue.
+    ///
+    /// The events from `other` are placed after the events of `self` that have
+    /// the same time. If there is not enough room in the queue for all events
+    /// from `other`, the queue is left unchanged and [`Error::QueueFull`] is
+    /// returned.
+    pub fn merge(&mut self, other: &EventQueue) -> Result<(), Error> {
+        let state = self.state();
+        let other_state = other.state();
+        let len: usize = other_state.entries.iter().map(|entry| entry.len).sum();
+        if state.entries.len() + other_state.entries.len() > state.max_events
+            || state.arena.len() + len > state.capacity
+        {
+            return Err(Error::QueueFull);
+        }
+
+        for entry in &other_state.entries {
+            let header = other_state.header(entry);
+            self.state_mut().try_push(header, entry.time)?;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Header> {
+        let state = self.state();
+        state.entries.get(index).map(|entry| state.header(entry))
+    }
+
+    /// Iterate over the events in the queue, cast to their concrete types.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = EventRef<'_>> {
+        let state = self.state();
+        state
+            .entries
+            .iter()
+            .map(|entry| EventRef::from(state.header(entry)))
+    }
+
+    /// Push all events from the queue to `out_events`.
+    pub fn send(&self, out_events: &mut OutputEvents<'_>) -> Result<(), Error> {
+        let state = self.state();
+        state
+            .entries
+            .iter()
+            .try_for_each(|entry| out_events.try_push(EventRef::from(state.header(entry))))
+    }
+
+    /// The queue as a list of input events.
+    pub fn input_events(&self) -> InputEvents<'_> {
+        // SAFETY: The pointers `size` and `get` are Some.
+        unsafe { InputEvents::new_unchecked(&self.clap_input_events) }
+    }
+
+    /// The queue as a list of output events.
+    ///
+    /// Events pushed to the list are copied into the queue.
+    pub fn output_events(&mut self) -> OutputEvents<'_> {
+        // SAFETY: The pointer `try_push` is Some.
+        unsafe { OutputEvents::new_unchecked(&self.clap_output_events) }
+    }
+
+    pub const fn as_clap_input_events(&self) -> &clap_input_events {
+        &self.clap_input_events
+    }
+
+    /// The events pushed to the list are copied into the queue. The list must
+    /// not be used while the queue is accessed otherwise.
+    pub const fn as_clap_output_events(&mut self) -> &clap_output_events {
+        &self.clap_output_events
+    }
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for EventQueue {
+    fn drop(&mut self) {
+        // SAFETY: The state was leaked from a Box in the constructor and is not
+        // used after this.
+        drop(unsafe { Box::from_raw(self.state.as_ptr()) });
+    }
+}
+
+mod ffi {
+    use super::*;
+
+    /// # Safety
+    ///
+    /// `ctx` must point to the state of an event queue.
+    unsafe fn state<'a>(ctx: *mut c_void) -> &'a State {
+        unsafe { &*ctx.cast::<State>() }
+    }
+
+    /// # Safety
+    ///
+    /// `ctx` must point to the state of an event queue, and there must be no
+    /// other references to the state.
+    unsafe fn state_mut<'a>(ctx: *mut c_void) -> &'a mut State {
+        unsafe { &mut *ctx.cast::<State>() }
+    }
+
+    pub(super) extern "C-unwind" fn size(list: *const clap_input_events) -> u32 {
+        if list.is_null() {
+            return 0;
+        }
+        // SAFETY: We just checked that the list is non-null. The list was
+        // created by an event queue.
+        let state = unsafe { state((*list).ctx) };
+        state.entries.len() as u32
+    }
+
+    pub(super) extern "C-unwind" fn get(
+        list: *const clap_input_events,
+        index: u32,
+    ) -> *const clap_event_header {
+        if list.is_null() {
+            return null();
+        }
+        // SAFETY: We just checked that the list is non-null. The list was
+        // created by an event queue.
+        let state = unsafe { state((*list).ctx) };
+        state
+            .entries
+            .get(index as usize)
+            .map_or(null(), |entry| state.header(entry).as_clap_event_header())
+    }
+
+    pub(super) extern "C-unwind" fn try_push(
+        list: *const clap_output_events,
+        event: *const clap_event_header,
+    ) -> bool {
+        if list.is_null() || event.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the list is non-null. The list was
+        // created by an event queue, borrowed mutably.
+        let state = unsafe { state_mut((*list).ctx) };
+        // SAFETY: The host guarantees that the event is valid.
+        let header = unsafe { Header::new_unchecked(&*event) };
+        if (header.size() as usize) < size_of::<clap_event_header>() {
+            return false;
+        }
+        let time = header.time();
+        let pushed = state.output_count;
+        if state.try_push(header, time).is_err() {
+            return false;
+        }
+        if state.unordered_output.is_none() && state.last_output_time.is_some_and(|t| time < t) {
+            state.unordered_output = Some(pushed);
+        }
+        state.last_output_time = Some(time);
+        state.output_count += 1;
+        true
+    }
+}
@@ -14,23 +14,40 @@
 //! [`Host::get_extension()']: crate::host::Host::get_extension
 //! [`StereoPorts`]: audio_ports::StereoPorts
 
-use std::fmt::{Display, Formatter};
+use std::{
+    ffi::c_char,
+    fmt::{Display, Formatter},
+    ptr::copy_nonoverlapping,
+};
 
 use crate::{
     ext::{
-        audio_ports::AudioPorts, latency::Latency, note_ports::NotePorts, params::Params,
-        state::State, tail::Tail,
+        audio_ports::AudioPorts, gui::Gui, latency::Latency, note_name::NoteName,
+        note_ports::NotePorts, param_indication::ParamIndication, params::Params,
+        posix_fd_support::PosixFdSupport, remote_controls::RemoteControls, render::Render,
+        state::State, tail::Tail, thread_pool::ThreadPool, timer_support::TimerSupport,
+        voice_info::VoiceInfo,
     },
     plugin::Plugin,
 };
 
 pub mod audio_ports;
+pub mod event_registry;
+pub mod gui;
 pub mod latency;
 pub mod log;
+pub mod note_name;
 pub mod note_ports;
+pub mod param_indication;
 pub mod params;
+pub mod posix_fd_support;
+pub mod remote_controls;
+pub mod render;
 pub mod state;
 pub mod tail;
+pub mod thread_pool;
+pub mod timer_support;
+pub mod voice_info;
 
 /// Plugin extensions.
 pub trait Extensions<P: Plugin> {
@@ -38,18 +55,42 @@ pub trait Extensions<P: Plugin> {
         None::<()>
     }
 
+    fn gui() -> Option<impl Gui<P>> {
+        None::<()>
+    }
+
     fn latency() -> Option<impl Latency<P>> {
         None::<()>
     }
 
+    fn note_name() -> Option<impl NoteName<P>> {
+        None::<()>
+    }
+
     fn note_ports() -> Option<impl NotePorts<P>> {
         None::<()>
     }
 
+    fn param_indication() -> Option<impl ParamIndication<P>> {
+        None::<()>
+    }
+
     fn params() -> Option<impl Params<P>> {
         None::<()>
     }
 
+    fn posix_fd_support() -> Option<impl PosixFdSupport<P>> {
+        None::<()>
+    }
+
+    fn remote_controls() -> Option<impl RemoteControls<P>> {
+        None::<()>
+    }
+
+    fn render() -> Option<impl Render<P>> {
+        None::<()>
+    }
+
     fn state() -> Option<impl State<P>> {
         None::<()>
     }
@@ -57,15 +98,52 @@ pub trait Extensions<P: Plugin> {
     fn tail() -> Option<impl Tail<P>> {
         None::<()>
     }
+
+    fn thread_pool() -> Option<impl ThreadPool<P>> {
+        None::<()>
+    }
+
+    fn timer_support() -> Option<impl TimerSupport<P>> {
+        None::<()>
+    }
+
+    fn voice_info() -> Option<impl VoiceInfo<P>> {
+        None::<()>
+    }
+}
+
+/// # SAFETY:
+///
+/// `N` must be larger than 0. `src` and `dst` buffers must be
+/// non-overlapping.
+///
+/// A trailing null byte will be added.  At most `N-1` bytes will be copied.
+pub(crate) unsafe fn copy_utf8_to_cstr<const N: usize>(src: &str, dst: &mut [c_char; N]) {
+    // N > 0, so subtracting 1 won't underflow.
+    let n = src.len().min(N - 1);
+    // SAFETY: The caller upholds the safety requirements.
+    unsafe {
+        copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr() as *mut _, n);
+    }
+    // n is within bounds.
+    dst[n] = b'\0' as _;
 }
 
 #[derive(Debug)]
 pub enum Error {
     Log(log::Error),
     AudioPorts(audio_ports::Error),
+    EventRegistry(event_registry::Error),
+    Gui(gui::Error),
+    NoteName(note_name::Error),
     NotePorts(note_ports::Error),
+    ParamIndication(param_indication::Error),
     Params(params::Error),
+    PosixFdSupport(posix_fd_support::Error),
+    RemoteControls(remote_controls::Error),
+    Render(render::Error),
     State(state::Error),
+    TimerSupport(timer_support::Error),
 }
 
 impl Display for Error {
@@ -73,9 +151,17 @@ impl Display for Error {
         match self {
             Error::Log(e) => write!(f, "log: {e}"),
             Error::AudioPorts(e) => write!(f, "audio_ports: {e}"),
+            Error::EventRegistry(e) => write!(f, "event_registry: {e}"),
+            Error::Gui(e) => write!(f, "gui: {e}"),
+            Error::NoteName(e) => write!(f, "note_name: {e}"),
             Error::NotePorts(e) => write!(f, "note_ports: {e}"),
+            Error::ParamIndication(e) => write!(f, "param_indication: {e}"),
             Error::Params(e) => write!(f, "params: {e}"),
+            Error::PosixFdSupport(e) => write!(f, "posix_fd_support: {e}"),
+            Error::RemoteControls(e) => write!(f, "remote_controls: {e}"),
+            Error::Render(e) => write!(f, "render: {e}"),
             Error::State(e) => write!(f, "state: {e}"),
+            Error::TimerSupport(e) => write!(f, "timer_support: {e}"),
         }
     }
 }
@@ -170,7 +170,7 @@ mod ffi {
 
 pub use port_info::{AudioPortFlags, AudioPortInfo, AudioPortType};
 mod port_info {
-    use std::ptr::null;
+    use std::{ffi::CStr, ptr::null};
 
     use crate::{
         ffi::{
@@ -215,6 +215,33 @@ mod port_info {
     }
 
     impl AudioPortInfo {
+        /// Port types other than the ones listed in [`AudioPortType`] are
+        /// read as `None`.
+        ///
+        /// # Safety
+        ///
+        /// The field: `port_type` must be null or a valid, null-terminated C
+        /// string.
+        pub unsafe fn from_unchecked(value: &clap_audio_port_info) -> Self {
+            // SAFETY: c_char and u8 have the same size and alignment.
+            let name = unsafe { &*(&raw const value.name).cast::<[u8; 256]>() };
+            Self {
+                id: value.id.try_into().unwrap_or(ClapId::invalid_id()),
+                name: CStr::from_bytes_until_nul(name)
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                flags: value.flags,
+                channel_count: value.channel_count,
+                port_type: (!value.port_type.is_null())
+                    // SAFETY: The safety condition is upheld by the caller.
+                    .then(|| unsafe { CStr::from_ptr(value.port_type) })
+                    .and_then(|s| AudioPortType::try_from(s.to_str().ok()?).ok()),
+                in_place_pair: (value.in_place_pair != CLAP_INVALID_ID)
+                    .then(|| value.in_place_pair.try_into().ok())
+                    .flatten(),
+            }
+        }
+
         pub(super) fn fill_clap_audio_port_info(&self, info: &mut clap_audio_port_info) {
             info.id = self.id.into();
 
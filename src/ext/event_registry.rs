@@ -0,0 +1,93 @@
+//! Event registry.
+//!
+//! This extension lets the plugin query the host for the id of an event
+//! space, by its name. Events from such a space can be built and cast with
+//! the facilities in [`events`](crate::events), e.g. [`CustomPayload`].
+//!
+//! [`CustomPayload`]: crate::events::CustomPayload
+
+use std::{
+    ffi::{CStr, CString, NulError},
+    fmt::{Display, Formatter},
+};
+
+use crate::{events::EventSpaceId, ffi::clap_host_event_registry, host::Host};
+
+#[derive(Debug)]
+pub struct HostEventRegistry<'a> {
+    host: &'a Host,
+    clap_host_event_registry: &'a clap_host_event_registry,
+}
+
+impl<'a> HostEventRegistry<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_event_registry: &'a clap_host_event_registry,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_event_registry,
+        }
+    }
+
+    /// Query an event space id by its name.
+    ///
+    /// Returns [`Error::Unsupported`] if the host doesn't know the space.
+    pub fn query_cstr(&self, space_name: &CStr) -> Result<EventSpaceId, Error> {
+        let mut space_id = u16::MAX;
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_event_registry.query.unwrap();
+        if !unsafe {
+            callback(
+                self.host.clap_host(),
+                space_name.as_ptr(),
+                &raw mut space_id,
+            )
+        } {
+            return Err(Error::Unsupported);
+        }
+
+        EventSpaceId::try_from(space_id).map_err(|_| Error::Unsupported)
+    }
+
+    /// Query an event space id by its name.
+    ///
+    /// Returns [`Error::Unsupported`] if the host doesn't know the space.
+    pub fn query(&self, space_name: &str) -> Result<EventSpaceId, Error> {
+        self.query_cstr(&CString::new(space_name)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    NulError(NulError),
+    Unsupported,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NulError(e) => write!(f, "error converting to C string: {e}"),
+            Error::Unsupported => write!(f, "event space not supported by the host"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<NulError> for Error {
+    fn from(value: NulError) -> Self {
+        Self::NulError(value)
+    }
+}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::EventRegistry(value).into()
+    }
+}
@@ -0,0 +1,880 @@
+//! Plugin GUI.
+//!
+//! Showing the GUI works as follows:
+//!
+//!  1. [`Gui::is_api_supported()`], check what can work
+//!  2. [`Gui::create()`], allocates gui resources
+//!  3. if the plugin window is floating
+//!     - [`Gui::set_transient()`]
+//!     - [`Gui::suggest_title()`]
+//!  4. else
+//!     - [`Gui::set_scale()`]
+//!     - [`Gui::can_resize()`]
+//!     - if resizable and has known size from previous session,
+//!       [`Gui::set_size()`]
+//!     - else [`Gui::get_size()`], gets initial size
+//!     - [`Gui::set_parent()`]
+//!  5. [`Gui::show()`]
+//!  6. [`Gui::hide()`]/[`Gui::show()`] ...
+//!  7. [`Gui::destroy()`] when done with the gui
+//!
+//! All the methods of the [`Gui`] trait are called on the main thread.
+
+use std::{
+    ffi::{CStr, c_void},
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    ffi::{
+        CLAP_WINDOW_API_COCOA, CLAP_WINDOW_API_WAYLAND, CLAP_WINDOW_API_WIN32, CLAP_WINDOW_API_X11,
+        clap_gui_resize_hints, clap_host_gui, clap_hwnd, clap_nsview, clap_window, clap_window__,
+        clap_xwnd,
+    },
+    host::Host,
+    plugin::Plugin,
+};
+
+/// Windowing API.
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::gui::WindowApi;
+/// assert_eq!(WindowApi::X11.as_cstr(), c"x11");
+/// assert_eq!(WindowApi::try_from(c"x11"), Ok(WindowApi::X11));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindowApi {
+    /// Uses physical size. Embed using `SetParent()`.
+    Win32,
+    /// Uses logical size, don't call [`Gui::set_scale()`].
+    Cocoa,
+    /// Uses physical size. Embed using XEmbed protocol.
+    X11,
+    /// Uses physical size. Embedding is not supported: floating windows only.
+    Wayland,
+}
+
+impl WindowApi {
+    pub const fn as_cstr(&self) -> &'static CStr {
+        match self {
+            WindowApi::Win32 => CLAP_WINDOW_API_WIN32,
+            WindowApi::Cocoa => CLAP_WINDOW_API_COCOA,
+            WindowApi::X11 => CLAP_WINDOW_API_X11,
+            WindowApi::Wayland => CLAP_WINDOW_API_WAYLAND,
+        }
+    }
+}
+
+impl TryFrom<&CStr> for WindowApi {
+    type Error = Error;
+
+    fn try_from(value: &CStr) -> Result<Self, Self::Error> {
+        if value == CLAP_WINDOW_API_WIN32 {
+            Ok(WindowApi::Win32)
+        } else if value == CLAP_WINDOW_API_COCOA {
+            Ok(WindowApi::Cocoa)
+        } else if value == CLAP_WINDOW_API_X11 {
+            Ok(WindowApi::X11)
+        } else if value == CLAP_WINDOW_API_WAYLAND {
+            Ok(WindowApi::Wayland)
+        } else {
+            Err(Error::UnknownApi)
+        }
+    }
+}
+
+/// A handle to a window provided by the host.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Window {
+    Win32(clap_hwnd),
+    Cocoa(clap_nsview),
+    X11(clap_xwnd),
+    Wayland(*mut c_void),
+}
+
+impl Window {
+    pub const fn api(&self) -> WindowApi {
+        match self {
+            Window::Win32(_) => WindowApi::Win32,
+            Window::Cocoa(_) => WindowApi::Cocoa,
+            Window::X11(_) => WindowApi::X11,
+            Window::Wayland(_) => WindowApi::Wayland,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The field `window.api` must be a valid, null-terminated C string. The
+    /// union `window.clap_window__` must hold the variant that corresponds to
+    /// the window API.
+    pub unsafe fn try_from_unchecked(window: &clap_window) -> Result<Self, Error> {
+        if window.api.is_null() {
+            return Err(Error::UnknownApi);
+        }
+        // SAFETY: We just checked if the pointer is non-null. The caller guarantees
+        // that it is a valid C string.
+        let api = unsafe { CStr::from_ptr(window.api) };
+        // SAFETY: The caller guarantees that the union holds the right variant.
+        Ok(match WindowApi::try_from(api)? {
+            WindowApi::Win32 => Window::Win32(unsafe { window.clap_window__.win32 }),
+            WindowApi::Cocoa => Window::Cocoa(unsafe { window.clap_window__.cocoa }),
+            WindowApi::X11 => Window::X11(unsafe { window.clap_window__.x11 }),
+            WindowApi::Wayland => Window::Wayland(unsafe { window.clap_window__.ptr }),
+        })
+    }
+
+    pub fn to_clap_window(&self) -> clap_window {
+        let clap_window__ = match *self {
+            Window::Win32(win32) => clap_window__ { win32 },
+            Window::Cocoa(cocoa) => clap_window__ { cocoa },
+            Window::X11(x11) => clap_window__ { x11 },
+            Window::Wayland(ptr) => clap_window__ { ptr },
+        };
+
+        clap_window {
+            api: self.api().as_cstr().as_ptr(),
+            clap_window__,
+        }
+    }
+}
+
+/// Information to improve window resizing when initiated by the host or
+/// window manager.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ResizeHints {
+    pub can_resize_horizontally: bool,
+    pub can_resize_vertically: bool,
+    /// Only if can resize horizontally and vertically.
+    pub preserve_aspect_ratio: bool,
+    pub aspect_ratio_width: u32,
+    pub aspect_ratio_height: u32,
+}
+
+impl From<clap_gui_resize_hints> for ResizeHints {
+    fn from(value: clap_gui_resize_hints) -> Self {
+        Self {
+            can_resize_horizontally: value.can_resize_horizontally,
+            can_resize_vertically: value.can_resize_vertically,
+            preserve_aspect_ratio: value.preserve_aspect_ratio,
+            aspect_ratio_width: value.aspect_ratio_width,
+            aspect_ratio_height: value.aspect_ratio_height,
+        }
+    }
+}
+
+impl From<ResizeHints> for clap_gui_resize_hints {
+    fn from(value: ResizeHints) -> Self {
+        Self {
+            can_resize_horizontally: value.can_resize_horizontally,
+            can_resize_vertically: value.can_resize_vertically,
+            preserve_aspect_ratio: value.preserve_aspect_ratio,
+            aspect_ratio_width: value.aspect_ratio_width,
+            aspect_ratio_height: value.aspect_ratio_height,
+        }
+    }
+}
+
+/// Size (width, height) is in pixels; the corresponding windowing system
+/// extension is responsible for defining if it is physical pixels or logical
+/// pixels.
+pub trait Gui<P: Plugin> {
+    /// Returns true if the requested gui api is supported, either in floating
+    /// (plugin-created) or non-floating (embedded) mode.
+    fn is_api_supported(plugin: &P, api: WindowApi, is_floating: bool) -> bool;
+
+    /// Returns the preferred API, and whether the plugin prefers a floating
+    /// window.
+    ///
+    /// The default implementation returns `None`.
+    #[allow(unused_variables)]
+    fn get_preferred_api(plugin: &P) -> Option<(WindowApi, bool)> {
+        None
+    }
+
+    /// Create and allocate all resources necessary for the gui.
+    ///
+    /// If `is_floating` is true, then the window will not be managed by the
+    /// host. The plugin can set its window to stays above the parent window,
+    /// see [`Gui::set_transient()`].
+    ///
+    /// If `is_floating` is false, then the plugin has to embed its window into
+    /// the parent window, see [`Gui::set_parent()`].
+    ///
+    /// After this call, the GUI may not be visible yet; don't forget to call
+    /// [`Gui::show()`].
+    fn create(plugin: &mut P, api: WindowApi, is_floating: bool) -> Result<(), crate::Error>;
+
+    /// Free all resources associated with the gui.
+    fn destroy(plugin: &mut P);
+
+    /// Set the absolute GUI scaling factor, and override any OS info.
+    ///
+    /// Should not be used if the windowing api relies upon logical pixels.
+    /// Return `Err` if the plugin prefers to work out the scaling factor
+    /// itself by querying the OS directly.
+    fn set_scale(plugin: &mut P, scale: f64) -> Result<(), crate::Error>;
+
+    /// Get the current size of the plugin UI.
+    ///
+    /// [`Gui::create()`] must have been called prior to asking the size.
+    fn get_size(plugin: &P) -> Option<(u32, u32)>;
+
+    /// Returns true if the window is resizeable (mouse drag).
+    ///
+    /// The default implementation returns `false`.
+    #[allow(unused_variables)]
+    fn can_resize(plugin: &P) -> bool {
+        false
+    }
+
+    /// Returns `Some` if the plugin can provide hints on how to resize the
+    /// window.
+    ///
+    /// The default implementation returns `None`.
+    #[allow(unused_variables)]
+    fn get_resize_hints(plugin: &P) -> Option<ResizeHints> {
+        None
+    }
+
+    /// If the plugin gui is resizable, then the plugin will calculate the
+    /// closest usable size which fits in the given size.
+    ///
+    /// This method does not change the size. The default implementation
+    /// returns `None`.
+    #[allow(unused_variables)]
+    fn adjust_size(plugin: &P, width: u32, height: u32) -> Option<(u32, u32)> {
+        None
+    }
+
+    /// Sets the window size.
+    fn set_size(plugin: &mut P, width: u32, height: u32) -> Result<(), crate::Error>;
+
+    /// Embeds the plugin window into the given window.
+    fn set_parent(plugin: &mut P, window: Window) -> Result<(), crate::Error>;
+
+    /// Set the plugin floating window to stay above the given window.
+    ///
+    /// The default implementation returns an error.
+    #[allow(unused_variables)]
+    fn set_transient(plugin: &mut P, window: Window) -> Result<(), crate::Error> {
+        Err(Error::Unsupported.into())
+    }
+
+    /// Suggests a window title. Only for floating windows.
+    ///
+    /// The default implementation does nothing.
+    #[allow(unused_variables)]
+    fn suggest_title(plugin: &mut P, title: &str) {}
+
+    /// Show the window.
+    fn show(plugin: &mut P) -> Result<(), crate::Error>;
+
+    /// Hide the window, this method does not free the resources, it just hides
+    /// the window content. Yet it may be a good idea to stop painting timers.
+    fn hide(plugin: &mut P) -> Result<(), crate::Error>;
+}
+
+impl<P: Plugin> Gui<P> for () {
+    fn is_api_supported(_: &P, _: WindowApi, _: bool) -> bool {
+        false
+    }
+
+    fn create(_: &mut P, _: WindowApi, _: bool) -> Result<(), crate::Error> {
+        Err(Error::Unsupported.into())
+    }
+
+    fn destroy(_: &mut P) {}
+
+    fn set_scale(_: &mut P, _: f64) -> Result<(), crate::Error> {
+        Err(Error::Unsupported.into())
+    }
+
+    fn get_size(_: &P) -> Option<(u32, u32)> {
+        None
+    }
+
+    fn set_size(_: &mut P, _: u32, _: u32) -> Result<(), crate::Error> {
+        Err(Error::Unsupported.into())
+    }
+
+    fn set_parent(_: &mut P, _: Window) -> Result<(), crate::Error> {
+        Err(Error::Unsupported.into())
+    }
+
+    fn show(_: &mut P) -> Result<(), crate::Error> {
+        Err(Error::Unsupported.into())
+    }
+
+    fn hide(_: &mut P) -> Result<(), crate::Error> {
+        Err(Error::Unsupported.into())
+    }
+}
+
+pub(crate) use ffi::PluginGui;
+
+mod ffi {
+    use std::{
+        ffi::{CStr, c_char},
+        marker::PhantomData,
+    };
+
+    use crate::{
+        ext::gui::{Gui, Window, WindowApi},
+        ffi::{clap_gui_resize_hints, clap_plugin, clap_plugin_gui, clap_window},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    /// # Safety
+    ///
+    /// The pointer `api`, if non-null, must be a valid C string.
+    unsafe fn window_api(api: *const c_char) -> Option<WindowApi> {
+        if api.is_null() {
+            return None;
+        }
+        // SAFETY: We just checked if the pointer is non-null. The caller guarantees
+        // that it is a valid C string.
+        WindowApi::try_from(unsafe { CStr::from_ptr(api) }).ok()
+    }
+
+    extern "C-unwind" fn is_api_supported<E, P>(
+        plugin: *const clap_plugin,
+        api: *const c_char,
+        is_floating: bool,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: The host guarantees that `api` is a valid C string.
+        let Some(api) = (unsafe { window_api(api) }) else {
+            return false;
+        };
+
+        E::is_api_supported(plugin, api, is_floating)
+    }
+
+    extern "C-unwind" fn get_preferred_api<E, P>(
+        plugin: *const clap_plugin,
+        api: *mut *const c_char,
+        is_floating: *mut bool,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() || api.is_null() || is_floating.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        let Some((preferred_api, floating)) = E::get_preferred_api(plugin) else {
+            return false;
+        };
+
+        // SAFETY: We just checked that the pointers are non-null. The API string
+        // has a static lifetime.
+        unsafe {
+            *api = preferred_api.as_cstr().as_ptr();
+            *is_floating = floating;
+        }
+        true
+    }
+
+    extern "C-unwind" fn create<E, P>(
+        plugin: *const clap_plugin,
+        api: *const c_char,
+        is_floating: bool,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: The host guarantees that `api` is a valid C string.
+        let Some(api) = (unsafe { window_api(api) }) else {
+            return false;
+        };
+
+        E::create(plugin, api, is_floating).is_ok()
+    }
+
+    extern "C-unwind" fn destroy<E, P>(plugin: *const clap_plugin)
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::destroy(plugin)
+    }
+
+    extern "C-unwind" fn set_scale<E, P>(plugin: *const clap_plugin, scale: f64) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::set_scale(plugin, scale).is_ok()
+    }
+
+    extern "C-unwind" fn get_size<E, P>(
+        plugin: *const clap_plugin,
+        width: *mut u32,
+        height: *mut u32,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() || width.is_null() || height.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        let Some((w, h)) = E::get_size(plugin) else {
+            return false;
+        };
+
+        // SAFETY: We just checked that the pointers are non-null.
+        unsafe {
+            *width = w;
+            *height = h;
+        }
+        true
+    }
+
+    extern "C-unwind" fn can_resize<E, P>(plugin: *const clap_plugin) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::can_resize(plugin)
+    }
+
+    extern "C-unwind" fn get_resize_hints<E, P>(
+        plugin: *const clap_plugin,
+        hints: *mut clap_gui_resize_hints,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() || hints.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        let Some(resize_hints) = E::get_resize_hints(plugin) else {
+            return false;
+        };
+
+        // SAFETY: We just checked that the pointer is non-null.
+        unsafe { *hints = resize_hints.into() };
+        true
+    }
+
+    extern "C-unwind" fn adjust_size<E, P>(
+        plugin: *const clap_plugin,
+        width: *mut u32,
+        height: *mut u32,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() || width.is_null() || height.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: We just checked that the pointers are non-null.
+        let Some((w, h)) = E::adjust_size(plugin, unsafe { *width }, unsafe { *height }) else {
+            return false;
+        };
+
+        // SAFETY: We just checked that the pointers are non-null.
+        unsafe {
+            *width = w;
+            *height = h;
+        }
+        true
+    }
+
+    extern "C-unwind" fn set_size<E, P>(plugin: *const clap_plugin, width: u32, height: u32) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::set_size(plugin, width, height).is_ok()
+    }
+
+    extern "C-unwind" fn set_parent<E, P>(
+        plugin: *const clap_plugin,
+        window: *const clap_window,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: The host guarantees that the window, if non-null, is valid for the
+        // duration of this call.
+        let Some(window) = (unsafe { window.as_ref() }) else {
+            return false;
+        };
+        let Ok(window) = (unsafe { Window::try_from_unchecked(window) }) else {
+            return false;
+        };
+
+        E::set_parent(plugin, window).is_ok()
+    }
+
+    extern "C-unwind" fn set_transient<E, P>(
+        plugin: *const clap_plugin,
+        window: *const clap_window,
+    ) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: The host guarantees that the window, if non-null, is valid for the
+        // duration of this call.
+        let Some(window) = (unsafe { window.as_ref() }) else {
+            return false;
+        };
+        let Ok(window) = (unsafe { Window::try_from_unchecked(window) }) else {
+            return false;
+        };
+
+        E::set_transient(plugin, window).is_ok()
+    }
+
+    extern "C-unwind" fn suggest_title<E, P>(plugin: *const clap_plugin, title: *const c_char)
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() || title.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: We just checked if the pointer is non-null. The host guarantees
+        // that it is a valid C string.
+        let Ok(title) = unsafe { CStr::from_ptr(title) }.to_str() else {
+            return;
+        };
+
+        E::suggest_title(plugin, title)
+    }
+
+    extern "C-unwind" fn show<E, P>(plugin: *const clap_plugin) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::show(plugin).is_ok()
+    }
+
+    extern "C-unwind" fn hide<E, P>(plugin: *const clap_plugin) -> bool
+    where
+        E: Gui<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::hide(plugin).is_ok()
+    }
+
+    pub(crate) struct PluginGui<P> {
+        #[allow(unused)]
+        clap_plugin_gui: clap_plugin_gui,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginGui<P> {
+        pub(crate) fn new<E: Gui<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_gui: clap_plugin_gui {
+                    is_api_supported: Some(is_api_supported::<E, P>),
+                    get_preferred_api: Some(get_preferred_api::<E, P>),
+                    create: Some(create::<E, P>),
+                    destroy: Some(destroy::<E, P>),
+                    set_scale: Some(set_scale::<E, P>),
+                    get_size: Some(get_size::<E, P>),
+                    can_resize: Some(can_resize::<E, P>),
+                    get_resize_hints: Some(get_resize_hints::<E, P>),
+                    adjust_size: Some(adjust_size::<E, P>),
+                    set_size: Some(set_size::<E, P>),
+                    set_parent: Some(set_parent::<E, P>),
+                    set_transient: Some(set_transient::<E, P>),
+                    suggest_title: Some(suggest_title::<E, P>),
+                    show: Some(show::<E, P>),
+                    hide: Some(hide::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HostGui<'a> {
+    host: &'a Host,
+    clap_host_gui: &'a clap_host_gui,
+}
+
+impl<'a> HostGui<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_gui: &'a clap_host_gui,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_gui,
+        }
+    }
+
+    /// The host should call [`Gui::get_resize_hints()`] again.
+    pub fn resize_hints_changed(&self) {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_gui.resize_hints_changed.unwrap();
+        unsafe { callback(self.host.clap_host()) }
+    }
+
+    /// Request the host to resize the client area to width, height.
+    ///
+    /// Returns true if the new size is accepted, false otherwise. The host
+    /// doesn't have to call [`Gui::set_size()`].
+    pub fn request_resize(&self, width: u32, height: u32) -> bool {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_gui.request_resize.unwrap();
+        unsafe { callback(self.host.clap_host(), width, height) }
+    }
+
+    /// Request the host to show the plugin gui.
+    ///
+    /// Returns true on success, false otherwise.
+    pub fn request_show(&self) -> bool {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_gui.request_show.unwrap();
+        unsafe { callback(self.host.clap_host()) }
+    }
+
+    /// Request the host to hide the plugin gui.
+    ///
+    /// Returns true on success, false otherwise.
+    pub fn request_hide(&self) -> bool {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_gui.request_hide.unwrap();
+        unsafe { callback(self.host.clap_host()) }
+    }
+
+    /// The floating window has been closed, or the connection to the gui has
+    /// been lost.
+    ///
+    /// If `was_destroyed` is true, then the host must call
+    /// [`Gui::destroy()`] to acknowledge the gui destruction.
+    pub fn closed(&self, was_destroyed: bool) {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_gui.closed.unwrap();
+        unsafe { callback(self.host.clap_host(), was_destroyed) }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Error {
+    UnknownApi,
+    Unsupported,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownApi => write!(f, "unknown window API"),
+            Error::Unsupported => write!(f, "operation not supported"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::Gui(value).into()
+    }
+}
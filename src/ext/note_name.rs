@@ -0,0 +1,218 @@
+//! Note names.
+//!
+//! Lets the plugin name individual keys, e.g. the pads of a drum machine.
+
+use std::{
+    ffi::CStr,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    ffi::{clap_host_note_name, clap_note_name},
+    host::Host,
+    plugin::Plugin,
+};
+
+/// Describes a note name.
+///
+/// The fields `port`, `key` and `channel` equal to -1 mean every port, key or
+/// channel, respectively.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteNameInfo {
+    pub name: String,
+    pub port: i16,
+    pub key: i16,
+    pub channel: i16,
+}
+
+impl Default for NoteNameInfo {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            port: -1,
+            key: -1,
+            channel: -1,
+        }
+    }
+}
+
+impl NoteNameInfo {
+    /// # Safety
+    ///
+    /// The `value` field: 'name' must be a valid, null-terminated C string.
+    pub unsafe fn try_from_unchecked(value: clap_note_name) -> Result<Self, Error> {
+        Ok(Self {
+            // SAFETY: The safety condition is upheld by the caller.
+            name: unsafe { CStr::from_ptr(value.name.as_ptr()) }
+                .to_str()?
+                .to_owned(),
+            port: value.port,
+            key: value.key,
+            channel: value.channel,
+        })
+    }
+}
+
+pub trait NoteName<P: Plugin> {
+    /// Return the number of note names.
+    fn count(plugin: &P) -> u32;
+
+    /// Returns the note name at the given index.
+    fn get(plugin: &P, index: u32) -> Option<NoteNameInfo>;
+}
+
+impl<P: Plugin> NoteName<P> for () {
+    fn count(_: &P) -> u32 {
+        0
+    }
+
+    fn get(_: &P, _: u32) -> Option<NoteNameInfo> {
+        None
+    }
+}
+
+pub(crate) use ffi::PluginNoteName;
+
+mod ffi {
+    use std::marker::PhantomData;
+
+    use crate::{
+        ext::{copy_utf8_to_cstr, note_name::NoteName},
+        ffi::{clap_note_name, clap_plugin, clap_plugin_note_name},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    extern "C-unwind" fn count<E, P>(plugin: *const clap_plugin) -> u32
+    where
+        E: NoteName<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return 0;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::count(plugin)
+    }
+
+    extern "C-unwind" fn get<E, P>(
+        plugin: *const clap_plugin,
+        index: u32,
+        note_name: *mut clap_note_name,
+    ) -> bool
+    where
+        E: NoteName<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: We just checked if note_name is non-null.
+        let Some(note_name) = (unsafe { note_name.as_mut() }) else {
+            return false;
+        };
+        let Some(info) = E::get(plugin, index) else {
+            return false;
+        };
+
+        // SAFETY: `note_name.name.len() > 0`, and the buffers aren't overlapping.
+        unsafe { copy_utf8_to_cstr(&info.name, &mut note_name.name) };
+        note_name.port = info.port;
+        note_name.key = info.key;
+        note_name.channel = info.channel;
+        true
+    }
+
+    pub(crate) struct PluginNoteName<P> {
+        #[allow(unused)]
+        clap_plugin_note_name: clap_plugin_note_name,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginNoteName<P> {
+        pub(crate) fn new<E: NoteName<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_note_name: clap_plugin_note_name {
+                    count: Some(count::<E, P>),
+                    get: Some(get::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HostNoteName<'a> {
+    host: &'a Host,
+    clap_host_note_name: &'a clap_host_note_name,
+}
+
+impl<'a> HostNoteName<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_note_name: &'a clap_host_note_name,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_note_name,
+        }
+    }
+
+    /// Informs the host that the note names have changed.
+    pub fn changed(&self) {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_note_name.changed.unwrap();
+        unsafe { callback(self.host.clap_host()) }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Error {
+    Utf8Error(std::str::Utf8Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Utf8Error(e) => write!(f, "UTF-8 encoding error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::str::Utf8Error> for Error {
+    fn from(value: std::str::Utf8Error) -> Self {
+        Self::Utf8Error(value)
+    }
+}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::NoteName(value).into()
+    }
+}
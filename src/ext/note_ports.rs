@@ -1,4 +1,7 @@
-use std::fmt::{Display, Formatter};
+use std::{
+    ffi::CStr,
+    fmt::{Display, Formatter},
+};
 
 use crate::{
     ffi::{
@@ -59,6 +62,21 @@ pub struct NotePortInfo {
     pub name: String,
 }
 
+impl From<&clap_note_port_info> for NotePortInfo {
+    fn from(value: &clap_note_port_info) -> Self {
+        // SAFETY: c_char and u8 have the same size and alignment.
+        let name = unsafe { &*(&raw const value.name).cast::<[u8; 256]>() };
+        Self {
+            id: value.id.try_into().unwrap_or(ClapId::invalid_id()),
+            supported_dialects: value.supported_dialects,
+            preferred_dialect: value.preferred_dialect,
+            name: CStr::from_bytes_until_nul(name)
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 impl NotePortInfo {
     pub(super) fn fill_clap_note_port_info(&self, info: &mut clap_note_port_info) {
         info.id = self.id.into();
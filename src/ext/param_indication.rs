@@ -0,0 +1,266 @@
+//! Parameter indication.
+//!
+//! This extension lets the host tell the plugin which parameters are mapped to
+//! a physical controller, and what their automation state is. The plugin can
+//! use this information to draw the host's colours on its knobs.
+//!
+//! The color semantic depends upon the host. Note that the host may pass no
+//! color, in which case the plugin should use its own default.
+
+use std::fmt::{Display, Formatter};
+
+use crate::{
+    color::Color,
+    ffi::{
+        CLAP_PARAM_INDICATION_AUTOMATION_NONE, CLAP_PARAM_INDICATION_AUTOMATION_OVERRIDING,
+        CLAP_PARAM_INDICATION_AUTOMATION_PLAYING, CLAP_PARAM_INDICATION_AUTOMATION_PRESENT,
+        CLAP_PARAM_INDICATION_AUTOMATION_RECORDING,
+    },
+    id::ClapId,
+    plugin::Plugin,
+};
+
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::param_indication::AutomationState;
+/// assert_eq!(AutomationState::try_from(2), Ok(AutomationState::Playing));
+/// assert_eq!(u32::from(AutomationState::Recording), 3);
+/// assert!(AutomationState::try_from(5).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AutomationState {
+    /// The host doesn't have an automation for this parameter.
+    None,
+    /// The host has an automation for this parameter, but it isn't playing it.
+    Present,
+    /// The host is playing an automation for this parameter.
+    Playing,
+    /// The host is recording an automation on this parameter.
+    Recording,
+    /// The host should play an automation for this parameter, but the user has
+    /// started to adjust this parameter and is overriding the automation
+    /// playback.
+    Overriding,
+}
+
+impl TryFrom<u32> for AutomationState {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            CLAP_PARAM_INDICATION_AUTOMATION_NONE => Ok(AutomationState::None),
+            CLAP_PARAM_INDICATION_AUTOMATION_PRESENT => Ok(AutomationState::Present),
+            CLAP_PARAM_INDICATION_AUTOMATION_PLAYING => Ok(AutomationState::Playing),
+            CLAP_PARAM_INDICATION_AUTOMATION_RECORDING => Ok(AutomationState::Recording),
+            CLAP_PARAM_INDICATION_AUTOMATION_OVERRIDING => Ok(AutomationState::Overriding),
+            _ => Err(Error::UnknownAutomationState(value)),
+        }
+    }
+}
+
+impl From<AutomationState> for u32 {
+    fn from(value: AutomationState) -> Self {
+        match value {
+            AutomationState::None => CLAP_PARAM_INDICATION_AUTOMATION_NONE,
+            AutomationState::Present => CLAP_PARAM_INDICATION_AUTOMATION_PRESENT,
+            AutomationState::Playing => CLAP_PARAM_INDICATION_AUTOMATION_PLAYING,
+            AutomationState::Recording => CLAP_PARAM_INDICATION_AUTOMATION_RECORDING,
+            AutomationState::Overriding => CLAP_PARAM_INDICATION_AUTOMATION_OVERRIDING,
+        }
+    }
+}
+
+pub trait ParamIndication<P: Plugin> {
+    /// Sets or clears a mapping indication.
+    ///
+    /// `has_mapping`: does the parameter currently have a mapping?
+    /// `color`: if set, the color to use to highlight the control in the
+    /// plugin GUI.
+    /// `label`: if set, a small string to display on top of the knob which
+    /// identifies the hardware controller.
+    /// `description`: if set, a string which can be used in a tooltip, which
+    /// describes the current mapping.
+    fn set_mapping(
+        plugin: &mut P,
+        param_id: ClapId,
+        has_mapping: bool,
+        color: Option<Color>,
+        label: Option<&str>,
+        description: Option<&str>,
+    );
+
+    /// Sets or clears an automation indication.
+    ///
+    /// `color`: if set, the color to use to display the automation indication
+    /// in the plugin GUI.
+    fn set_automation(
+        plugin: &mut P,
+        param_id: ClapId,
+        automation_state: AutomationState,
+        color: Option<Color>,
+    );
+}
+
+impl<P: Plugin> ParamIndication<P> for () {
+    fn set_mapping(
+        _: &mut P,
+        _: ClapId,
+        _: bool,
+        _: Option<Color>,
+        _: Option<&str>,
+        _: Option<&str>,
+    ) {
+    }
+
+    fn set_automation(_: &mut P, _: ClapId, _: AutomationState, _: Option<Color>) {}
+}
+
+pub(crate) use ffi::PluginParamIndication;
+
+mod ffi {
+    use std::{
+        ffi::{CStr, c_char},
+        marker::PhantomData,
+    };
+
+    use crate::{
+        color::Color,
+        ext::param_indication::{AutomationState, ParamIndication},
+        ffi::{clap_color, clap_id, clap_plugin, clap_plugin_param_indication},
+        id::ClapId,
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    /// # Safety
+    ///
+    /// If non-null, `color` must point to a valid `clap_color`.
+    unsafe fn color_from_ptr(color: *const clap_color) -> Option<Color> {
+        // SAFETY: The safety condition is upheld by the caller.
+        unsafe { color.as_ref() }.map(|&c| c.into())
+    }
+
+    /// # Safety
+    ///
+    /// If non-null, `s` must be a valid, null-terminated C string.
+    unsafe fn str_from_ptr<'a>(s: *const c_char) -> Option<&'a str> {
+        // SAFETY: The safety condition is upheld by the caller.
+        (!s.is_null())
+            .then(|| unsafe { CStr::from_ptr(s) }.to_str().ok())
+            .flatten()
+    }
+
+    extern "C-unwind" fn set_mapping<E, P>(
+        plugin: *const clap_plugin,
+        param_id: clap_id,
+        has_mapping: bool,
+        color: *const clap_color,
+        label: *const c_char,
+        description: *const c_char,
+    ) where
+        E: ParamIndication<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return;
+        }
+        let Ok(param_id) = ClapId::try_from(param_id) else {
+            return;
+        };
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: The host guarantees that the pointers, if non-null, are valid
+        // for the duration of this call.
+        let (color, label, description) = unsafe {
+            (
+                color_from_ptr(color),
+                str_from_ptr(label),
+                str_from_ptr(description),
+            )
+        };
+
+        E::set_mapping(plugin, param_id, has_mapping, color, label, description)
+    }
+
+    extern "C-unwind" fn set_automation<E, P>(
+        plugin: *const clap_plugin,
+        param_id: clap_id,
+        automation_state: u32,
+        color: *const clap_color,
+    ) where
+        E: ParamIndication<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return;
+        }
+        let Ok(param_id) = ClapId::try_from(param_id) else {
+            return;
+        };
+        let Ok(automation_state) = AutomationState::try_from(automation_state) else {
+            return;
+        };
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: The host guarantees that the pointer, if non-null, is valid
+        // for the duration of this call.
+        let color = unsafe { color_from_ptr(color) };
+
+        E::set_automation(plugin, param_id, automation_state, color)
+    }
+
+    pub(crate) struct PluginParamIndication<P> {
+        #[allow(unused)]
+        clap_plugin_param_indication: clap_plugin_param_indication,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginParamIndication<P> {
+        pub(crate) fn new<E: ParamIndication<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_param_indication: clap_plugin_param_indication {
+                    set_mapping: Some(set_mapping::<E, P>),
+                    set_automation: Some(set_automation::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Error {
+    UnknownAutomationState(u32),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownAutomationState(state) => write!(f, "unknown automation state: {state}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::ParamIndication(value).into()
+    }
+}
@@ -24,6 +24,19 @@ use crate::{
     plugin::Plugin,
 };
 
+mod format;
+pub mod modulation;
+pub mod smoothing;
+mod store;
+mod typed;
+
+pub use clap_clap_derive::{ParamEnum, Params};
+pub use format::{
+    Decibels, FormatError, Hertz, Labels, Mapping, Pan, Percent, Seconds, Semitones, ValueFormat,
+};
+pub use store::ParamStore;
+pub use typed::{BoolParam, EnumParam, FloatParam, IntParam, Param, ParamEnum, ParamSet, ParamsOf};
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u32)]
 pub enum InfoFlags {
@@ -174,9 +187,9 @@ pub trait Params<P: Plugin> {
     /// isn't required, also be aware that the plugin may use the sample
     /// offset in process(), while this information would be lost within
     /// flush().
-    fn flush_inactive(plugin: &P, in_events: &InputEvents, out_events: &OutputEvents);
+    fn flush_inactive(plugin: &P, in_events: &InputEvents, out_events: &mut OutputEvents);
 
-    fn flush(audio_thread: &P::AudioThread, in_events: &InputEvents, out_events: &OutputEvents);
+    fn flush(audio_thread: &P::AudioThread, in_events: &InputEvents, out_events: &mut OutputEvents);
 }
 
 impl<P: Plugin> Params<P> for () {
@@ -200,9 +213,9 @@ impl<P: Plugin> Params<P> for () {
         Ok(0.0)
     }
 
-    fn flush_inactive(_: &P, _: &InputEvents, _: &OutputEvents) {}
+    fn flush_inactive(_: &P, _: &InputEvents, _: &mut OutputEvents) {}
 
-    fn flush(_: &P::AudioThread, _: &InputEvents, _: &OutputEvents) {}
+    fn flush(_: &P::AudioThread, _: &InputEvents, _: &mut OutputEvents) {}
 }
 
 pub(crate) use ffi::PluginParams;
@@ -213,12 +226,15 @@ mod ffi {
     use std::{
         ffi::{CStr, c_char},
         marker::PhantomData,
-        ptr::{copy_nonoverlapping, slice_from_raw_parts_mut},
+        ptr::slice_from_raw_parts_mut,
     };
 
     use crate::{
         events::{InputEvents, OutputEvents},
-        ext::params::{Error, Params},
+        ext::{
+            copy_utf8_to_cstr,
+            params::{Error, Params},
+        },
         ffi::{
             clap_id, clap_input_events, clap_output_events, clap_param_info, clap_plugin,
             clap_plugin_params,
@@ -226,23 +242,6 @@ mod ffi {
         plugin::{ClapPlugin, Plugin},
     };
 
-    /// # SAFETY:
-    ///
-    /// `N` must be larger than 0. `src` and `dst` buffers must be
-    /// non-overlapping.
-    ///
-    /// A trailing null byte will be added.  At most `N-1` bytes will be copied.
-    unsafe fn copy_utf8_to_cstr<const N: usize>(src: &str, dst: &mut [c_char; N]) {
-        // N > 0, so subtracting 1 won't underflow.
-        let n = src.len().min(N - 1);
-        // SAFETY: The caller upholds the safety requirements.
-        unsafe {
-            copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr() as *mut _, n);
-        }
-        // n is within bounds.
-        dst[n] = b'\0' as _;
-    }
-
     extern "C-unwind" fn count<E, P>(plugin: *const clap_plugin) -> u32
     where
         P: Plugin,
@@ -448,7 +447,7 @@ mod ffi {
         let Some(r#out) = (unsafe { out.as_ref() }) else {
             return;
         };
-        let out_events = if out.try_push.is_some() {
+        let mut out_events = if out.try_push.is_some() {
             unsafe { OutputEvents::new_unchecked(out) }
         } else {
             return;
@@ -466,14 +465,14 @@ mod ffi {
             // we are the only function accessing audio_thread now. So a mutable reference
             // to audio_thread for the duration of this call is safe.
             let audio_thread = unsafe { clap_plugin.audio_thread() }.unwrap();
-            E::flush(audio_thread, &in_events, &out_events)
+            E::flush(audio_thread, &in_events, &mut out_events)
         } else {
             // SAFETY: This function is called on the main thread.
             // It is guaranteed that we are the only function accessing the plugin now.
             // So the mutable reference to plugin for the duration of this call is
             // safe.
             let plugin = unsafe { clap_plugin.plugin() };
-            E::flush_inactive(plugin, &in_events, &out_events);
+            E::flush_inactive(plugin, &in_events, &mut out_events);
         }
     }
 
@@ -612,9 +611,12 @@ impl<'a> HostParams<'a> {
 #[derive(Debug, PartialEq)]
 pub enum Error {
     ConvertToText(f64),
+    Format(FormatError),
     ParseFloat(Option<std::num::ParseFloatError>),
     IdError(id::Error),
     Nullptr,
+    ParseText(String),
+    UnknownParam(ClapId),
     Utf8Error(std::str::Utf8Error),
 }
 
@@ -622,9 +624,12 @@ impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::ConvertToText(val) => write!(f, "conversion from value to text: {val}"),
+            Error::Format(e) => write!(f, "value format: {e}"),
             Error::ParseFloat(e) => write!(f, "float conversion: {:?}", e),
             Error::IdError(e) => write!(f, "ClapId error: {e}"),
             Error::Nullptr => write!(f, "null pointer"),
+            Error::ParseText(text) => write!(f, "unrecognized value: {text:?}"),
+            Error::UnknownParam(id) => write!(f, "unknown parameter: {id:?}"),
             Error::Utf8Error(e) => write!(f, "UTF-8 encoding error: {e}"),
         }
     }
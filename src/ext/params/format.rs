@@ -0,0 +1,317 @@
+use std::{
+    fmt::{Debug, Display, Formatter},
+    io::Write,
+};
+
+use crate::ext::params::Error;
+
+/// Conversion of plain parameter values to text and back.
+///
+/// Use a format to implement [`Params::value_to_text`] and
+/// [`Params::text_to_value`], or set it on a [`FloatParam`] or an
+/// [`IntParam`].
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::params::{Hertz, ValueFormat};
+/// assert_eq!(Hertz.to_text(1500.0), "1.50 kHz");
+/// assert_eq!(Hertz.to_value("440 Hz").unwrap(), 440.0);
+/// assert_eq!(Hertz.to_value("1.2k").unwrap(), 1200.0);
+/// ```
+///
+/// [`Params::value_to_text`]: crate::ext::params::Params::value_to_text
+/// [`Params::text_to_value`]: crate::ext::params::Params::text_to_value
+/// [`FloatParam`]: crate::ext::params::FloatParam
+/// [`IntParam`]: crate::ext::params::IntParam
+pub trait ValueFormat: Debug + Send + Sync {
+    fn to_text(&self, value: f64) -> String;
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError>;
+
+    /// Write the text to the buffer, e.g. in [`Params::value_to_text`].
+    ///
+    /// [`Params::value_to_text`]: crate::ext::params::Params::value_to_text
+    fn write_text(&self, value: f64, mut out_buf: &mut [u8]) -> Result<(), crate::Error> {
+        Ok(out_buf.write_all(self.to_text(value).as_bytes())?)
+    }
+}
+
+/// A linear gain, shown in decibels, e.g. `"-6.02 dB"`.
+///
+/// The gain of `0.0` is shown as `"-inf dB"`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Decibels;
+
+impl ValueFormat for Decibels {
+    fn to_text(&self, value: f64) -> String {
+        if value > 0.0 {
+            format!("{:.2} dB", 20.0 * value.log10())
+        } else {
+            "-inf dB".to_string()
+        }
+    }
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError> {
+        let db = text.trim().trim_end_matches(['d', 'D', 'b', 'B']).trim();
+        if db.eq_ignore_ascii_case("-inf") {
+            return Ok(0.0);
+        }
+        Ok(10f64.powf(parse_number(db)? / 20.0))
+    }
+}
+
+/// A frequency in hertz, e.g. `"440.0 Hz"`, or `"1.50 kHz"`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Hertz;
+
+impl ValueFormat for Hertz {
+    fn to_text(&self, value: f64) -> String {
+        if value.abs() < 1000.0 {
+            format!("{value:.1} Hz")
+        } else {
+            format!("{:.2} kHz", value / 1000.0)
+        }
+    }
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError> {
+        parse_with_units(text, &[("khz", 1000.0), ("hz", 1.0), ("k", 1000.0)])
+    }
+}
+
+/// A time in seconds, e.g. `"250.0 ms"`, or `"1.50 s"`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Seconds;
+
+impl ValueFormat for Seconds {
+    fn to_text(&self, value: f64) -> String {
+        if value.abs() < 1.0 {
+            format!("{:.1} ms", value * 1000.0)
+        } else {
+            format!("{value:.2} s")
+        }
+    }
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError> {
+        parse_with_units(text, &[("ms", 0.001), ("s", 1.0)])
+    }
+}
+
+/// A fraction, shown in percent, e.g. `"50.0%"`.
+///
+/// A number without the unit is read as percent too.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Percent;
+
+impl ValueFormat for Percent {
+    fn to_text(&self, value: f64) -> String {
+        format!("{:.1}%", value * 100.0)
+    }
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError> {
+        Ok(parse_with_units(text, &[("%", 1.0)])? / 100.0)
+    }
+}
+
+/// A stereo position from `-1.0` (left) to `1.0` (right), e.g. `"L50"`,
+/// `"C"`, or `"R100"`.
+///
+/// A number without a side is read in percent, from `-100` to `100`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Pan;
+
+impl ValueFormat for Pan {
+    fn to_text(&self, value: f64) -> String {
+        let percent = (value * 100.0).round();
+        if percent < 0.0 {
+            format!("L{}", -percent)
+        } else if percent > 0.0 {
+            format!("R{percent}")
+        } else {
+            "C".to_string()
+        }
+    }
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("c") || text.eq_ignore_ascii_case("center") {
+            return Ok(0.0);
+        }
+        let side = |c: char| c.eq_ignore_ascii_case(&'l') || c.eq_ignore_ascii_case(&'r');
+        let (number, sign) = match text
+            .strip_prefix(side)
+            .map(|number| (number, &text[..1]))
+            .or_else(|| {
+                text.strip_suffix(side)
+                    .map(|n| (n, &text[text.len() - 1..]))
+            }) {
+            Some((number, side)) if side.eq_ignore_ascii_case("l") => (number, -1.0),
+            Some((number, _)) => (number, 1.0),
+            None => (text, 1.0),
+        };
+        Ok(sign * parse_number(number.trim())? / 100.0)
+    }
+}
+
+/// An interval in semitones, e.g. `"+7 st"`, or `"-0.50 st"`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Semitones;
+
+impl ValueFormat for Semitones {
+    fn to_text(&self, value: f64) -> String {
+        if value.fract() == 0.0 {
+            format!("{value:+} st")
+        } else {
+            format!("{value:+.2} st")
+        }
+    }
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError> {
+        parse_with_units(text, &[("semitones", 1.0), ("st", 1.0)])
+    }
+}
+
+/// Labels of the stepped values: `0.0`, `1.0`, `2.0`, etc.
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::params::{Labels, ValueFormat};
+/// let shape = Labels(&["Sine", "Saw", "Square"]);
+///
+/// assert_eq!(shape.to_text(1.0), "Saw");
+/// assert_eq!(shape.to_value("square").unwrap(), 2.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Labels(pub &'static [&'static str]);
+
+impl ValueFormat for Labels {
+    fn to_text(&self, value: f64) -> String {
+        let index = (value.round().max(0.0) as usize).min(self.0.len().saturating_sub(1));
+        self.0.get(index).copied().unwrap_or_default().to_string()
+    }
+
+    fn to_value(&self, text: &str) -> Result<f64, FormatError> {
+        let text = text.trim();
+        self.0
+            .iter()
+            .position(|label| label.eq_ignore_ascii_case(text))
+            .map(|index| index as f64)
+            .ok_or_else(|| FormatError::UnknownLabel(text.to_string()))
+    }
+}
+
+/// Read a number followed by one of the units (case-insensitive), and scale
+/// it. A number without a unit is not scaled.
+fn parse_with_units(text: &str, units: &[(&str, f64)]) -> Result<f64, FormatError> {
+    let text = text.trim().to_ascii_lowercase();
+    for (unit, scale) in units {
+        if let Some(number) = text.strip_suffix(unit) {
+            return Ok(parse_number(number.trim())? * scale);
+        }
+    }
+    parse_number(&text)
+}
+
+fn parse_number(text: &str) -> Result<f64, FormatError> {
+    if text.is_empty() {
+        return Err(FormatError::Empty);
+    }
+    text.parse()
+        .map_err(|_| FormatError::InvalidNumber(text.to_string()))
+}
+
+/// Mapping of plain values in `[min, max]` to normalized values in `[0, 1]`,
+/// e.g. for the position of a knob.
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::params::Mapping;
+/// let cutoff = Mapping::Logarithmic;
+///
+/// assert_eq!(cutoff.denormalize(0.5, 20.0, 20000.0).round(), 632.0);
+/// assert_eq!(cutoff.normalize(20000.0, 20.0, 20000.0), 1.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mapping {
+    Linear,
+    /// The plain value is: `min + (max - min) * normalized.powf(exponent)`.
+    ///
+    /// An exponent greater than `1.0` gives more resolution to the lower
+    /// values.
+    Skewed(f64),
+    /// The plain value is: `min * (max / min).powf(normalized)`.
+    ///
+    /// Both `min` and `max` must be positive.
+    Logarithmic,
+}
+
+impl Mapping {
+    /// A skewed mapping such that the normalized value `0.5` is mapped to
+    /// `center`.
+    pub fn with_center(min: f64, max: f64, center: f64) -> Self {
+        let t = (center - min) / (max - min);
+        if t > 0.0 && t < 1.0 {
+            Self::Skewed(t.ln() / 0.5f64.ln())
+        } else {
+            Self::Linear
+        }
+    }
+
+    /// Map the plain value to `[0, 1]`. The value is clamped to the range.
+    pub fn normalize(&self, value: f64, min: f64, max: f64) -> f64 {
+        if max == min {
+            return 0.0;
+        }
+        let value = value.clamp(min.min(max), max.max(min));
+        let linear = (value - min) / (max - min);
+        match *self {
+            Self::Linear => linear,
+            Self::Skewed(exponent) => linear.powf(exponent.recip()),
+            Self::Logarithmic => (value / min).ln() / (max / min).ln(),
+        }
+    }
+
+    /// Map the normalized value to `[min, max]`. The normalized value is
+    /// clamped to `[0, 1]`.
+    pub fn denormalize(&self, normalized: f64, min: f64, max: f64) -> f64 {
+        let normalized = normalized.clamp(0.0, 1.0);
+        match *self {
+            Self::Linear => min + (max - min) * normalized,
+            Self::Skewed(exponent) => min + (max - min) * normalized.powf(exponent),
+            Self::Logarithmic => min * (max / min).powf(normalized),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatError {
+    Empty,
+    InvalidNumber(String),
+    UnknownLabel(String),
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Empty => write!(f, "empty text"),
+            FormatError::InvalidNumber(text) => write!(f, "invalid number: {text:?}"),
+            FormatError::UnknownLabel(text) => write!(f, "unknown label: {text:?}"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl From<FormatError> for Error {
+    fn from(value: FormatError) -> Self {
+        Self::Format(value)
+    }
+}
+
+impl From<FormatError> for crate::Error {
+    fn from(value: FormatError) -> Self {
+        Error::Format(value).into()
+    }
+}
@@ -0,0 +1,294 @@
+//! Polyphonic parameter modulation.
+//!
+//! The host addresses `ParamMod` and `ParamValue` events to voices by note id,
+//! port index, channel, and key, where `-1` matches any value.  A
+//! [`ModRouter`] resolves those events to the voices of a voice pool
+//! implemented by the plugin, and keeps a modulation offset per voice for
+//! each modulatable parameter.  Per-voice automation (`ParamValue` events) is
+//! resolved to voices with [`targets`].
+//!
+//! When a voice ends its note, and the plugin sends the `NoteEnd` event, the
+//! plugin clears the offsets of the voice with [`ModRouter::note_end`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use clap_clap::{
+//! #     events::{EventBuilder, ParamMod},
+//! #     ext::params::{InfoFlags, ParamInfo, modulation::{ModRouter, NoteAddress}},
+//! #     id::ClapId,
+//! # };
+//! let cutoff = ParamInfo {
+//!     id: ClapId::from(1),
+//!     flags: InfoFlags::Modulatable as u32 | InfoFlags::ModulatablePerNoteId as u32,
+//!     name: "Cutoff".to_string(),
+//!     module: String::new(),
+//!     min_value: 20.0,
+//!     max_value: 20000.0,
+//!     default_value: 1000.0,
+//! };
+//! // Voices playing the notes with ids: 7 and 8.
+//! let voices = [7, 8].map(|note_id| Some(NoteAddress::new(note_id, 0, 0, 60)));
+//! let mut router = ModRouter::new(voices.len(), [cutoff]);
+//!
+//! let event = ParamMod::build()
+//!     .param_id(ClapId::from(1))
+//!     .note_id(8)
+//!     .port_index(-1)
+//!     .channel(-1)
+//!     .key(-1)
+//!     .amount(-100.0);
+//! router.param_mod(&voices, &event.event());
+//!
+//! assert_eq!(router.offset(0, ClapId::from(1)), 0.0);
+//! assert_eq!(router.offset(1, ClapId::from(1)), -100.0);
+//! ```
+
+use crate::{
+    events::{EventRef, Note, ParamMod, ParamValue},
+    ext::params::{InfoFlags, ParamInfo},
+    ffi::clap_id,
+    id::ClapId,
+};
+
+/// The note addressed by an event, or played by a voice.
+///
+/// In events, the value `-1` of a field is a wildcard.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NoteAddress {
+    pub note_id: i32,
+    pub port_index: i16,
+    pub channel: i16,
+    pub key: i16,
+}
+
+impl NoteAddress {
+    /// The address that matches every note.
+    pub const ALL: Self = Self::new(-1, -1, -1, -1);
+
+    pub const fn new(note_id: i32, port_index: i16, channel: i16, key: i16) -> Self {
+        Self {
+            note_id,
+            port_index,
+            channel,
+            key,
+        }
+    }
+
+    /// Check if every field is a wildcard, i.e. the event is monophonic.
+    pub const fn is_global(&self) -> bool {
+        self.note_id == -1 && self.port_index == -1 && self.channel == -1 && self.key == -1
+    }
+
+    /// Check if the note played by a voice matches this address.
+    pub const fn matches(&self, note: &NoteAddress) -> bool {
+        (self.note_id == -1 || self.note_id == note.note_id)
+            && (self.port_index == -1 || self.port_index == note.port_index)
+            && (self.channel == -1 || self.channel == note.channel)
+            && (self.key == -1 || self.key == note.key)
+    }
+}
+
+impl From<&Note<'_>> for NoteAddress {
+    fn from(note: &Note<'_>) -> Self {
+        Self::new(
+            note.note_id(),
+            note.port_index(),
+            note.channel(),
+            note.key(),
+        )
+    }
+}
+
+impl From<&ParamMod<'_>> for NoteAddress {
+    fn from(event: &ParamMod<'_>) -> Self {
+        Self::new(
+            event.note_id(),
+            event.port_index(),
+            event.channel(),
+            event.key(),
+        )
+    }
+}
+
+impl From<&ParamValue<'_>> for NoteAddress {
+    fn from(event: &ParamValue<'_>) -> Self {
+        Self::new(
+            event.note_id(),
+            event.port_index(),
+            event.channel(),
+            event.key(),
+        )
+    }
+}
+
+/// A voice of the plugin's voice pool.
+pub trait Voice {
+    /// The note played by the voice, or `None` if the voice is free.
+    fn note(&self) -> Option<NoteAddress>;
+}
+
+impl Voice for NoteAddress {
+    fn note(&self) -> Option<NoteAddress> {
+        Some(*self)
+    }
+}
+
+impl<V: Voice> Voice for Option<V> {
+    fn note(&self) -> Option<NoteAddress> {
+        self.as_ref().and_then(V::note)
+    }
+}
+
+/// The indices of the voices from the pool that play a note matching the
+/// address.
+pub fn targets<V: Voice>(voices: &[V], address: NoteAddress) -> impl Iterator<Item = usize> + '_ {
+    voices
+        .iter()
+        .enumerate()
+        .filter(move |(_, voice)| voice.note().is_some_and(|note| address.matches(&note)))
+        .map(|(index, _)| index)
+}
+
+/// Replace the fields of the address that the parameter can't be modulated by
+/// with wildcards.
+fn supported_address(flags: u32, address: NoteAddress) -> NoteAddress {
+    let field = |flag: InfoFlags, value: i16| if flag.is_set(flags) { value } else { -1 };
+    NoteAddress {
+        note_id: if InfoFlags::ModulatablePerNoteId.is_set(flags) {
+            address.note_id
+        } else {
+            -1
+        },
+        port_index: field(InfoFlags::ModulatablePerPort, address.port_index),
+        channel: field(InfoFlags::ModulatablePerChannel, address.channel),
+        key: field(InfoFlags::ModulatablePerKey, address.key),
+    }
+}
+
+/// Modulation offsets of the modulatable parameters, per voice.
+///
+/// A `ParamMod` event addressed to every note is a global modulation, applied
+/// to all voices.  Other events are applied to the matching voices only.  The
+/// offset of a parameter for a voice is the sum of both.
+///
+/// The addressing of an event by note id, port, channel, or key is taken into
+/// account only if the parameter is flagged as modulatable that way, e.g.
+/// with [`ModulatablePerKey`].  Otherwise, the field is read as a wildcard.
+///
+/// [`ModulatablePerKey`]: InfoFlags::ModulatablePerKey
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModRouter {
+    params: Box<[clap_id]>,
+    flags: Box<[u32]>,
+    voice_count: usize,
+    global: Box<[f64]>,
+    offsets: Box<[f64]>,
+}
+
+impl ModRouter {
+    /// Keep the offsets of the parameters flagged as: [`Modulatable`], for
+    /// `voice_count` voices.  Other parameters are ignored.
+    ///
+    /// [`Modulatable`]: InfoFlags::Modulatable
+    pub fn new(voice_count: usize, params: impl IntoIterator<Item = ParamInfo>) -> Self {
+        let mut params: Vec<_> = params
+            .into_iter()
+            .filter(|info| info.id.is_valid() && InfoFlags::Modulatable.is_set(info.flags))
+            .map(|info| (clap_id::from(info.id), info.flags))
+            .collect();
+        params.sort_by_key(|(id, _)| *id);
+        params.dedup_by_key(|(id, _)| *id);
+        let (params, flags): (Vec<_>, Vec<_>) = params.into_iter().unzip();
+
+        Self {
+            voice_count,
+            global: vec![0.0; params.len()].into_boxed_slice(),
+            offsets: vec![0.0; params.len() * voice_count].into_boxed_slice(),
+            params: params.into_boxed_slice(),
+            flags: flags.into_boxed_slice(),
+        }
+    }
+
+    pub fn voice_count(&self) -> usize {
+        self.voice_count
+    }
+
+    /// Check if the router keeps the offsets of the parameter.
+    pub fn contains(&self, param_id: ClapId) -> bool {
+        self.index(param_id).is_some()
+    }
+
+    fn index(&self, param_id: ClapId) -> Option<usize> {
+        self.params.binary_search(&clap_id::from(param_id)).ok()
+    }
+
+    /// Set the modulation amount of the voices addressed by the event.
+    ///
+    /// Events for other parameters are ignored.  Voices beyond the voice count
+    /// are ignored too.
+    pub fn param_mod<V: Voice>(&mut self, voices: &[V], event: &ParamMod<'_>) {
+        let Some(param) = self.index(event.param_id()) else {
+            return;
+        };
+        let address = supported_address(self.flags[param], NoteAddress::from(event));
+        if address.is_global() {
+            self.global[param] = event.amount();
+            return;
+        }
+        let count = self.params.len();
+        for voice in targets(voices, address) {
+            if let Some(offset) = self.offsets.get_mut(voice * count + param) {
+                *offset = event.amount();
+            }
+        }
+    }
+
+    /// Clear the offsets of the voice that ended its note.
+    ///
+    /// Call it when the voice is freed, i.e. when the plugin sends the
+    /// `NoteEnd` event for the voice.  Voice indices beyond the voice count
+    /// are ignored.
+    pub fn note_end(&mut self, voice: usize) {
+        self.clear_voice(voice);
+    }
+
+    /// Apply `ParamMod` events.  Other events are ignored.
+    pub fn apply<V: Voice>(&mut self, voices: &[V], event: &EventRef<'_>) {
+        if let EventRef::ParamMod(event) = event {
+            self.param_mod(voices, event);
+        }
+    }
+
+    /// Clear the offsets of the voice, e.g. when it starts a new note.
+    pub fn clear_voice(&mut self, voice: usize) {
+        let count = self.params.len();
+        if let Some(offsets) = self.offsets.get_mut(voice * count..(voice + 1) * count) {
+            offsets.fill(0.0);
+        }
+    }
+
+    /// Clear all the offsets, e.g. when the plugin is reset.
+    pub fn clear(&mut self) {
+        self.global.fill(0.0);
+        self.offsets.fill(0.0);
+    }
+
+    /// The global offset of the parameter.
+    pub fn global_offset(&self, param_id: ClapId) -> f64 {
+        self.index(param_id).map_or(0.0, |param| self.global[param])
+    }
+
+    /// The offset of the parameter for the voice, without the global offset.
+    pub fn voice_offset(&self, voice: usize, param_id: ClapId) -> f64 {
+        self.index(param_id)
+            .and_then(|param| self.offsets.get(voice * self.params.len() + param))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The total offset of the parameter for the voice.
+    pub fn offset(&self, voice: usize, param_id: ClapId) -> f64 {
+        self.global_offset(param_id) + self.voice_offset(voice, param_id)
+    }
+}
@@ -0,0 +1,158 @@
+//! Smoothing of parameter changes.
+//!
+//! A [`Smoother`] ramps from its current value to the target value in a fixed
+//! time, to avoid zipper noise when a parameter is automated.  Create it in
+//! [`Plugin::activate`] with the sample rate, retarget it with the
+//! `ParamValue` events received in [`AudioThread::process`], and read the
+//! smoothed values sample by sample, or a block at a time.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use clap_clap::ext::params::smoothing::{Smoother, Smoothing};
+//! let mut gain = Smoother::new(Smoothing::Linear, 0.01, 48000.0);
+//! gain.reset(1.0);
+//! gain.set_target(0.0);
+//!
+//! let mut block = [0.0f32; 64];
+//! gain.fill(&mut block);
+//! assert!(block.windows(2).all(|w| w[1] < w[0]));
+//! assert!(gain.is_smoothing());
+//! ```
+//!
+//! [`Plugin::activate`]: crate::plugin::Plugin::activate
+//! [`AudioThread::process`]: crate::plugin::AudioThread::process
+
+use crate::events::ParamValue;
+
+/// The shape of the ramp.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Smoothing {
+    /// Constant steps.
+    Linear,
+    /// A one-pole low-pass filter.  The ramp is steep at first and slows down
+    /// as it approaches the target.  The value is within -60 dB of the target
+    /// after the smoothing time, and then it jumps to the target.
+    Exponential,
+    /// Constant ratios, e.g. for frequencies.  Both values must be positive,
+    /// otherwise the smoother jumps to the target.
+    Logarithmic,
+}
+
+/// A ramp to the target value over a fixed number of samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Smoother {
+    smoothing: Smoothing,
+    steps: u32,
+    remaining: u32,
+    current: f64,
+    target: f64,
+    delta: f64,
+}
+
+impl Smoother {
+    /// A smoother that ramps in `time` seconds, at the sample rate
+    /// `sample_rate`.  The value is `0.0`.
+    pub fn new(smoothing: Smoothing, time: f64, sample_rate: f64) -> Self {
+        Self {
+            smoothing,
+            steps: (time * sample_rate).round().max(0.0) as u32,
+            remaining: 0,
+            current: 0.0,
+            target: 0.0,
+            delta: 0.0,
+        }
+    }
+
+    pub fn smoothing(&self) -> Smoothing {
+        self.smoothing
+    }
+
+    /// The length of the ramp in samples.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// The value of the last sample.
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    pub fn is_smoothing(&self) -> bool {
+        self.remaining > 0
+    }
+
+    /// Set the value without smoothing, e.g. when the plugin is activated.
+    pub fn reset(&mut self, value: f64) {
+        self.current = value;
+        self.target = value;
+        self.remaining = 0;
+    }
+
+    /// Ramp from the current value to the target.
+    pub fn set_target(&mut self, target: f64) {
+        self.target = target;
+        if self.steps == 0 || target == self.current {
+            return self.reset(target);
+        }
+        let steps = f64::from(self.steps);
+        self.delta = match self.smoothing {
+            Smoothing::Linear => (target - self.current) / steps,
+            Smoothing::Exponential => 0.001f64.powf(steps.recip()),
+            Smoothing::Logarithmic if self.current > 0.0 && target > 0.0 => {
+                (target / self.current).powf(steps.recip())
+            }
+            Smoothing::Logarithmic => return self.reset(target),
+        };
+        self.remaining = self.steps;
+    }
+
+    /// Ramp to the value of the event.
+    ///
+    /// The event is not checked for the parameter id.
+    pub fn apply_param_value(&mut self, event: &ParamValue<'_>) {
+        self.set_target(event.value());
+    }
+
+    /// The value of the next sample.
+    pub fn tick(&mut self) -> f64 {
+        if self.remaining == 0 {
+            return self.target;
+        }
+        self.remaining -= 1;
+        self.current = if self.remaining == 0 {
+            self.target
+        } else {
+            match self.smoothing {
+                Smoothing::Linear => self.current + self.delta,
+                Smoothing::Exponential => self.target + (self.current - self.target) * self.delta,
+                Smoothing::Logarithmic => self.current * self.delta,
+            }
+        };
+        self.current
+    }
+
+    /// Fill the buffer with the values of the next samples.
+    pub fn fill(&mut self, out: &mut [f32]) {
+        if !self.is_smoothing() {
+            return out.fill(self.target as f32);
+        }
+        for value in out {
+            *value = self.tick() as f32;
+        }
+    }
+
+    /// Fill the buffer with the values of the next samples.
+    pub fn fill_f64(&mut self, out: &mut [f64]) {
+        if !self.is_smoothing() {
+            return out.fill(self.target);
+        }
+        for value in out {
+            *value = self.tick();
+        }
+    }
+}
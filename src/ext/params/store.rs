@@ -0,0 +1,335 @@
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+use crate::{
+    events::{
+        self, EventBuilder, EventRef, GestureKind, InputEvents, OutputEvents, ParamGesture,
+        ParamValue,
+    },
+    ext::params::{Error, ParamInfo},
+    ffi::clap_id,
+    id::ClapId,
+};
+
+const GESTURE_BEGIN: u32 = 1 << 0;
+const VALUE: u32 = 1 << 1;
+const GESTURE_END: u32 = 1 << 2;
+
+/// Lock-free storage of parameter values, indexed by [`ClapId`].
+///
+/// The store is meant to be shared (e.g. with an `Arc`) between the plugin,
+/// its GUI and its audio thread:
+///
+/// * The audio thread applies the `ParamValue` events sent by the host with
+///   [`apply`].  Each change is queued, and the main thread can read it back
+///   with [`pop_change`], e.g. to update the GUI.
+/// * The main thread reports changes made by the user with [`begin_gesture`],
+///   [`change`], and [`end_gesture`]. Those changes are marked as dirty until
+///   [`send`] emits them to the host as `ParamValue` and `ParamGesture` events.
+///
+/// Implement [`Params::flush`] and [`Params::flush_inactive`] with [`flush`].
+///
+/// [`apply`]: Self::apply
+/// [`pop_change`]: Self::pop_change
+/// [`begin_gesture`]: Self::begin_gesture
+/// [`change`]: Self::change
+/// [`end_gesture`]: Self::end_gesture
+/// [`send`]: Self::send
+/// [`flush`]: Self::flush
+/// [`Params::flush`]: crate::ext::params::Params::flush
+/// [`Params::flush_inactive`]: crate::ext::params::Params::flush_inactive
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::{events::EventQueue, ext::params::{ParamInfo, ParamStore}, id::ClapId};
+/// let gain = ParamInfo {
+///     id: ClapId::from(0),
+///     flags: 0,
+///     name: "Gain".to_string(),
+///     module: String::new(),
+///     min_value: 0.0,
+///     max_value: 2.0,
+///     default_value: 1.0,
+/// };
+/// let store = ParamStore::new([gain]);
+/// assert_eq!(store.get(ClapId::from(0)), Some(1.0));
+///
+/// // The user drags the knob.
+/// store.begin_gesture(ClapId::from(0)).unwrap();
+/// store.change(ClapId::from(0), 0.5).unwrap();
+/// store.end_gesture(ClapId::from(0)).unwrap();
+///
+/// let mut out_events = EventQueue::new();
+/// store.send(&mut out_events.output_events()).unwrap();
+/// assert_eq!(out_events.len(), 3);
+/// ```
+#[derive(Debug)]
+pub struct ParamStore {
+    slots: Box<[Slot]>,
+    changes: ChangeQueue,
+}
+
+#[derive(Debug)]
+struct Slot {
+    id: clap_id,
+    value: AtomicU64,
+    pending: AtomicU32,
+}
+
+impl ParamStore {
+    /// The default capacity of the queue of changes made by the host.
+    pub const QUEUE_CAPACITY: usize = 1024;
+
+    /// Store the parameters, initialized with their default values.
+    ///
+    /// # Panic
+    ///
+    /// Panic if a parameter id is invalid or not unique.
+    pub fn new(params: impl IntoIterator<Item = ParamInfo>) -> Self {
+        Self::with_queue_capacity(params, Self::QUEUE_CAPACITY)
+    }
+
+    /// Store the parameters, and queue at most `capacity` changes made by the
+    /// host.
+    ///
+    /// # Panic
+    ///
+    /// Panic if a parameter id is invalid or not unique.
+    pub fn with_queue_capacity(
+        params: impl IntoIterator<Item = ParamInfo>,
+        capacity: usize,
+    ) -> Self {
+        let mut slots: Vec<_> = params
+            .into_iter()
+            .map(|info| {
+                assert!(info.id.is_valid(), "invalid parameter id");
+                Slot {
+                    id: info.id.into(),
+                    value: AtomicU64::new(info.default_value.to_bits()),
+                    pending: AtomicU32::new(0),
+                }
+            })
+            .collect();
+        slots.sort_by_key(|slot| slot.id);
+        if let Some(w) = slots.windows(2).find(|w| w[0].id == w[1].id) {
+            panic!("duplicate parameter id: {}", w[0].id);
+        }
+
+        Self {
+            slots: slots.into_boxed_slice(),
+            changes: ChangeQueue::new(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn contains(&self, param_id: ClapId) -> bool {
+        self.slot(param_id).is_ok()
+    }
+
+    /// The parameter ids and values, ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = (ClapId, f64)> + '_ {
+        self.slots.iter().map(|slot| (slot_id(slot), slot.get()))
+    }
+
+    fn slot(&self, param_id: ClapId) -> Result<&Slot, Error> {
+        let id = clap_id::from(param_id);
+        self.slots
+            .binary_search_by_key(&id, |slot| slot.id)
+            .map(|index| &self.slots[index])
+            .map_err(|_| Error::UnknownParam(param_id))
+    }
+
+    pub fn get(&self, param_id: ClapId) -> Option<f64> {
+        self.slot(param_id).ok().map(Slot::get)
+    }
+
+    /// Set the value without notifying the host or the main thread, e.g. when
+    /// loading the plugin's state.
+    pub fn set(&self, param_id: ClapId, value: f64) -> Result<(), Error> {
+        self.slot(param_id).map(|slot| slot.set(value))
+    }
+
+    /// Begin a gesture, e.g. the user starts dragging a knob.
+    pub fn begin_gesture(&self, param_id: ClapId) -> Result<(), Error> {
+        self.slot(param_id).map(|slot| slot.mark(GESTURE_BEGIN))
+    }
+
+    /// Change the value and notify the host.
+    pub fn change(&self, param_id: ClapId, value: f64) -> Result<(), Error> {
+        let slot = self.slot(param_id)?;
+        slot.set(value);
+        slot.mark(VALUE);
+        Ok(())
+    }
+
+    /// End a gesture, e.g. the user releases a knob.
+    pub fn end_gesture(&self, param_id: ClapId) -> Result<(), Error> {
+        self.slot(param_id).map(|slot| slot.mark(GESTURE_END))
+    }
+
+    /// Check if there are changes made on the main thread that haven't been
+    /// sent to the host yet.
+    pub fn is_dirty(&self, param_id: ClapId) -> bool {
+        self.slot(param_id)
+            .is_ok_and(|slot| slot.pending.load(Ordering::Acquire) != 0)
+    }
+
+    /// Apply a `ParamValue` event sent by the host, and queue the change for
+    /// the main thread.
+    ///
+    /// Events for unknown parameters are ignored. If the queue is full, the
+    /// change is applied but not queued.
+    pub fn apply_param_value(&self, event: &ParamValue<'_>) {
+        let param_id = event.param_id();
+        if let Ok(slot) = self.slot(param_id) {
+            slot.set(event.value());
+            self.changes.push(slot.id, event.value());
+        }
+    }
+
+    /// Apply all the `ParamValue` events from the list.
+    ///
+    /// This method must not be called concurrently from different threads.
+    pub fn apply(&self, in_events: &InputEvents) {
+        for event in in_events {
+            if let EventRef::ParamValue(event) = event {
+                self.apply_param_value(&event);
+            }
+        }
+    }
+
+    /// Emit the changes made on the main thread as events to the host.
+    ///
+    /// If an event cannot be pushed, the change remains dirty, and will be
+    /// sent again with the next call.
+    pub fn send(&self, out_events: &mut OutputEvents) -> Result<(), events::Error> {
+        for slot in &self.slots {
+            let pending = slot.pending.swap(0, Ordering::AcqRel);
+            if pending == 0 {
+                continue;
+            }
+            let id = slot_id(slot);
+            for flag in [GESTURE_BEGIN, VALUE, GESTURE_END] {
+                if pending & flag == 0 {
+                    continue;
+                }
+                let pushed = match flag {
+                    GESTURE_BEGIN => out_events
+                        .try_push(ParamGesture::build(GestureKind::Begin).param_id(id).event()),
+                    VALUE => {
+                        let value = ParamValue::build()
+                            .param_id(id)
+                            .value(slot.get())
+                            .note_id(-1)
+                            .port_index(-1)
+                            .channel(-1)
+                            .key(-1);
+                        out_events.try_push(value.event())
+                    }
+                    _ => out_events
+                        .try_push(ParamGesture::build(GestureKind::End).param_id(id).event()),
+                };
+                if let Err(e) = pushed {
+                    // Keep this and the following changes dirty.
+                    slot.mark(pending & !(flag - 1));
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the events from the host, and send the changes made on the main
+    /// thread.
+    ///
+    /// Call this method from both: [`Params::flush`] and
+    /// [`Params::flush_inactive`].
+    ///
+    /// [`Params::flush`]: crate::ext::params::Params::flush
+    /// [`Params::flush_inactive`]: crate::ext::params::Params::flush_inactive
+    pub fn flush(&self, in_events: &InputEvents, out_events: &mut OutputEvents) {
+        self.apply(in_events);
+        // Unsent changes remain dirty until the next flush.
+        let _ = self.send(out_events);
+    }
+
+    /// Take the oldest change made by the host from the queue.
+    ///
+    /// This method must not be called concurrently from different threads.
+    pub fn pop_change(&self) -> Option<(ClapId, f64)> {
+        self.changes
+            .pop()
+            .map(|(id, value)| (id.try_into().unwrap_or(ClapId::invalid_id()), value))
+    }
+}
+
+fn slot_id(slot: &Slot) -> ClapId {
+    slot.id.try_into().unwrap_or(ClapId::invalid_id())
+}
+
+impl Slot {
+    fn get(&self) -> f64 {
+        f64::from_bits(self.value.load(Ordering::Acquire))
+    }
+
+    fn set(&self, value: f64) {
+        self.value.store(value.to_bits(), Ordering::Release);
+    }
+
+    fn mark(&self, flags: u32) {
+        self.pending.fetch_or(flags, Ordering::AcqRel);
+    }
+}
+
+/// A single-producer, single-consumer ring buffer of changes.
+#[derive(Debug)]
+struct ChangeQueue {
+    ids: Box<[AtomicU32]>,
+    values: Box<[AtomicU64]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+impl ChangeQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            ids: (0..capacity).map(|_| AtomicU32::new(0)).collect(),
+            values: (0..capacity).map(|_| AtomicU64::new(0)).collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    fn push(&self, id: clap_id, value: f64) -> bool {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) >= self.ids.len() {
+            return false;
+        }
+        let index = tail % self.ids.len();
+        self.ids[index].store(id, Ordering::Relaxed);
+        self.values[index].store(value.to_bits(), Ordering::Relaxed);
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    fn pop(&self) -> Option<(clap_id, f64)> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let index = head % self.ids.len();
+        let id = self.ids[index].load(Ordering::Relaxed);
+        let value = f64::from_bits(self.values[index].load(Ordering::Relaxed));
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some((id, value))
+    }
+}
@@ -0,0 +1,574 @@
+use std::{
+    io::Write,
+    marker::PhantomData,
+    ops::RangeInclusive,
+    sync::atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering},
+};
+
+use crate::{
+    events::{EventRef, InputEvents, OutputEvents},
+    ext::params::{Error, InfoFlags, ParamInfo, Params, ValueFormat, modulation::NoteAddress},
+    id::ClapId,
+    plugin::Plugin,
+};
+
+/// A parameter with a lock-free value.
+///
+/// The value can be read and changed through a shared reference, so that the
+/// parameter can be shared between the plugin and its audio thread.
+pub trait Param: Send + Sync {
+    fn info(&self) -> &ParamInfo;
+
+    /// The current plain value.
+    fn value(&self) -> f64;
+
+    /// Set the plain value. The value is clamped to the parameter's range.
+    fn set_value(&self, value: f64);
+
+    fn value_to_text(&self, value: f64, out_buf: &mut [u8]) -> Result<(), crate::Error>;
+
+    fn text_to_value(&self, text: &str) -> Result<f64, crate::Error>;
+}
+
+/// A set of parameters.
+///
+/// Implement this trait with: `#[derive(Params)]`.
+pub trait ParamSet: Send + Sync {
+    fn count(&self) -> u32;
+
+    fn param(&self, index: u32) -> Option<&dyn Param>;
+
+    fn param_by_id(&self, param_id: ClapId) -> Option<&dyn Param> {
+        (0..self.count())
+            .filter_map(|index| self.param(index))
+            .find(|param| param.info().id == param_id)
+    }
+
+    /// Apply the `ParamValue` events from the list.
+    ///
+    /// Only the events addressed to every note are applied.  Per-voice
+    /// automation is left to the plugin, e.g. with [`modulation::targets`].
+    ///
+    /// [`modulation::targets`]: crate::ext::params::modulation::targets
+    fn apply(&self, in_events: &InputEvents) {
+        for event in in_events {
+            if let EventRef::ParamValue(event) = event {
+                if !NoteAddress::from(&event).is_global() {
+                    continue;
+                }
+                if let Some(param) = self.param_by_id(event.param_id()) {
+                    param.set_value(event.value());
+                }
+            }
+        }
+    }
+}
+
+/// Implementation of [`Params`] for a [`ParamSet`].
+///
+/// Both the plugin and its audio thread must give access to the same set of
+/// parameters with `AsRef<T>`, e.g. by holding an `Arc<T>`.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use clap_clap::prelude as clap;
+/// use clap::{BoolParam, FloatParam, ParamsOf};
+///
+/// #[derive(clap::Params)]
+/// struct GainParams {
+///     #[param(id = 0, range = 0.0..=2.0, default = 1.0, flags = Automatable)]
+///     gain: FloatParam,
+///     #[param(id = 1, name = "Bypass", flags = Bypass)]
+///     bypass: BoolParam,
+/// }
+///
+/// #[derive(Default)]
+/// struct Gain {
+///     params: Arc<GainParams>,
+/// }
+///
+/// impl AsRef<GainParams> for Gain {
+///     fn as_ref(&self) -> &GainParams {
+///         &self.params
+///     }
+/// }
+///
+/// impl clap::Extensions<Self> for Gain {
+///     fn params() -> Option<impl clap::Params<Self>> {
+///         Some(ParamsOf::<GainParams>::new())
+///     }
+/// }
+///
+/// struct AudioThread {
+///     params: Arc<GainParams>,
+/// }
+///
+/// impl AsRef<GainParams> for AudioThread {
+///     fn as_ref(&self) -> &GainParams {
+///         &self.params
+///     }
+/// }
+///
+/// impl clap::AudioThread<Gain> for AudioThread {
+///     fn process(&mut self, process: &mut clap::Process) -> Result<clap::Status, clap::Error> {
+///         let _gain = self.params.gain.get();
+///         Ok(clap::Continue)
+///     }
+/// }
+///
+/// impl clap::Plugin for Gain {
+///     type AudioThread = AudioThread;
+///     const ID: &'static str = "gain";
+///     const NAME: &'static str = "Gain";
+///
+///     fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<AudioThread, clap::Error> {
+///         let params = self.params.clone();
+///         Ok(AudioThread { params })
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ParamsOf<T>(PhantomData<fn() -> T>);
+
+impl<T> ParamsOf<T> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for ParamsOf<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P, T> Params<P> for ParamsOf<T>
+where
+    P: Plugin + AsRef<T>,
+    P::AudioThread: AsRef<T>,
+    T: ParamSet,
+{
+    fn count(plugin: &P) -> u32 {
+        plugin.as_ref().count()
+    }
+
+    fn get_info(plugin: &P, param_index: u32) -> Option<ParamInfo> {
+        plugin.as_ref().param(param_index).map(|p| p.info().clone())
+    }
+
+    fn get_value(plugin: &P, param_id: ClapId) -> Option<f64> {
+        plugin.as_ref().param_by_id(param_id).map(|p| p.value())
+    }
+
+    fn value_to_text(
+        plugin: &P,
+        param_id: ClapId,
+        value: f64,
+        out_buf: &mut [u8],
+    ) -> Result<(), crate::Error> {
+        let param = plugin.as_ref().param_by_id(param_id);
+        param
+            .ok_or(Error::UnknownParam(param_id))?
+            .value_to_text(value, out_buf)
+    }
+
+    fn text_to_value(plugin: &P, param_id: ClapId, text: &str) -> Result<f64, crate::Error> {
+        let param = plugin.as_ref().param_by_id(param_id);
+        param
+            .ok_or(Error::UnknownParam(param_id))?
+            .text_to_value(text)
+    }
+
+    fn flush_inactive(plugin: &P, in_events: &InputEvents, _: &mut OutputEvents) {
+        plugin.as_ref().apply(in_events);
+    }
+
+    fn flush(audio_thread: &P::AudioThread, in_events: &InputEvents, _: &mut OutputEvents) {
+        audio_thread.as_ref().apply(in_events);
+    }
+}
+
+fn new_info(id: ClapId, name: &str, flags: u32, max_value: f64) -> ParamInfo {
+    ParamInfo {
+        id,
+        flags,
+        name: name.to_string(),
+        module: String::new(),
+        min_value: 0.0,
+        max_value,
+        default_value: 0.0,
+    }
+}
+
+macro_rules! impl_param_builder {
+    ($($Param:ident$(<$E:ident: $Bound:ident>)?),* $(,)?) => {$(
+        impl$(<$E: $Bound>)? $Param$(<$E>)? {
+            /// The module path containing the parameter, e.g.
+            /// `"Oscillators/Wavetable 1"`.
+            pub fn module(mut self, module: &str) -> Self {
+                self.info.module = module.to_string();
+                self
+            }
+
+            /// Add the flags to the parameter's flags.
+            pub fn flags(mut self, flags: u32) -> Self {
+                self.info.flags |= flags;
+                self
+            }
+
+            pub fn info(&self) -> &ParamInfo {
+                &self.info
+            }
+        }
+    )*};
+}
+
+impl_param_builder!(FloatParam, IntParam, BoolParam, EnumParam<E: ParamEnum>);
+
+/// A continuous parameter.
+#[derive(Debug)]
+pub struct FloatParam {
+    info: ParamInfo,
+    unit: &'static str,
+    format: Option<Box<dyn ValueFormat>>,
+    value: AtomicU64,
+}
+
+impl FloatParam {
+    /// A parameter with the range: `0.0..=1.0`, and the default value: `0.0`.
+    pub fn new(id: ClapId, name: &str) -> Self {
+        Self {
+            info: new_info(id, name, 0, 1.0),
+            unit: "",
+            format: None,
+            value: AtomicU64::new(0.0f64.to_bits()),
+        }
+    }
+
+    /// # Panic
+    ///
+    /// Panic if the range is reversed, or if a bound is NaN.
+    pub fn range(mut self, range: RangeInclusive<f64>) -> Self {
+        let (min, max) = range.into_inner();
+        assert!(min <= max, "invalid parameter range: {min}..={max}");
+        (self.info.min_value, self.info.max_value) = (min, max);
+        let default = self.clamp(self.info.default_value);
+        self.default_value(default)
+    }
+
+    /// The value is clamped to the range.
+    pub fn default_value(mut self, value: f64) -> Self {
+        let value = self.clamp(value);
+        self.info.default_value = value;
+        *self.value.get_mut() = value.to_bits();
+        self
+    }
+
+    /// The unit appended to the value's text, e.g. `"dB"`.
+    pub fn unit(mut self, unit: &'static str) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Convert the value to text and back with the format, e.g. [`Decibels`].
+    /// This replaces the unit.
+    ///
+    /// [`Decibels`]: crate::ext::params::Decibels
+    pub fn format(mut self, format: impl ValueFormat + 'static) -> Self {
+        self.format = Some(Box::new(format));
+        self
+    }
+
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.value.load(Ordering::Acquire))
+    }
+
+    pub fn set(&self, value: f64) {
+        self.value
+            .store(self.clamp(value).to_bits(), Ordering::Release);
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        value.clamp(self.info.min_value, self.info.max_value)
+    }
+}
+
+impl Param for FloatParam {
+    fn info(&self) -> &ParamInfo {
+        &self.info
+    }
+
+    fn value(&self) -> f64 {
+        self.get()
+    }
+
+    fn set_value(&self, value: f64) {
+        self.set(value);
+    }
+
+    fn value_to_text(&self, value: f64, mut out_buf: &mut [u8]) -> Result<(), crate::Error> {
+        if let Some(format) = &self.format {
+            return format.write_text(value, out_buf);
+        }
+        Ok(write!(out_buf, "{value:.2}{}", with_space(self.unit))?)
+    }
+
+    fn text_to_value(&self, text: &str) -> Result<f64, crate::Error> {
+        if let Some(format) = &self.format {
+            return Ok(format.to_value(text)?);
+        }
+        let value = text.trim().trim_end_matches(self.unit).trim_end();
+        Ok(value.parse().map_err(Error::from)?)
+    }
+}
+
+/// A stepped parameter with integer values.
+#[derive(Debug)]
+pub struct IntParam {
+    info: ParamInfo,
+    unit: &'static str,
+    format: Option<Box<dyn ValueFormat>>,
+    value: AtomicI64,
+}
+
+impl IntParam {
+    /// A parameter with the range: `0..=1`, and the default value: `0`.
+    pub fn new(id: ClapId, name: &str) -> Self {
+        Self {
+            info: new_info(id, name, InfoFlags::Stepped as u32, 1.0),
+            unit: "",
+            format: None,
+            value: AtomicI64::new(0),
+        }
+    }
+
+    /// # Panic
+    ///
+    /// Panic if the range is reversed.
+    pub fn range(mut self, range: RangeInclusive<i64>) -> Self {
+        let (min, max) = range.into_inner();
+        assert!(min <= max, "invalid parameter range: {min}..={max}");
+        (self.info.min_value, self.info.max_value) = (min as f64, max as f64);
+        let default = self.info.default_value as i64;
+        self.default_value(default)
+    }
+
+    /// The value is clamped to the range.
+    pub fn default_value(mut self, value: i64) -> Self {
+        let (min, max) = (self.info.min_value as i64, self.info.max_value as i64);
+        let value = value.clamp(min, max);
+        self.info.default_value = value as f64;
+        *self.value.get_mut() = value;
+        self
+    }
+
+    /// The unit appended to the value's text, e.g. `"st"`.
+    pub fn unit(mut self, unit: &'static str) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Convert the value to text and back with the format, e.g.
+    /// [`Semitones`]. This replaces the unit.
+    ///
+    /// [`Semitones`]: crate::ext::params::Semitones
+    pub fn format(mut self, format: impl ValueFormat + 'static) -> Self {
+        self.format = Some(Box::new(format));
+        self
+    }
+
+    pub fn get(&self) -> i64 {
+        self.value.load(Ordering::Acquire)
+    }
+
+    pub fn set(&self, value: i64) {
+        let (min, max) = (self.info.min_value as i64, self.info.max_value as i64);
+        self.value.store(value.clamp(min, max), Ordering::Release);
+    }
+}
+
+impl Param for IntParam {
+    fn info(&self) -> &ParamInfo {
+        &self.info
+    }
+
+    fn value(&self) -> f64 {
+        self.get() as f64
+    }
+
+    fn set_value(&self, value: f64) {
+        self.set(value.round() as i64);
+    }
+
+    fn value_to_text(&self, value: f64, mut out_buf: &mut [u8]) -> Result<(), crate::Error> {
+        let value = value.round() as i64;
+        if let Some(format) = &self.format {
+            return format.write_text(value as f64, out_buf);
+        }
+        Ok(write!(out_buf, "{value}{}", with_space(self.unit))?)
+    }
+
+    fn text_to_value(&self, text: &str) -> Result<f64, crate::Error> {
+        if let Some(format) = &self.format {
+            return Ok(format.to_value(text)?.round());
+        }
+        let value = text.trim().trim_end_matches(self.unit).trim_end();
+        Ok(value.parse::<f64>().map_err(Error::from)?.round())
+    }
+}
+
+/// A switch, with the values: `0.0` (off) and `1.0` (on).
+#[derive(Debug)]
+pub struct BoolParam {
+    info: ParamInfo,
+    value: AtomicBool,
+}
+
+impl BoolParam {
+    /// A parameter with the default value: `false`.
+    pub fn new(id: ClapId, name: &str) -> Self {
+        Self {
+            info: new_info(id, name, InfoFlags::Stepped as u32, 1.0),
+            value: AtomicBool::new(false),
+        }
+    }
+
+    pub fn default_value(mut self, value: bool) -> Self {
+        self.info.default_value = f64::from(u8::from(value));
+        *self.value.get_mut() = value;
+        self
+    }
+
+    pub fn get(&self) -> bool {
+        self.value.load(Ordering::Acquire)
+    }
+
+    pub fn set(&self, value: bool) {
+        self.value.store(value, Ordering::Release);
+    }
+}
+
+impl Param for BoolParam {
+    fn info(&self) -> &ParamInfo {
+        &self.info
+    }
+
+    fn value(&self) -> f64 {
+        f64::from(u8::from(self.get()))
+    }
+
+    fn set_value(&self, value: f64) {
+        self.set(value >= 0.5);
+    }
+
+    fn value_to_text(&self, value: f64, mut out_buf: &mut [u8]) -> Result<(), crate::Error> {
+        let text = if value >= 0.5 { "On" } else { "Off" };
+        Ok(out_buf.write_all(text.as_bytes())?)
+    }
+
+    fn text_to_value(&self, text: &str) -> Result<f64, crate::Error> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "on" | "true" | "1" => Ok(1.0),
+            "off" | "false" | "0" => Ok(0.0),
+            _ => Err(Error::ParseText(text.to_string()).into()),
+        }
+    }
+}
+
+/// An enum with unit variants, used as the value of [`EnumParam`].
+///
+/// Implement this trait with: `#[derive(ParamEnum)]`.
+pub trait ParamEnum: Copy + Send + Sync + 'static {
+    /// All the variants, in the order of their parameter values.
+    const VARIANTS: &'static [Self];
+
+    /// The position of the variant in `VARIANTS`.
+    fn index(&self) -> usize;
+
+    /// The label of the variant.
+    fn name(&self) -> &'static str;
+}
+
+/// A parameter with the values labeled by the variants of an enum.
+#[derive(Debug)]
+pub struct EnumParam<E> {
+    info: ParamInfo,
+    value: AtomicUsize,
+    _marker: PhantomData<E>,
+}
+
+impl<E: ParamEnum> EnumParam<E> {
+    /// A parameter with the default value: `E::VARIANTS[0]`.
+    ///
+    /// # Panic
+    ///
+    /// Panic if `E::VARIANTS` is empty.
+    pub fn new(id: ClapId, name: &str) -> Self {
+        assert!(
+            !E::VARIANTS.is_empty(),
+            "enum must have at least one variant"
+        );
+        let flags = InfoFlags::Stepped as u32 | InfoFlags::Enum as u32;
+        let max_value = (E::VARIANTS.len() - 1) as f64;
+        Self {
+            info: new_info(id, name, flags, max_value),
+            value: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn default_value(mut self, value: E) -> Self {
+        self.info.default_value = value.index() as f64;
+        *self.value.get_mut() = value.index();
+        self
+    }
+
+    pub fn get(&self) -> E {
+        E::VARIANTS[self.value.load(Ordering::Acquire)]
+    }
+
+    pub fn set(&self, value: E) {
+        self.value.store(value.index(), Ordering::Release);
+    }
+
+    fn variant(value: f64) -> E {
+        let max = E::VARIANTS.len() - 1;
+        E::VARIANTS[(value.round().max(0.0) as usize).min(max)]
+    }
+}
+
+impl<E: ParamEnum> Param for EnumParam<E> {
+    fn info(&self) -> &ParamInfo {
+        &self.info
+    }
+
+    fn value(&self) -> f64 {
+        self.get().index() as f64
+    }
+
+    fn set_value(&self, value: f64) {
+        self.set(Self::variant(value));
+    }
+
+    fn value_to_text(&self, value: f64, mut out_buf: &mut [u8]) -> Result<(), crate::Error> {
+        Ok(out_buf.write_all(Self::variant(value).name().as_bytes())?)
+    }
+
+    fn text_to_value(&self, text: &str) -> Result<f64, crate::Error> {
+        E::VARIANTS
+            .iter()
+            .find(|variant| variant.name() == text.trim())
+            .map(|variant| variant.index() as f64)
+            .ok_or_else(|| Error::ParseText(text.to_string()).into())
+    }
+}
+
+fn with_space(unit: &str) -> String {
+    if unit.is_empty() {
+        String::new()
+    } else {
+        format!(" {unit}")
+    }
+}
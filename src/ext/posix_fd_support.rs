@@ -0,0 +1,185 @@
+//! Integrate file descriptors with the host's main-thread event loop.
+//!
+//! The plugin registers file descriptors with
+//! [`HostPosixFdSupport::register_fd()`], and the host calls
+//! [`PosixFdSupport::on_fd()`] on the main thread when they become ready.
+
+use std::{
+    ffi::c_int,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    ffi::{
+        CLAP_POSIX_FD_ERROR, CLAP_POSIX_FD_READ, CLAP_POSIX_FD_WRITE, clap_host_posix_fd_support,
+    },
+    host::Host,
+    impl_flags_u32,
+    plugin::Plugin,
+};
+
+/// File descriptor events to watch for.
+///
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::posix_fd_support::FdFlags;
+/// assert_eq!(FdFlags::Read as u32, 0b1);
+/// assert!(FdFlags::Write.is_set(0b110));
+/// assert_eq!(FdFlags::Error.set(FdFlags::Read as u32), 0b101);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum FdFlags {
+    /// IO events flags, they can be used to form a mask which describes:
+    /// - which events you are interested in (register_fd/modify_fd)
+    /// - which events happened (on_fd)
+    Read = CLAP_POSIX_FD_READ,
+    Write = CLAP_POSIX_FD_WRITE,
+    Error = CLAP_POSIX_FD_ERROR,
+}
+
+impl_flags_u32!(FdFlags);
+
+pub trait PosixFdSupport<P: Plugin> {
+    /// This callback is "level-triggered". It means that a writable fd will
+    /// continuously produce "on_fd()" events; don't forget using
+    /// [`HostPosixFdSupport::modify_fd()`] to remove the write notification
+    /// once you're done writing.
+    ///
+    /// The argument `flags` is a bit mask of [`FdFlags`].
+    fn on_fd(plugin: &mut P, fd: c_int, flags: u32);
+}
+
+impl<P: Plugin> PosixFdSupport<P> for () {
+    fn on_fd(_: &mut P, _: c_int, _: u32) {}
+}
+
+pub(crate) use ffi::PluginPosixFdSupport;
+
+mod ffi {
+    use std::{ffi::c_int, marker::PhantomData};
+
+    use crate::{
+        ext::posix_fd_support::PosixFdSupport,
+        ffi::{clap_plugin, clap_plugin_posix_fd_support, clap_posix_fd_flags},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    extern "C-unwind" fn on_fd<E, P>(
+        plugin: *const clap_plugin,
+        fd: c_int,
+        flags: clap_posix_fd_flags,
+    ) where
+        E: PosixFdSupport<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::on_fd(plugin, fd, flags)
+    }
+
+    pub(crate) struct PluginPosixFdSupport<P> {
+        #[allow(unused)]
+        clap_plugin_posix_fd_support: clap_plugin_posix_fd_support,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginPosixFdSupport<P> {
+        pub(crate) fn new<E: PosixFdSupport<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_posix_fd_support: clap_plugin_posix_fd_support {
+                    on_fd: Some(on_fd::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HostPosixFdSupport<'a> {
+    host: &'a Host,
+    clap_host_posix_fd_support: &'a clap_host_posix_fd_support,
+}
+
+impl<'a> HostPosixFdSupport<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_posix_fd_support: &'a clap_host_posix_fd_support,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_posix_fd_support,
+        }
+    }
+
+    /// The argument `flags` is a bit mask of [`FdFlags`].
+    pub fn register_fd(&self, fd: c_int, flags: u32) -> Result<(), Error> {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_posix_fd_support.register_fd.unwrap();
+        unsafe { callback(self.host.clap_host(), fd, flags) }
+            .then_some(())
+            .ok_or(Error::Register(fd))
+    }
+
+    /// The argument `flags` is a bit mask of [`FdFlags`].
+    pub fn modify_fd(&self, fd: c_int, flags: u32) -> Result<(), Error> {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_posix_fd_support.modify_fd.unwrap();
+        unsafe { callback(self.host.clap_host(), fd, flags) }
+            .then_some(())
+            .ok_or(Error::Modify(fd))
+    }
+
+    pub fn unregister_fd(&self, fd: c_int) -> Result<(), Error> {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_posix_fd_support.unregister_fd.unwrap();
+        unsafe { callback(self.host.clap_host(), fd) }
+            .then_some(())
+            .ok_or(Error::Unregister(fd))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Error {
+    Register(c_int),
+    Modify(c_int),
+    Unregister(c_int),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Register(fd) => write!(f, "cannot register fd: {fd}"),
+            Error::Modify(fd) => write!(f, "cannot modify fd: {fd}"),
+            Error::Unregister(fd) => write!(f, "cannot unregister fd: {fd}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::PosixFdSupport(value).into()
+    }
+}
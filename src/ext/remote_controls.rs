@@ -0,0 +1,290 @@
+//! Remote controls.
+//!
+//! This extension lets the plugin provide a structured way of mapping
+//! parameters to a hardware controller.
+//!
+//! This is done by providing a set of remote control pages organized by
+//! section. A page contains up to 8 controls, which references parameters
+//! using param_id.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use clap_clap::{ext::remote_controls::RemoteControlsPage, id::ClapId};
+//! let page = RemoteControlsPage::new(ClapId::from(1), "Filter")
+//!     .section_name("Main")
+//!     .param(0, ClapId::from(10))
+//!     .param(1, ClapId::from(11));
+//!
+//! assert_eq!(page.page_name, "Filter");
+//! assert_eq!(page.param_ids[1], ClapId::from(11));
+//! assert!(!page.param_ids[2].is_valid());
+//! ```
+
+use std::{
+    ffi::CStr,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    ffi::{CLAP_REMOTE_CONTROLS_COUNT, clap_host_remote_controls, clap_remote_controls_page},
+    host::Host,
+    id::ClapId,
+    plugin::Plugin,
+};
+
+/// The number of controls on a page.
+pub const REMOTE_CONTROLS_COUNT: usize = CLAP_REMOTE_CONTROLS_COUNT as usize;
+
+/// Describes a page of remote controls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteControlsPage {
+    pub section_name: String,
+    pub page_id: ClapId,
+    pub page_name: String,
+    /// Unused slots are set to [`ClapId::invalid_id()`].
+    pub param_ids: [ClapId; REMOTE_CONTROLS_COUNT],
+    /// This is used to separate device pages versus preset pages.
+    /// If true, then this page is specific to this preset.
+    pub is_for_preset: bool,
+}
+
+impl Default for RemoteControlsPage {
+    fn default() -> Self {
+        Self {
+            section_name: String::new(),
+            page_id: ClapId::invalid_id(),
+            page_name: String::new(),
+            param_ids: [ClapId::invalid_id(); REMOTE_CONTROLS_COUNT],
+            is_for_preset: false,
+        }
+    }
+}
+
+impl RemoteControlsPage {
+    /// Create an empty page with the given id and name.
+    pub fn new(page_id: ClapId, page_name: &str) -> Self {
+        Self {
+            page_id,
+            page_name: page_name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn section_name(mut self, section_name: &str) -> Self {
+        self.section_name = section_name.to_owned();
+        self
+    }
+
+    /// Assign a parameter to the control slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`REMOTE_CONTROLS_COUNT`].
+    pub fn param(mut self, index: usize, param_id: ClapId) -> Self {
+        self.param_ids[index] = param_id;
+        self
+    }
+
+    pub fn is_for_preset(mut self, is_for_preset: bool) -> Self {
+        self.is_for_preset = is_for_preset;
+        self
+    }
+
+    /// # Safety
+    ///
+    /// The `value` fields: 'section_name' and 'page_name' must be valid,
+    /// null-terminated C strings.
+    pub unsafe fn try_from_unchecked(value: clap_remote_controls_page) -> Result<Self, Error> {
+        Ok(Self {
+            // SAFETY: The safety condition is upheld by the caller.
+            section_name: unsafe { CStr::from_ptr(value.section_name.as_ptr()) }
+                .to_str()?
+                .to_owned(),
+            page_id: value.page_id.try_into().unwrap_or(ClapId::invalid_id()),
+            // SAFETY: The safety condition is upheld by the caller.
+            page_name: unsafe { CStr::from_ptr(value.page_name.as_ptr()) }
+                .to_str()?
+                .to_owned(),
+            param_ids: value
+                .param_ids
+                .map(|id| id.try_into().unwrap_or(ClapId::invalid_id())),
+            is_for_preset: value.is_for_preset,
+        })
+    }
+}
+
+pub trait RemoteControls<P: Plugin> {
+    /// Return the number of pages.
+    fn count(plugin: &P) -> u32;
+
+    /// Get a page by index.
+    fn get(plugin: &P, page_index: u32) -> Option<RemoteControlsPage>;
+}
+
+impl<P: Plugin> RemoteControls<P> for () {
+    fn count(_: &P) -> u32 {
+        0
+    }
+
+    fn get(_: &P, _: u32) -> Option<RemoteControlsPage> {
+        None
+    }
+}
+
+pub(crate) use ffi::PluginRemoteControls;
+
+mod ffi {
+    use std::marker::PhantomData;
+
+    use crate::{
+        ext::{copy_utf8_to_cstr, remote_controls::RemoteControls},
+        ffi::{clap_plugin, clap_plugin_remote_controls, clap_remote_controls_page},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    extern "C-unwind" fn count<E, P>(plugin: *const clap_plugin) -> u32
+    where
+        E: RemoteControls<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return 0;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::count(plugin)
+    }
+
+    extern "C-unwind" fn get<E, P>(
+        plugin: *const clap_plugin,
+        page_index: u32,
+        page: *mut clap_remote_controls_page,
+    ) -> bool
+    where
+        E: RemoteControls<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        // SAFETY: We just checked if page is non-null.
+        let Some(page) = (unsafe { page.as_mut() }) else {
+            return false;
+        };
+        let Some(info) = E::get(plugin, page_index) else {
+            return false;
+        };
+
+        // SAFETY: `page.section_name.len() > 0`, and the buffers aren't overlapping.
+        unsafe { copy_utf8_to_cstr(&info.section_name, &mut page.section_name) };
+        page.page_id = info.page_id.into();
+        // SAFETY: `page.page_name.len() > 0`, and the buffers aren't overlapping.
+        unsafe { copy_utf8_to_cstr(&info.page_name, &mut page.page_name) };
+        page.param_ids = info.param_ids.map(Into::into);
+        page.is_for_preset = info.is_for_preset;
+        true
+    }
+
+    pub(crate) struct PluginRemoteControls<P> {
+        #[allow(unused)]
+        clap_plugin_remote_controls: clap_plugin_remote_controls,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginRemoteControls<P> {
+        pub(crate) fn new<E: RemoteControls<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_remote_controls: clap_plugin_remote_controls {
+                    count: Some(count::<E, P>),
+                    get: Some(get::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HostRemoteControls<'a> {
+    host: &'a Host,
+    clap_host_remote_controls: &'a clap_host_remote_controls,
+}
+
+impl<'a> HostRemoteControls<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_remote_controls: &'a clap_host_remote_controls,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_remote_controls,
+        }
+    }
+
+    /// Informs the host that the remote controls have changed.
+    pub fn changed(&self) {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_remote_controls.changed.unwrap();
+        unsafe { callback(self.host.clap_host()) }
+    }
+
+    /// Suggest a page to the host because it corresponds to what the user is
+    /// currently editing in the plugin's GUI.
+    pub fn suggest_page(&self, page_id: ClapId) {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_remote_controls.suggest_page.unwrap();
+        unsafe { callback(self.host.clap_host(), page_id.into()) }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Error {
+    Utf8Error(std::str::Utf8Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Utf8Error(e) => write!(f, "UTF-8 encoding error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::str::Utf8Error> for Error {
+    fn from(value: std::str::Utf8Error) -> Self {
+        Self::Utf8Error(value)
+    }
+}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::RemoteControls(value).into()
+    }
+}
@@ -0,0 +1,172 @@
+//! Render mode.
+//!
+//! The render extension is used to let the plugin know if it has "realtime"
+//! pressure to process.
+//!
+//! If this information does not influence your rendering code, then don't
+//! implement this extension.
+
+use std::fmt::{Display, Formatter};
+
+use crate::{
+    ffi::{CLAP_RENDER_OFFLINE, CLAP_RENDER_REALTIME, clap_plugin_render_mode},
+    plugin::Plugin,
+};
+
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::render::RenderMode;
+/// assert_eq!(RenderMode::try_from(1), Ok(RenderMode::Offline));
+/// assert_eq!(i32::from(RenderMode::Realtime), 0);
+/// assert!(RenderMode::try_from(2).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Default setting, for "realtime" processing.
+    Realtime,
+    /// For processing without realtime pressure. The plugin may use more
+    /// expensive algorithms for higher sound quality.
+    Offline,
+}
+
+impl TryFrom<clap_plugin_render_mode> for RenderMode {
+    type Error = Error;
+
+    fn try_from(value: clap_plugin_render_mode) -> Result<Self, Self::Error> {
+        match value {
+            CLAP_RENDER_REALTIME => Ok(RenderMode::Realtime),
+            CLAP_RENDER_OFFLINE => Ok(RenderMode::Offline),
+            _ => Err(Error::UnknownMode(value)),
+        }
+    }
+}
+
+impl From<RenderMode> for clap_plugin_render_mode {
+    fn from(value: RenderMode) -> Self {
+        match value {
+            RenderMode::Realtime => CLAP_RENDER_REALTIME,
+            RenderMode::Offline => CLAP_RENDER_OFFLINE,
+        }
+    }
+}
+
+pub trait Render<P: Plugin> {
+    /// Returns true if the plugin has a hard requirement to process in
+    /// real-time. This is especially useful for plugins acting as a proxy to
+    /// hardware device.
+    fn has_hard_realtime_requirement(plugin: &P) -> bool;
+
+    /// Returns `Ok` if the rendering mode could be applied.
+    fn set(plugin: &mut P, mode: RenderMode) -> Result<(), crate::Error>;
+}
+
+impl<P: Plugin> Render<P> for () {
+    fn has_hard_realtime_requirement(_: &P) -> bool {
+        false
+    }
+
+    fn set(_: &mut P, _: RenderMode) -> Result<(), crate::Error> {
+        Ok(())
+    }
+}
+
+pub(crate) use ffi::PluginRender;
+
+mod ffi {
+    use std::marker::PhantomData;
+
+    use crate::{
+        ext::render::{Render, RenderMode},
+        ffi::{clap_plugin, clap_plugin_render, clap_plugin_render_mode},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    extern "C-unwind" fn has_hard_realtime_requirement<E, P>(plugin: *const clap_plugin) -> bool
+    where
+        E: Render<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        E::has_hard_realtime_requirement(plugin)
+    }
+
+    extern "C-unwind" fn set<E, P>(
+        plugin: *const clap_plugin,
+        mode: clap_plugin_render_mode,
+    ) -> bool
+    where
+        E: Render<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        let Ok(mode) = RenderMode::try_from(mode) else {
+            return false;
+        };
+
+        E::set(plugin, mode).is_ok()
+    }
+
+    pub(crate) struct PluginRender<P> {
+        #[allow(unused)]
+        clap_plugin_render: clap_plugin_render,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginRender<P> {
+        pub(crate) fn new<E: Render<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_render: clap_plugin_render {
+                    has_hard_realtime_requirement: Some(has_hard_realtime_requirement::<E, P>),
+                    set: Some(set::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Error {
+    UnknownMode(clap_plugin_render_mode),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownMode(mode) => write!(f, "unknown render mode: {mode}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::Render(value).into()
+    }
+}
@@ -0,0 +1,191 @@
+//! Use the host's thread pool.
+//!
+//! The plugin provides [`ThreadPool`], and the host may provide
+//! [`HostThreadPool`]. If it doesn't, the plugin should process its data by its
+//! own means. In the worst case, a single threaded for-loop.
+//!
+//! Be aware that using a thread pool may break hard real-time rules due to the
+//! thread synchronization involved.
+//!
+//! The tasks don't have access to the audio thread, which is borrowed mutably
+//! by [`AudioThread::process()`] for the duration of the request.  Instead, the
+//! audio thread passes to [`HostThreadPool::request_exec()`] the data shared by
+//! the tasks, e.g. the voices to render, and the host's worker threads call
+//! [`ThreadPool::exec()`] with a shared reference to it.
+//!
+//! [`AudioThread::process()`]: crate::plugin::AudioThread::process
+
+use std::{any::TypeId, ptr::null_mut, sync::atomic::Ordering};
+
+use crate::{ffi::clap_host_thread_pool, host::Host, plugin::Plugin};
+
+pub trait ThreadPool<P: Plugin> {
+    /// The data shared by the tasks.
+    type Shared: Sync + 'static;
+
+    /// Execute the task with the given index.
+    ///
+    /// This method is called concurrently by the host's worker threads during
+    /// [`HostThreadPool::request_exec()`], with the data passed to that call.
+    fn exec(shared: &Self::Shared, task_index: u32);
+}
+
+impl<P: Plugin> ThreadPool<P> for () {
+    type Shared = ();
+
+    fn exec(_: &(), _: u32) {}
+}
+
+/// The type of the data shared by the tasks of the thread pool `E`.
+///
+/// Take the value returned by: [`Extensions::thread_pool()`], so that the type
+/// of the thread pool can be inferred.
+///
+/// [`Extensions::thread_pool()`]: crate::ext::Extensions::thread_pool
+pub(crate) fn shared_type_id<P: Plugin, E: ThreadPool<P>>(_: &Option<E>) -> TypeId {
+    TypeId::of::<E::Shared>()
+}
+
+/// The tasks of a pending request: the type and the address of the shared
+/// data.
+#[derive(Debug)]
+pub(crate) struct Task {
+    type_id: TypeId,
+    shared: *const (),
+}
+
+impl Task {
+    /// # Safety
+    ///
+    /// The task must be published by [`HostThreadPool::request_exec()`], which
+    /// is still waiting for the host to return.
+    unsafe fn shared<T: 'static>(&self) -> Option<&T> {
+        // SAFETY: The type matches, and the shared data outlives the request.
+        (self.type_id == TypeId::of::<T>()).then(|| unsafe { &*self.shared.cast::<T>() })
+    }
+}
+
+pub(crate) use ffi::PluginThreadPool;
+
+mod ffi {
+    use std::{marker::PhantomData, sync::atomic::Ordering};
+
+    use crate::{
+        ext::thread_pool::ThreadPool,
+        ffi::{clap_plugin, clap_plugin_thread_pool},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    extern "C-unwind" fn exec<E, P>(plugin: *const clap_plugin, task_index: u32)
+    where
+        E: ThreadPool<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // The audio thread is not accessed here: it is borrowed mutably by the
+        // call to process() that waits for the request to return.
+        let task = clap_plugin.host().thread_pool_task.load(Ordering::Acquire);
+        // SAFETY: A non-null task is published by HostThreadPool::request_exec(),
+        // until the host returns from the request.  This function is called by
+        // the host during the request.
+        let Some(shared) = (unsafe { task.as_ref() }).and_then(|task| unsafe { task.shared() })
+        else {
+            return;
+        };
+
+        E::exec(shared, task_index)
+    }
+
+    pub(crate) struct PluginThreadPool<P> {
+        #[allow(unused)]
+        clap_plugin_thread_pool: clap_plugin_thread_pool,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginThreadPool<P> {
+        pub(crate) fn new<E: ThreadPool<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_thread_pool: clap_plugin_thread_pool {
+                    exec: Some(exec::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HostThreadPool<'a> {
+    host: &'a Host,
+    clap_host_thread_pool: &'a clap_host_thread_pool,
+}
+
+impl<'a> HostThreadPool<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_thread_pool: &'a clap_host_thread_pool,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_thread_pool,
+        }
+    }
+
+    /// Schedule `num_tasks` jobs in the host thread pool.  Each job calls
+    /// [`ThreadPool::exec()`] with a reference to `shared`.
+    ///
+    /// The type `T` must be the type [`ThreadPool::Shared`] of the plugin.
+    /// Otherwise, the request is not sent to the host, and the call returns
+    /// false.
+    ///
+    /// It can't be called concurrently or from the thread pool. It will return
+    /// once all the tasks are processed. This must be used exclusively for
+    /// realtime processing, i.e. from [`AudioThread::process()`].
+    ///
+    /// Returns true if the host did execute all the tasks, false if the request
+    /// was rejected. The host should check that the plugin is within the process
+    /// call, and if not, reject the exec request.
+    ///
+    /// [`AudioThread::process()`]: crate::plugin::AudioThread::process
+    pub fn request_exec<T: Sync + 'static>(&self, shared: &T, num_tasks: u32) -> bool {
+        if TypeId::of::<T>() != self.host.thread_pool_shared {
+            return false;
+        }
+        let task = Task {
+            type_id: TypeId::of::<T>(),
+            shared: (&raw const *shared).cast(),
+        };
+        let slot = &self.host.thread_pool_task;
+        // Reject concurrent and nested requests.
+        if slot
+            .compare_exchange(
+                null_mut(),
+                (&raw const task).cast_mut(),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_err()
+        {
+            return false;
+        }
+
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_thread_pool.request_exec.unwrap();
+        let done = unsafe { callback(self.host.clap_host(), num_tasks) };
+
+        // The task must not be accessed after the request returns.
+        slot.store(null_mut(), Ordering::Release);
+        done
+    }
+}
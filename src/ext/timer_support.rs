@@ -0,0 +1,169 @@
+//! Timers on the main thread.
+//!
+//! Register a timer with [`HostTimerSupport::register_timer()`], and the host
+//! will periodically call [`TimerSupport::on_timer()`] with the returned
+//! [`TimerId`].
+
+use std::fmt::{Display, Formatter};
+
+use crate::{
+    ffi::{CLAP_INVALID_ID, clap_host_timer_support, clap_id},
+    host::Host,
+    plugin::Plugin,
+};
+
+/// Timer identifier, as returned by the host.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimerId(clap_id);
+
+impl TryFrom<clap_id> for TimerId {
+    type Error = Error;
+
+    fn try_from(value: clap_id) -> Result<Self, Self::Error> {
+        (value != CLAP_INVALID_ID)
+            .then_some(Self(value))
+            .ok_or(Error::InvalidId)
+    }
+}
+
+impl From<TimerId> for clap_id {
+    fn from(value: TimerId) -> Self {
+        value.0
+    }
+}
+
+pub trait TimerSupport<P: Plugin> {
+    /// Called by the host on the main thread, each time the timer with the
+    /// given id fires.
+    fn on_timer(plugin: &mut P, timer_id: TimerId);
+}
+
+impl<P: Plugin> TimerSupport<P> for () {
+    fn on_timer(_: &mut P, _: TimerId) {}
+}
+
+pub(crate) use ffi::PluginTimerSupport;
+
+mod ffi {
+    use std::marker::PhantomData;
+
+    use crate::{
+        ext::timer_support::{TimerId, TimerSupport},
+        ffi::{clap_id, clap_plugin, clap_plugin_timer_support},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    extern "C-unwind" fn on_timer<E, P>(plugin: *const clap_plugin, timer_id: clap_id)
+    where
+        E: TimerSupport<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        let Ok(timer_id) = TimerId::try_from(timer_id) else {
+            return;
+        };
+
+        E::on_timer(plugin, timer_id)
+    }
+
+    pub(crate) struct PluginTimerSupport<P> {
+        #[allow(unused)]
+        clap_plugin_timer_support: clap_plugin_timer_support,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginTimerSupport<P> {
+        pub(crate) fn new<E: TimerSupport<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_timer_support: clap_plugin_timer_support {
+                    on_timer: Some(on_timer::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HostTimerSupport<'a> {
+    host: &'a Host,
+    clap_host_timer_support: &'a clap_host_timer_support,
+}
+
+impl<'a> HostTimerSupport<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_timer_support: &'a clap_host_timer_support,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_timer_support,
+        }
+    }
+
+    /// Register a periodic timer.
+    ///
+    /// The host may adjust the period if it is under a certain threshold.
+    /// 30 Hz should be allowed.
+    pub fn register_timer(&self, period_ms: u32) -> Result<TimerId, Error> {
+        let mut timer_id = CLAP_INVALID_ID;
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_timer_support.register_timer.unwrap();
+        if unsafe { callback(self.host.clap_host(), period_ms, &raw mut timer_id) } {
+            TimerId::try_from(timer_id)
+        } else {
+            Err(Error::Register(period_ms))
+        }
+    }
+
+    pub fn unregister_timer(&self, timer_id: TimerId) -> Result<(), Error> {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_timer_support.unregister_timer.unwrap();
+        unsafe { callback(self.host.clap_host(), timer_id.into()) }
+            .then_some(())
+            .ok_or(Error::Unregister(timer_id))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Error {
+    InvalidId,
+    Register(u32),
+    Unregister(TimerId),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidId => write!(f, "invalid timer id"),
+            Error::Register(period_ms) => write!(f, "cannot register timer: period: {period_ms}ms"),
+            Error::Unregister(id) => write!(f, "cannot unregister timer: {}", id.0),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::ext::Error::TimerSupport(value).into()
+    }
+}
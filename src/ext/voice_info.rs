@@ -0,0 +1,159 @@
+//! Voice info.
+//!
+//! This extension indicates the number of voices the synthesizer has.
+//! It is useful for the host when performing polyphonic modulations,
+//! because the host needs its own voice management and should try to follow
+//! what the plugin is doing:
+//!
+//! - make the host's voice pool coherent with what the plugin has
+//! - turn the host's voice management to mono when the plugin is mono
+
+use crate::{
+    ffi::{CLAP_VOICE_INFO_SUPPORTS_OVERLAPPING_NOTES, clap_host_voice_info, clap_voice_info},
+    host::Host,
+    impl_flags_u32,
+    plugin::Plugin,
+};
+
+/// # Example
+///
+/// ```rust
+/// # use clap_clap::ext::voice_info::VoiceInfoFlags;
+/// assert_eq!(VoiceInfoFlags::SupportsOverlappingNotes as u32, 0b1);
+/// assert!(VoiceInfoFlags::SupportsOverlappingNotes.is_set(0b1));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum VoiceInfoFlags {
+    /// Allows overlapping voices with the same key.
+    SupportsOverlappingNotes = CLAP_VOICE_INFO_SUPPORTS_OVERLAPPING_NOTES,
+}
+
+impl_flags_u32!(VoiceInfoFlags);
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct VoiceInfoData {
+    /// Current number of voices. Patch may change it.
+    pub voice_count: u32,
+    /// Number of allocated voices.
+    pub voice_capacity: u32,
+    /// A bit mask of [`VoiceInfoFlags`].
+    pub flags: u32,
+}
+
+impl From<VoiceInfoData> for clap_voice_info {
+    fn from(value: VoiceInfoData) -> Self {
+        Self {
+            voice_count: value.voice_count,
+            voice_capacity: value.voice_capacity,
+            flags: value.flags.into(),
+        }
+    }
+}
+
+impl From<clap_voice_info> for VoiceInfoData {
+    fn from(value: clap_voice_info) -> Self {
+        Self {
+            voice_count: value.voice_count,
+            voice_capacity: value.voice_capacity,
+            flags: value.flags as u32,
+        }
+    }
+}
+
+pub trait VoiceInfo<P: Plugin> {
+    /// Gets the voice info, returns `None` on error.
+    fn get(plugin: &P) -> Option<VoiceInfoData>;
+}
+
+impl<P: Plugin> VoiceInfo<P> for () {
+    fn get(_: &P) -> Option<VoiceInfoData> {
+        None
+    }
+}
+
+pub(crate) use ffi::PluginVoiceInfo;
+
+mod ffi {
+    use std::marker::PhantomData;
+
+    use crate::{
+        ext::voice_info::VoiceInfo,
+        ffi::{clap_plugin, clap_plugin_voice_info, clap_voice_info},
+        plugin::{ClapPlugin, Plugin},
+    };
+
+    extern "C-unwind" fn get<E, P>(plugin: *const clap_plugin, info: *mut clap_voice_info) -> bool
+    where
+        E: VoiceInfo<P>,
+        P: Plugin,
+    {
+        if plugin.is_null() || info.is_null() {
+            return false;
+        }
+        // SAFETY: We just checked that the pointer is non-null and the plugin
+        // has been obtained from host and is tied to type P.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(plugin) };
+
+        // SAFETY: This function is called on the main thread.
+        // It is guaranteed that we are the only function accessing the plugin now.
+        // So the mutable reference to plugin for the duration of this call is
+        // safe.
+        let plugin = unsafe { clap_plugin.plugin() };
+
+        let Some(voice_info) = E::get(plugin) else {
+            return false;
+        };
+
+        // SAFETY: We just checked that the pointer is non-null.
+        unsafe { *info = voice_info.into() };
+        true
+    }
+
+    pub(crate) struct PluginVoiceInfo<P> {
+        #[allow(unused)]
+        clap_plugin_voice_info: clap_plugin_voice_info,
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: Plugin> PluginVoiceInfo<P> {
+        pub(crate) fn new<E: VoiceInfo<P>>(_: E) -> Self {
+            Self {
+                clap_plugin_voice_info: clap_plugin_voice_info {
+                    get: Some(get::<E, P>),
+                },
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HostVoiceInfo<'a> {
+    host: &'a Host,
+    clap_host_voice_info: &'a clap_host_voice_info,
+}
+
+impl<'a> HostVoiceInfo<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some), and
+    /// the functions must be thread-safe.
+    pub(crate) const unsafe fn new_unchecked(
+        host: &'a Host,
+        clap_host_voice_info: &'a clap_host_voice_info,
+    ) -> Self {
+        Self {
+            host,
+            clap_host_voice_info,
+        }
+    }
+
+    /// Informs the host that the voice info has changed.
+    pub fn changed(&self) {
+        // SAFETY: By construction, the callback must be a valid function pointer,
+        // and the call is thread-safe.
+        let callback = self.clap_host_voice_info.changed.unwrap();
+        unsafe { callback(self.host.clap_host()) }
+    }
+}
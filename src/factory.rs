@@ -7,6 +7,7 @@ use std::{
 };
 
 use crate::{
+    ext::{Extensions, thread_pool},
     ffi::{clap_host, clap_plugin, clap_plugin_descriptor},
     host::Host,
     plugin,
@@ -75,7 +76,8 @@ impl<P: Plugin> FactoryPlugin for FactoryPluginPrototype<P> {
         // SAFETY: The pointer unwrapped from FactoryHost is a valid pointer
         // to a CLAP host, obtained as the argument passed to plugin
         // factory's create_plugin().
-        let host = unsafe { Host::new_unchecked(host.0) };
+        let mut host = unsafe { Host::new_unchecked(host.0) };
+        host.thread_pool_shared = thread_pool::shared_type_id(&<P as Extensions<P>>::thread_pool());
         Ok(Runtime::<P>::initialize(Arc::new(host))
             .map_err(Error::PluginDescriptor)?
             .into_clap_plugin()
@@ -1580,18 +1580,18 @@ pub struct clap_hosthread_check {
     pub is_audio_thread: Option<unsafe extern "C-unwind" fn(host: *const clap_host) -> bool>,
 }
 
-#[doc = " @page\n\n This extension lets the plugin use the host's thread pool.\n\n The plugin must provide @ref clap_pluginhread_pool, and the host may provide @ref\n clap_hosthread_pool. If it doesn't, the plugin should process its data by its own means. In\n the worst case, a single threaded for-loop.\n\n Simple example with N voices to process\n\n @code\n void myplug_thread_pool_exec(const clap_plugin *plugin, uint32_t voice_index)\n {\n    compute_voice(plugin, voice_index);\n }\n\n void myplug_process(const clap_plugin *plugin, const clap_process *process)\n {\n    ...\n    bool didComputeVoices = false;\n    if (host_thread_pool && host_thread_pool.exec)\n       didComputeVoices = host_thread_pool.request_exec(host, plugin, N);\n\n    if (!didComputeVoices)\n       for (uint32_t i = 0; i < N; ++i)\n          myplug_thread_pool_exec(plugin, i);\n    ...\n }\n @endcode\n\n Be aware that using a thread pool may break hard real-time rules due to the thread\n synchronization involved.\n\n If the host knows that it is running under hard real-time pressure it may decide to not\n provide this interface."]
+#[doc = " @page\n\n This extension lets the plugin use the host's thread pool.\n\n The plugin must provide @ref clap_plugin_thread_pool, and the host may provide @ref\n clap_host_thread_pool. If it doesn't, the plugin should process its data by its own means. In\n the worst case, a single threaded for-loop.\n\n Simple example with N voices to process\n\n @code\n void myplug_thread_pool_exec(const clap_plugin *plugin, uint32_t voice_index)\n {\n    compute_voice(plugin, voice_index);\n }\n\n void myplug_process(const clap_plugin *plugin, const clap_process *process)\n {\n    ...\n    bool didComputeVoices = false;\n    if (host_thread_pool && host_thread_pool.exec)\n       didComputeVoices = host_thread_pool.request_exec(host, plugin, N);\n\n    if (!didComputeVoices)\n       for (uint32_t i = 0; i < N; ++i)\n          myplug_thread_pool_exec(plugin, i);\n    ...\n }\n @endcode\n\n Be aware that using a thread pool may break hard real-time rules due to the thread\n synchronization involved.\n\n If the host knows that it is running under hard real-time pressure it may decide to not\n provide this interface."]
 pub const CLAP_EXT_THREAD_POOL: &CStr = c"clap.thread-pool";
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
-pub struct clap_pluginhread_pool {
+pub struct clap_plugin_thread_pool {
     pub exec: Option<unsafe extern "C-unwind" fn(plugin: *const clap_plugin, task_index: u32)>,
 }
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
-pub struct clap_hosthread_pool {
+pub struct clap_host_thread_pool {
     pub request_exec:
         Option<unsafe extern "C-unwind" fn(host: *const clap_host, num_tasks: u32) -> bool>,
 }
@@ -1600,14 +1600,14 @@ pub const CLAP_EXT_TIMER_SUPPORT: &CStr = c"clap.timer-support";
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
-pub struct clap_pluginimer_support {
+pub struct clap_plugin_timer_support {
     pub on_timer:
         Option<unsafe extern "C-unwind" fn(plugin: *const clap_plugin, timer_id: clap_id)>,
 }
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
-pub struct clap_hostimer_support {
+pub struct clap_host_timer_support {
     pub register_timer: Option<
         unsafe extern "C-unwind" fn(
             host: *const clap_host,
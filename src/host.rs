@@ -1,24 +1,55 @@
 use std::{
+    any::TypeId,
     ffi::{CStr, c_void},
     fmt::{Display, Formatter},
+    ptr::null_mut,
+    sync::atomic::AtomicPtr,
 };
 
 use crate::{
     ext::{
-        audio_ports::HostAudioPorts, latency::HostLatency, log::HostLog, note_ports::HostNotePorts,
-        params::HostParams, state::HostState, tail::HostTail,
+        audio_ports::HostAudioPorts,
+        event_registry::HostEventRegistry,
+        gui::HostGui,
+        latency::HostLatency,
+        log::HostLog,
+        note_name::HostNoteName,
+        note_ports::HostNotePorts,
+        params::HostParams,
+        posix_fd_support::HostPosixFdSupport,
+        remote_controls::HostRemoteControls,
+        state::HostState,
+        tail::HostTail,
+        thread_pool::{HostThreadPool, Task},
+        timer_support::HostTimerSupport,
+        voice_info::HostVoiceInfo,
     },
     ffi::{
-        CLAP_EXT_AUDIO_PORTS, CLAP_EXT_LATENCY, CLAP_EXT_LOG, CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAMS,
-        CLAP_EXT_STATE, CLAP_EXT_TAIL, clap_host, clap_host_audio_ports, clap_host_latency,
-        clap_host_log, clap_host_note_ports, clap_host_params, clap_host_state, clap_host_tail,
+        CLAP_EXT_AUDIO_PORTS, CLAP_EXT_EVENT_REGISTRY, CLAP_EXT_GUI, CLAP_EXT_LATENCY,
+        CLAP_EXT_LOG, CLAP_EXT_NOTE_NAME, CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAMS,
+        CLAP_EXT_POSIX_FD_SUPPORT, CLAP_EXT_REMOTE_CONTROLS, CLAP_EXT_STATE, CLAP_EXT_TAIL,
+        CLAP_EXT_THREAD_POOL, CLAP_EXT_TIMER_SUPPORT, CLAP_EXT_VOICE_INFO, clap_host,
+        clap_host_audio_ports, clap_host_event_registry, clap_host_gui, clap_host_latency,
+        clap_host_log, clap_host_note_name, clap_host_note_ports, clap_host_params,
+        clap_host_posix_fd_support, clap_host_remote_controls, clap_host_state, clap_host_tail,
+        clap_host_thread_pool, clap_host_timer_support, clap_host_voice_info,
     },
     version::ClapVersion,
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct Host {
     clap_host: *const clap_host,
+    /// The tasks of the pending [`HostThreadPool::request_exec()`].
+    pub(crate) thread_pool_task: AtomicPtr<Task>,
+    /// The type of the data shared by the tasks of the plugin's thread pool.
+    pub(crate) thread_pool_shared: TypeId,
+}
+
+impl PartialEq for Host {
+    fn eq(&self, other: &Self) -> bool {
+        self.clap_host == other.clap_host
+    }
 }
 
 impl Host {
@@ -34,7 +65,7 @@ impl Host {
     /// The function will panic if host description strings aren't properly
     /// validated UTF-8 strings.
     #[doc(hidden)]
-    pub const unsafe fn new_unchecked(clap_host: *const clap_host) -> Self {
+    pub unsafe fn new_unchecked(clap_host: *const clap_host) -> Self {
         #[cfg(debug_assertions)]
         {
             assert!(!clap_host.is_null());
@@ -45,7 +76,11 @@ impl Host {
             assert!(clap_host.request_restart.is_some());
         }
 
-        Self { clap_host }
+        Self {
+            clap_host,
+            thread_pool_task: AtomicPtr::new(null_mut()),
+            thread_pool_shared: TypeId::of::<()>(),
+        }
     }
 
     pub const fn clap_host(&self) -> &clap_host {
@@ -58,7 +93,7 @@ impl Host {
         self.clap_host().clap_version
     }
 
-    pub const fn get_extension(&self) -> HostExtensions {
+    pub const fn get_extension(&self) -> HostExtensions<'_> {
         // SAFETY: By construction, the function pointer to `get_extension()` is valid.
         unsafe { HostExtensions::new_unchecked(self) }
     }
@@ -150,6 +185,53 @@ impl<'a> HostExtensions<'a> {
         Ok(unsafe { HostAudioPorts::new_unchecked(self.host, clap_host_audio_ports) })
     }
 
+    pub fn event_registry(&self) -> Result<HostEventRegistry<'a>, Error> {
+        let clap_host_event_registry = self
+            .get_extension_ptr(CLAP_EXT_EVENT_REGISTRY)
+            .ok_or(Error::ExtensionNotFound("event_registry"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_event_registry is
+        // non-null. We return a reference to it for the lifetime of Host.
+        let clap_host_event_registry: &clap_host_event_registry =
+            unsafe { &*clap_host_event_registry.cast() };
+
+        let _ = clap_host_event_registry
+            .query
+            .ok_or(Error::Callback("query"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_event_registry, and
+        // all its methods are non-null.
+        Ok(unsafe { HostEventRegistry::new_unchecked(self.host, clap_host_event_registry) })
+    }
+
+    pub fn gui(&self) -> Result<HostGui<'a>, Error> {
+        let clap_host_gui = self
+            .get_extension_ptr(CLAP_EXT_GUI)
+            .ok_or(Error::ExtensionNotFound("gui"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_gui is non-null. We
+        // return a reference to it for the lifetime of Host.
+        let clap_host_gui: &clap_host_gui = unsafe { &*clap_host_gui.cast() };
+
+        let _ = clap_host_gui
+            .resize_hints_changed
+            .ok_or(Error::Callback("resize_hints_changed"))?;
+        let _ = clap_host_gui
+            .request_resize
+            .ok_or(Error::Callback("request_resize"))?;
+        let _ = clap_host_gui
+            .request_show
+            .ok_or(Error::Callback("request_show"))?;
+        let _ = clap_host_gui
+            .request_hide
+            .ok_or(Error::Callback("request_hide"))?;
+        let _ = clap_host_gui.closed.ok_or(Error::Callback("closed"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_gui, and all its
+        // methods are non-null.
+        Ok(unsafe { HostGui::new_unchecked(self.host, clap_host_gui) })
+    }
+
     pub fn latency(&self) -> Result<HostLatency<'a>, Error> {
         let clap_host_latency = self
             .get_extension_ptr(CLAP_EXT_LATENCY)
@@ -184,6 +266,24 @@ impl<'a> HostExtensions<'a> {
         Ok(unsafe { HostLog::new_unchecked(self.host, clap_host_log) })
     }
 
+    pub fn note_name(&self) -> Result<HostNoteName<'a>, Error> {
+        let clap_host_note_name = self
+            .get_extension_ptr(CLAP_EXT_NOTE_NAME)
+            .ok_or(Error::ExtensionNotFound("note_name"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_note_name is non-null.
+        // We return a reference to it for the lifetime of Host.
+        let clap_host_note_name: &clap_host_note_name = unsafe { &*clap_host_note_name.cast() };
+
+        let _ = clap_host_note_name
+            .changed
+            .ok_or(Error::Callback("changed"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_note_name, and all its
+        // methods are non-null.
+        Ok(unsafe { HostNoteName::new_unchecked(self.host, clap_host_note_name) })
+    }
+
     pub fn note_ports(&self) -> Result<HostNotePorts<'a>, Error> {
         let clap_host_note_ports = self
             .get_extension_ptr(CLAP_EXT_NOTE_PORTS)
@@ -224,6 +324,53 @@ impl<'a> HostExtensions<'a> {
         Ok(unsafe { HostParams::new_unchecked(self.host, clap_host_params) })
     }
 
+    pub fn posix_fd_support(&self) -> Result<HostPosixFdSupport<'a>, Error> {
+        let clap_host_posix_fd_support = self
+            .get_extension_ptr(CLAP_EXT_POSIX_FD_SUPPORT)
+            .ok_or(Error::ExtensionNotFound("posix_fd_support"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_posix_fd_support is
+        // non-null. We return a reference to it for the lifetime of Host.
+        let clap_host_posix_fd_support: &clap_host_posix_fd_support =
+            unsafe { &*clap_host_posix_fd_support.cast() };
+
+        let _ = clap_host_posix_fd_support
+            .register_fd
+            .ok_or(Error::Callback("register_fd"))?;
+        let _ = clap_host_posix_fd_support
+            .modify_fd
+            .ok_or(Error::Callback("modify_fd"))?;
+        let _ = clap_host_posix_fd_support
+            .unregister_fd
+            .ok_or(Error::Callback("unregister_fd"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_posix_fd_support, and
+        // all its methods are non-null.
+        Ok(unsafe { HostPosixFdSupport::new_unchecked(self.host, clap_host_posix_fd_support) })
+    }
+
+    pub fn remote_controls(&self) -> Result<HostRemoteControls<'a>, Error> {
+        let clap_host_remote_controls = self
+            .get_extension_ptr(CLAP_EXT_REMOTE_CONTROLS)
+            .ok_or(Error::ExtensionNotFound("remote_controls"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_remote_controls is
+        // non-null. We return a reference to it for the lifetime of Host.
+        let clap_host_remote_controls: &clap_host_remote_controls =
+            unsafe { &*clap_host_remote_controls.cast() };
+
+        let _ = clap_host_remote_controls
+            .changed
+            .ok_or(Error::Callback("changed"))?;
+        let _ = clap_host_remote_controls
+            .suggest_page
+            .ok_or(Error::Callback("suggest_page"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_remote_controls, and
+        // all its methods are non-null.
+        Ok(unsafe { HostRemoteControls::new_unchecked(self.host, clap_host_remote_controls) })
+    }
+
     pub fn state(&self) -> Result<HostState<'a>, Error> {
         let clap_host_state = self
             .get_extension_ptr(CLAP_EXT_STATE)
@@ -257,6 +404,65 @@ impl<'a> HostExtensions<'a> {
         // methods are non-null.
         Ok(unsafe { HostTail::new_unchecked(self.host, clap_host_tail) })
     }
+
+    pub fn thread_pool(&self) -> Result<HostThreadPool<'a>, Error> {
+        let clap_host_thread_pool = self
+            .get_extension_ptr(CLAP_EXT_THREAD_POOL)
+            .ok_or(Error::ExtensionNotFound("thread_pool"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_thread_pool is
+        // non-null. We return a reference to it for the lifetime of Host.
+        let clap_host_thread_pool: &clap_host_thread_pool =
+            unsafe { &*clap_host_thread_pool.cast() };
+
+        let _ = clap_host_thread_pool
+            .request_exec
+            .ok_or(Error::Callback("request_exec"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_thread_pool, and all
+        // its methods are non-null.
+        Ok(unsafe { HostThreadPool::new_unchecked(self.host, clap_host_thread_pool) })
+    }
+
+    pub fn timer_support(&self) -> Result<HostTimerSupport<'a>, Error> {
+        let clap_host_timer_support = self
+            .get_extension_ptr(CLAP_EXT_TIMER_SUPPORT)
+            .ok_or(Error::ExtensionNotFound("timer_support"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_timer_support is
+        // non-null. We return a reference to it for the lifetime of Host.
+        let clap_host_timer_support: &clap_host_timer_support =
+            unsafe { &*clap_host_timer_support.cast() };
+
+        let _ = clap_host_timer_support
+            .register_timer
+            .ok_or(Error::Callback("register_timer"))?;
+        let _ = clap_host_timer_support
+            .unregister_timer
+            .ok_or(Error::Callback("unregister_timer"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_timer_support, and
+        // all its methods are non-null.
+        Ok(unsafe { HostTimerSupport::new_unchecked(self.host, clap_host_timer_support) })
+    }
+
+    pub fn voice_info(&self) -> Result<HostVoiceInfo<'a>, Error> {
+        let clap_host_voice_info = self
+            .get_extension_ptr(CLAP_EXT_VOICE_INFO)
+            .ok_or(Error::ExtensionNotFound("voice_info"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_voice_info is
+        // non-null. We return a reference to it for the lifetime of Host.
+        let clap_host_voice_info: &clap_host_voice_info = unsafe { &*clap_host_voice_info.cast() };
+
+        let _ = clap_host_voice_info
+            .changed
+            .ok_or(Error::Callback("changed"))?;
+
+        // SAFETY: We just checked if the pointer to clap_host_voice_info, and all
+        // its methods are non-null.
+        Ok(unsafe { HostVoiceInfo::new_unchecked(self.host, clap_host_voice_info) })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,1118 @@
+//! Load CLAP plugins from shared libraries and drive them from Rust.
+//!
+//! [`Bundle`] opens a `.clap` file, initializes its entry point and queries the
+//! plugin factory. Plugins are created with [`Bundle::instantiate()`], which
+//! returns an initialized [`PluginInstance`]. The instance owns its audio
+//! buffers and event queues, and gives typed access to the plugin's
+//! extensions with [`PluginInstance::get_extension()`].
+//!
+//! The host callbacks that the plugin calls are forwarded to a
+//! [`HostHandler`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use clap_clap::hosting::Bundle;
+//! let bundle = Bundle::load("/usr/lib/clap/gain.clap").unwrap();
+//! let descriptor = &bundle.descriptors()[0];
+//!
+//! let mut instance = bundle.instantiate(&descriptor.id, Arc::new(())).unwrap();
+//! let params = instance.get_extension().params().unwrap();
+//! for index in 0..params.count() {
+//!     println!("{}", params.get_info(index).unwrap().name);
+//! }
+//!
+//! instance.set_audio_ports(&[2], &[2]);
+//! instance.activate(48000.0, 1, 256).unwrap();
+//! instance.start_processing().unwrap();
+//! instance.audio_input_mut(0).channel_mut(0).fill(1.0);
+//! instance.process(256).unwrap();
+//! ```
+
+use std::{
+    any::Any,
+    ffi::{CStr, CString, c_char, c_void},
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    ptr::{null, null_mut},
+    sync::Arc,
+};
+
+use crate::{
+    events::EventQueue,
+    ext::{log::Severity, params},
+    ffi::{
+        CLAP_EXT_AUDIO_PORTS, CLAP_EXT_LOG, CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAMS, CLAP_EXT_STATE,
+        CLAP_LOG_DEBUG, CLAP_LOG_ERROR, CLAP_LOG_FATAL, CLAP_LOG_HOST_MISBEHAVING, CLAP_LOG_INFO,
+        CLAP_LOG_PLUGIN_MISBEHAVING, CLAP_LOG_WARNING, CLAP_PLUGIN_FACTORY_ID,
+        CLAP_PROCESS_CONTINUE, CLAP_PROCESS_CONTINUE_IF_NOT_QUIET, CLAP_PROCESS_SLEEP,
+        CLAP_PROCESS_TAIL, CLAP_VERSION, clap_audio_buffer, clap_host, clap_host_log,
+        clap_log_severity, clap_plugin, clap_plugin_audio_ports, clap_plugin_descriptor,
+        clap_plugin_entry, clap_plugin_factory, clap_plugin_note_ports, clap_plugin_params,
+        clap_plugin_state, clap_process, clap_process_status,
+    },
+    process::Status,
+    version::ClapVersion,
+};
+
+mod audio_ports_ext;
+mod library;
+mod note_ports_ext;
+mod params_ext;
+mod state_ext;
+
+pub use audio_ports_ext::PluginAudioPorts;
+use library::Library;
+pub use note_ports_ext::PluginNotePorts;
+pub use params_ext::PluginParams;
+pub use state_ext::PluginState;
+
+/// A plugin description, as advertised by the plugin factory.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Descriptor {
+    pub clap_version: (u32, u32, u32),
+    pub id: String,
+    pub name: String,
+    pub vendor: String,
+    pub url: String,
+    pub manual_url: String,
+    pub support_url: String,
+    pub version: String,
+    pub description: String,
+    pub features: Vec<String>,
+    /// The fields that are not valid UTF-8 strings, e.g. `"name"`.
+    pub invalid_utf8: Vec<&'static str>,
+}
+
+impl Descriptor {
+    /// Missing strings are read as empty, invalid UTF-8 is replaced with
+    /// `U+FFFD` and the field is listed in [`Descriptor::invalid_utf8`].
+    ///
+    /// # Safety
+    ///
+    /// All string fields must be null or valid, null-terminated C strings, and
+    /// `features` must be null or a null-terminated array of such strings.
+    pub unsafe fn from_unchecked(value: &clap_plugin_descriptor) -> Self {
+        let mut invalid_utf8 = Vec::new();
+        let mut string = |field, s: *const c_char| {
+            // SAFETY: The safety condition is upheld by the caller.
+            let (s, valid) = unsafe { string_from_ptr(s) };
+            if !valid && !invalid_utf8.contains(&field) {
+                invalid_utf8.push(field);
+            }
+            s
+        };
+
+        let id = string("id", value.id);
+        let name = string("name", value.name);
+        let vendor = string("vendor", value.vendor);
+        let url = string("url", value.url);
+        let manual_url = string("manual_url", value.manual_url);
+        let support_url = string("support_url", value.support_url);
+        let version = string("version", value.version);
+        let description = string("description", value.description);
+
+        let mut features = Vec::new();
+        if !value.features.is_null() {
+            // SAFETY: The safety condition is upheld by the caller.
+            let mut feature = value.features;
+            while !unsafe { *feature }.is_null() {
+                features.push(string("features", unsafe { *feature }));
+                feature = unsafe { feature.add(1) };
+            }
+        }
+
+        let ClapVersion {
+            major,
+            minor,
+            revision,
+        } = value.clap_version;
+        Self {
+            clap_version: (major, minor, revision),
+            id,
+            name,
+            vendor,
+            url,
+            manual_url,
+            support_url,
+            version,
+            description,
+            features,
+            invalid_utf8,
+        }
+    }
+}
+
+/// # Safety
+///
+/// Return the string, and whether it was valid UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be null or a valid, null-terminated C string.
+unsafe fn string_from_ptr(ptr: *const c_char) -> (String, bool) {
+    if ptr.is_null() {
+        return (String::new(), true);
+    }
+    let s = unsafe { CStr::from_ptr(ptr) };
+    match s.to_str() {
+        Ok(s) => (s.to_owned(), true),
+        Err(_) => (s.to_string_lossy().into_owned(), false),
+    }
+}
+
+struct BundleData {
+    path: PathBuf,
+    entry: *const clap_plugin_entry,
+    factory: *const clap_plugin_factory,
+    // This field must be last: the library is closed after the entry is
+    // deinitialized.
+    _library: Option<Library>,
+}
+
+// SAFETY: CLAP requires the entry and the plugin factory to be thread-safe.
+unsafe impl Send for BundleData {}
+// SAFETY: CLAP requires the entry and the plugin factory to be thread-safe.
+unsafe impl Sync for BundleData {}
+
+impl Drop for BundleData {
+    fn drop(&mut self) {
+        // SAFETY: The entry was initialized, and all plugins created by the
+        // factory hold a reference to the bundle, so they are destroyed by now.
+        unsafe { (*self.entry).deinit.unwrap()() };
+    }
+}
+
+/// A CLAP bundle: a shared library exporting the symbol `clap_entry`.
+///
+/// Cloning the bundle yields a handle to the same library. The library is
+/// deinitialized and closed when the last handle and the last plugin instance
+/// created from it are dropped.
+///
+/// CLAP requires the entry to be initialized only once. Don't load the same
+/// file more than once at the same time.
+#[derive(Clone)]
+pub struct Bundle(Arc<BundleData>);
+
+impl Bundle {
+    /// Open a `.clap` file and initialize its entry.
+    ///
+    /// On macOS, `path` can point to the bundle directory.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let library = Library::open(path)?;
+        let entry = library.symbol(c"clap_entry").ok_or(Error::EntryNotFound)?;
+        // SAFETY: The symbol `clap_entry` of a CLAP bundle is a static
+        // clap_plugin_entry, which lives as long as the library is open.
+        unsafe { Self::init(entry.as_ptr().cast(), path, Some(library)) }
+    }
+
+    /// Initialize a plugin entry that is already in memory, e.g. the entry
+    /// exported by [`entry!`] in the current crate.
+    ///
+    /// # Safety
+    ///
+    /// The entry must be a valid CLAP plugin entry, and it must not have been
+    /// initialized already.
+    ///
+    /// [`entry!`]: crate::entry
+    pub unsafe fn from_entry(
+        entry: &'static clap_plugin_entry,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        // SAFETY: The safety condition is upheld by the caller.
+        unsafe { Self::init(entry, path.as_ref(), None) }
+    }
+
+    /// # Safety
+    ///
+    /// `entry` must be non-null and must point to a valid CLAP plugin entry
+    /// that remains valid while the library is open.
+    unsafe fn init(
+        entry: *const clap_plugin_entry,
+        path: &Path,
+        library: Option<Library>,
+    ) -> Result<Self, Error> {
+        // SAFETY: The safety condition is upheld by the caller.
+        let clap_entry = unsafe { &*entry };
+        let version = clap_entry.clap_version;
+        if version.major < 1 {
+            return Err(Error::ClapVersion(
+                version.major,
+                version.minor,
+                version.revision,
+            ));
+        }
+        let (Some(init), Some(_), Some(get_factory)) =
+            (clap_entry.init, clap_entry.deinit, clap_entry.get_factory)
+        else {
+            return Err(Error::Callback("clap_entry"));
+        };
+
+        let plugin_path =
+            CString::new(path.to_string_lossy().into_owned()).map_err(|_| Error::NulString)?;
+        // SAFETY: The entry is valid, and the path is a valid C string.
+        if !unsafe { init(plugin_path.as_ptr()) } {
+            return Err(Error::EntryInit);
+        }
+        // From now on, Drop calls deinit().
+        let mut bundle = BundleData {
+            path: path.to_path_buf(),
+            entry,
+            factory: null(),
+            _library: library,
+        };
+
+        // SAFETY: The entry is initialized.
+        let factory: *const clap_plugin_factory =
+            unsafe { get_factory(CLAP_PLUGIN_FACTORY_ID.as_ptr()) }.cast();
+        // SAFETY: The factory is null or valid while the library is open.
+        let factory_ref = unsafe { factory.as_ref() }.ok_or(Error::FactoryNotFound)?;
+        if factory_ref.get_plugin_count.is_none()
+            || factory_ref.get_plugin_descriptor.is_none()
+            || factory_ref.create_plugin.is_none()
+        {
+            return Err(Error::Callback("clap_plugin_factory"));
+        }
+        bundle.factory = factory;
+
+        Ok(Self(Arc::new(bundle)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0.path
+    }
+
+    /// The CLAP version of the entry.
+    pub fn clap_version(&self) -> ClapVersion {
+        // SAFETY: The entry is valid while the library is open.
+        unsafe { (*self.0.entry).clap_version }
+    }
+
+    fn clap_plugin_factory(&self) -> &clap_plugin_factory {
+        // SAFETY: By construction, the factory is non-null and valid while the
+        // library is open.
+        unsafe { &*self.0.factory }
+    }
+
+    pub fn plugin_count(&self) -> u32 {
+        let factory = self.clap_plugin_factory();
+        // SAFETY: By construction, the method is non-null. The factory is
+        // thread-safe.
+        unsafe { factory.get_plugin_count.unwrap()(factory) }
+    }
+
+    pub fn descriptor(&self, index: u32) -> Option<Descriptor> {
+        let factory = self.clap_plugin_factory();
+        // SAFETY: By construction, the method is non-null. The factory is
+        // thread-safe.
+        let desc = unsafe { factory.get_plugin_descriptor.unwrap()(factory, index) };
+        // SAFETY: The descriptor is null or valid while the library is open.
+        unsafe { desc.as_ref() }.map(|desc| unsafe { Descriptor::from_unchecked(desc) })
+    }
+
+    pub fn descriptors(&self) -> Vec<Descriptor> {
+        (0..self.plugin_count())
+            .filter_map(|index| self.descriptor(index))
+            .collect()
+    }
+
+    /// Create and initialize a plugin.
+    ///
+    /// The calls that the plugin makes to the host are forwarded to `handler`.
+    pub fn instantiate<H: HostHandler>(
+        &self,
+        plugin_id: &str,
+        handler: Arc<H>,
+    ) -> Result<PluginInstance, Error> {
+        let plugin_id = CString::new(plugin_id).map_err(|_| Error::NulString)?;
+        let host = HostData::new(handler)?;
+
+        let factory = self.clap_plugin_factory();
+        // SAFETY: By construction, the method is non-null. The host data is
+        // boxed, so the pointer to clap_host remains valid until the instance
+        // is dropped.
+        let clap_plugin =
+            unsafe { factory.create_plugin.unwrap()(factory, &host.clap_host, plugin_id.as_ptr()) };
+        // SAFETY: The plugin is null or valid until it is destroyed.
+        let plugin = unsafe { clap_plugin.as_ref() }
+            .ok_or_else(|| Error::PluginNotFound(plugin_id.to_string_lossy().into_owned()))?;
+
+        if plugin.destroy.is_none() {
+            return Err(Error::Callback("destroy"));
+        }
+        // SAFETY: The plugin is valid, and its destroy() is non-null.
+        let mut instance =
+            unsafe { PluginInstance::new_unchecked(clap_plugin, host, Some(self.clone())) };
+        // From now on, Drop calls destroy().
+        let callback = [
+            ("init", plugin.init.is_none()),
+            ("activate", plugin.activate.is_none()),
+            ("deactivate", plugin.deactivate.is_none()),
+            ("start_processing", plugin.start_processing.is_none()),
+            ("stop_processing", plugin.stop_processing.is_none()),
+            ("reset", plugin.reset.is_none()),
+            ("process", plugin.process.is_none()),
+            ("get_extension", plugin.get_extension.is_none()),
+            ("on_main_thread", plugin.on_main_thread.is_none()),
+        ]
+        .into_iter()
+        .find_map(|(name, missing)| missing.then_some(name));
+        if let Some(name) = callback {
+            return Err(Error::Callback(name));
+        }
+
+        instance.init()?;
+        Ok(instance)
+    }
+}
+
+/// Receive the calls that plugins make to the host.
+///
+/// All methods have default implementations that do nothing. The methods can
+/// be called from any thread.
+pub trait HostHandler: Send + Sync + 'static {
+    fn name(&self) -> &str {
+        "clap-clap"
+    }
+
+    fn vendor(&self) -> &str {
+        ""
+    }
+
+    fn url(&self) -> &str {
+        ""
+    }
+
+    fn version(&self) -> &str {
+        ""
+    }
+
+    fn request_restart(&self) {}
+
+    fn request_process(&self) {}
+
+    fn request_callback(&self) {}
+
+    fn log(&self, _severity: Severity, _msg: &str) {}
+}
+
+impl HostHandler for () {}
+
+#[repr(C)]
+struct HostData<H> {
+    // This field must be first: callbacks cast the pointer to clap_host into a
+    // pointer to HostData.
+    clap_host: clap_host,
+    clap_host_log: clap_host_log,
+    handler: Arc<H>,
+    name: CString,
+    vendor: CString,
+    url: CString,
+    version: CString,
+}
+
+impl<H: HostHandler> HostData<H> {
+    fn new(handler: Arc<H>) -> Result<Box<Self>, Error> {
+        let string = |s: &str| CString::new(s).map_err(|_| Error::NulString);
+        let mut host = Box::new(Self {
+            clap_host: clap_host {
+                clap_version: CLAP_VERSION,
+                host_data: null_mut(),
+                name: null(),
+                vendor: null(),
+                url: null(),
+                version: null(),
+                get_extension: Some(ffi::get_extension::<H>),
+                request_restart: Some(ffi::request_restart::<H>),
+                request_process: Some(ffi::request_process::<H>),
+                request_callback: Some(ffi::request_callback::<H>),
+            },
+            clap_host_log: clap_host_log {
+                log: Some(ffi::log::<H>),
+            },
+            name: string(handler.name())?,
+            vendor: string(handler.vendor())?,
+            url: string(handler.url())?,
+            version: string(handler.version())?,
+            handler,
+        });
+        host.clap_host.name = host.name.as_ptr();
+        host.clap_host.vendor = host.vendor.as_ptr();
+        host.clap_host.url = host.url.as_ptr();
+        host.clap_host.version = host.version.as_ptr();
+        Ok(host)
+    }
+}
+
+/// An owned audio buffer with 32-bit channels.
+#[derive(Debug, Default)]
+pub struct OwnedAudioBuffer {
+    channels: Vec<Vec<f32>>,
+    pointers: Vec<*mut f32>,
+}
+
+impl OwnedAudioBuffer {
+    pub fn new(channel_count: u32, frames_count: u32) -> Self {
+        let mut buffer = Self {
+            channels: vec![Vec::new(); channel_count as usize],
+            pointers: Vec::new(),
+        };
+        buffer.resize(frames_count);
+        buffer.pointers.reserve_exact(channel_count as usize);
+        buffer
+    }
+
+    pub(crate) fn resize(&mut self, frames_count: u32) {
+        for channel in &mut self.channels {
+            channel.resize(frames_count as usize, 0.0);
+        }
+    }
+
+    pub fn channel_count(&self) -> u32 {
+        self.channels.len() as u32
+    }
+
+    pub fn frames_count(&self) -> u32 {
+        self.channels.first().map_or(0, |c| c.len() as u32)
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `channel` is greater or equal to `self.channel_count()`.
+    pub fn channel(&self, channel: u32) -> &[f32] {
+        &self.channels[channel as usize]
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `channel` is greater or equal to `self.channel_count()`.
+    pub fn channel_mut(&mut self, channel: u32) -> &mut [f32] {
+        &mut self.channels[channel as usize]
+    }
+
+    /// Set all samples to zero.
+    pub fn clear(&mut self) {
+        self.channels.iter_mut().for_each(|c| c.fill(0.0));
+    }
+
+    pub(crate) fn clap_audio_buffer(&mut self) -> clap_audio_buffer {
+        // Take the pointers anew, since references to the channels handed out
+        // in the meantime might have invalidated them.
+        self.pointers.clear();
+        self.pointers
+            .extend(self.channels.iter_mut().map(|c| c.as_mut_ptr()));
+        clap_audio_buffer {
+            data32: self.pointers.as_mut_ptr(),
+            data64: null_mut(),
+            channel_count: self.channel_count(),
+            latency: 0,
+            constant_mask: 0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum InstanceState {
+    Created,
+    Initialized,
+    Active,
+    Processing,
+}
+
+/// A plugin created from a [`Bundle`].
+///
+/// The instance is initialized. It must be activated, and then started
+/// processing, before it can process audio. Calling the methods out of order
+/// returns an error and the plugin is not called.
+///
+/// When dropped, the instance stops processing, deactivates and destroys the
+/// plugin.
+///
+/// The instance drives any plugin through its `clap_plugin` pointer. It is
+/// also the core of [`testing::PluginInstance`].
+///
+/// [`testing::PluginInstance`]: crate::testing::PluginInstance
+pub struct PluginInstance {
+    clap_plugin: *const clap_plugin,
+    state: InstanceState,
+    max_frames_count: u32,
+    steady_time: i64,
+    audio_inputs: Vec<OwnedAudioBuffer>,
+    audio_outputs: Vec<OwnedAudioBuffer>,
+    in_events: EventQueue,
+    out_events: EventQueue,
+    // These fields must be last: the host and the library must outlive the
+    // plugin.
+    _host: Box<dyn Any>,
+    bundle: Option<Bundle>,
+}
+
+impl PluginInstance {
+    /// Take ownership of the plugin, before it is initialized.
+    ///
+    /// # Safety
+    ///
+    /// The pointer `clap_plugin` must be valid until the plugin is destroyed,
+    /// and its method `destroy()` must be non-null. Other methods must be
+    /// non-null before any other method of the instance is called. The `host`
+    /// must keep alive the `clap_host` the plugin was created with.
+    pub(crate) unsafe fn new_unchecked(
+        clap_plugin: *const clap_plugin,
+        host: Box<dyn Any>,
+        bundle: Option<Bundle>,
+    ) -> Self {
+        Self {
+            clap_plugin,
+            state: InstanceState::Created,
+            max_frames_count: 0,
+            steady_time: 0,
+            audio_inputs: Vec::new(),
+            audio_outputs: Vec::new(),
+            in_events: EventQueue::new(),
+            out_events: EventQueue::new(),
+            _host: host,
+            bundle,
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) const fn as_ptr(&self) -> *const clap_plugin {
+        self.clap_plugin
+    }
+
+    pub const fn clap_plugin(&self) -> &clap_plugin {
+        // SAFETY: The plugin remains valid until the instance is dropped.
+        unsafe { &*self.clap_plugin }
+    }
+
+    pub fn descriptor(&self) -> Descriptor {
+        // SAFETY: The descriptor is valid until the plugin is destroyed.
+        unsafe { self.clap_plugin().desc.as_ref() }
+            .map(|desc| unsafe { Descriptor::from_unchecked(desc) })
+            .unwrap_or_default()
+    }
+
+    /// The bundle the plugin was loaded from, or `None` if the plugin was
+    /// created in-process, e.g. by [`TestHost`].
+    ///
+    /// [`TestHost`]: crate::testing::TestHost
+    pub fn bundle(&self) -> Option<&Bundle> {
+        self.bundle.as_ref()
+    }
+
+    pub const fn get_extension(&self) -> PluginExtensions<'_> {
+        PluginExtensions { instance: self }
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.state,
+            InstanceState::Active | InstanceState::Processing
+        )
+    }
+
+    pub fn is_processing(&self) -> bool {
+        self.state == InstanceState::Processing
+    }
+
+    pub(crate) fn init(&mut self) -> Result<(), Error> {
+        if self.state != InstanceState::Created {
+            return Err(Error::AlreadyInitialized);
+        }
+        // SAFETY: The plugin is valid, and we are on the main thread.
+        if unsafe { self.clap_plugin().init.unwrap()(self.clap_plugin) } {
+            self.state = InstanceState::Initialized;
+            Ok(())
+        } else {
+            Err(Error::Init)
+        }
+    }
+
+    /// Activate the plugin.
+    ///
+    /// The audio buffers are resized to hold `max_frames_count` frames.
+    pub fn activate(
+        &mut self,
+        sample_rate: f64,
+        min_frames_count: u32,
+        max_frames_count: u32,
+    ) -> Result<(), Error> {
+        match self.state {
+            InstanceState::Created => return Err(Error::NotInitialized),
+            InstanceState::Active | InstanceState::Processing => return Err(Error::AlreadyActive),
+            InstanceState::Initialized => {}
+        }
+        // SAFETY: The plugin is initialized and not active.
+        let activate = self.clap_plugin().activate.unwrap();
+        if !unsafe {
+            activate(
+                self.clap_plugin,
+                sample_rate,
+                min_frames_count,
+                max_frames_count,
+            )
+        } {
+            return Err(Error::Activate);
+        }
+
+        self.state = InstanceState::Active;
+        self.max_frames_count = max_frames_count;
+        self.steady_time = 0;
+        for buffer in self.audio_inputs.iter_mut().chain(&mut self.audio_outputs) {
+            buffer.resize(max_frames_count);
+        }
+        Ok(())
+    }
+
+    /// Deactivate the plugin, stopping processing first if needed.
+    pub fn deactivate(&mut self) -> Result<(), Error> {
+        match self.state {
+            InstanceState::Created | InstanceState::Initialized => return Err(Error::NotActive),
+            InstanceState::Processing => self.stop_processing()?,
+            InstanceState::Active => {}
+        }
+        // SAFETY: The plugin is active and not processing.
+        unsafe { self.clap_plugin().deactivate.unwrap()(self.clap_plugin) };
+        self.state = InstanceState::Initialized;
+        Ok(())
+    }
+
+    pub fn start_processing(&mut self) -> Result<(), Error> {
+        match self.state {
+            InstanceState::Created | InstanceState::Initialized => return Err(Error::NotActive),
+            InstanceState::Processing => return Err(Error::AlreadyProcessing),
+            InstanceState::Active => {}
+        }
+        // SAFETY: The plugin is active.
+        if unsafe { self.clap_plugin().start_processing.unwrap()(self.clap_plugin) } {
+            self.state = InstanceState::Processing;
+            Ok(())
+        } else {
+            Err(Error::StartProcessing)
+        }
+    }
+
+    pub fn stop_processing(&mut self) -> Result<(), Error> {
+        if self.state != InstanceState::Processing {
+            return Err(Error::NotProcessing);
+        }
+        // SAFETY: The plugin is processing.
+        unsafe { self.clap_plugin().stop_processing.unwrap()(self.clap_plugin) };
+        self.state = InstanceState::Active;
+        Ok(())
+    }
+
+    pub fn reset(&mut self) -> Result<(), Error> {
+        if !self.is_active() {
+            return Err(Error::NotActive);
+        }
+        // SAFETY: The plugin is active.
+        unsafe { self.clap_plugin().reset.unwrap()(self.clap_plugin) };
+        Ok(())
+    }
+
+    /// Call the plugin's `on_main_thread()`, as the answer to
+    /// `request_callback()`.
+    pub fn on_main_thread(&mut self) {
+        // SAFETY: The plugin is valid, and we are on the main thread.
+        unsafe { self.clap_plugin().on_main_thread.unwrap()(self.clap_plugin) };
+    }
+
+    /// Set the number of channels for each audio input and output port.
+    ///
+    /// The previous buffers are discarded. If the plugin is active, the
+    /// buffers hold the maximal number of frames the plugin was activated
+    /// with.
+    pub fn set_audio_ports(&mut self, inputs: &[u32], outputs: &[u32]) {
+        let frames_count = self.max_frames_count;
+        self.audio_inputs = inputs
+            .iter()
+            .map(|&n| OwnedAudioBuffer::new(n, frames_count))
+            .collect();
+        self.audio_outputs = outputs
+            .iter()
+            .map(|&n| OwnedAudioBuffer::new(n, frames_count))
+            .collect();
+    }
+
+    pub fn audio_inputs_count(&self) -> u32 {
+        self.audio_inputs.len() as u32
+    }
+
+    pub fn audio_outputs_count(&self) -> u32 {
+        self.audio_outputs.len() as u32
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_inputs_count()`.
+    pub fn audio_input(&self, port: u32) -> &OwnedAudioBuffer {
+        &self.audio_inputs[port as usize]
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_inputs_count()`.
+    pub fn audio_input_mut(&mut self, port: u32) -> &mut OwnedAudioBuffer {
+        &mut self.audio_inputs[port as usize]
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_outputs_count()`.
+    pub fn audio_output(&self, port: u32) -> &OwnedAudioBuffer {
+        &self.audio_outputs[port as usize]
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_outputs_count()`.
+    pub fn audio_output_mut(&mut self, port: u32) -> &mut OwnedAudioBuffer {
+        &mut self.audio_outputs[port as usize]
+    }
+
+    /// Events to be sent to the plugin with the next call to `process()`.
+    pub fn in_events(&mut self) -> &mut EventQueue {
+        &mut self.in_events
+    }
+
+    /// Events that the plugin sent during the last call to `process()`.
+    pub fn out_events(&self) -> &EventQueue {
+        &self.out_events
+    }
+
+    /// Process `frames_count` frames.
+    ///
+    /// The input events are consumed: the queue is empty after the call. The
+    /// output events from the previous call are discarded. The steady time
+    /// starts at zero when the plugin is activated and advances by
+    /// `frames_count` with each call.
+    ///
+    /// # Panic
+    ///
+    /// Panic if `frames_count` is greater than the maximal number of frames
+    /// the plugin was activated with.
+    pub fn process(&mut self, frames_count: u32) -> Result<Status, Error> {
+        if self.state != InstanceState::Processing {
+            return Err(Error::NotProcessing);
+        }
+        assert!(
+            frames_count <= self.max_frames_count,
+            "frames_count must not be greater than max_frames_count"
+        );
+
+        self.out_events.clear();
+        let audio_inputs: Vec<_> = self
+            .audio_inputs
+            .iter_mut()
+            .map(OwnedAudioBuffer::clap_audio_buffer)
+            .collect();
+        let mut audio_outputs: Vec<_> = self
+            .audio_outputs
+            .iter_mut()
+            .map(OwnedAudioBuffer::clap_audio_buffer)
+            .collect();
+
+        let process = clap_process {
+            steady_time: self.steady_time,
+            frames_count,
+            transport: null(),
+            audio_inputs: audio_inputs.as_ptr(),
+            audio_outputs: audio_outputs.as_mut_ptr(),
+            audio_inputs_count: audio_inputs.len() as u32,
+            audio_outputs_count: audio_outputs.len() as u32,
+            in_events: self.in_events.as_clap_input_events(),
+            out_events: self.out_events.as_clap_output_events(),
+        };
+
+        // SAFETY: The plugin is processing. The audio buffers hold at least
+        // `frames_count` frames, and the event lists remain valid for the
+        // duration of the call.
+        let status = unsafe { self.clap_plugin().process.unwrap()(self.clap_plugin, &process) };
+
+        self.in_events.clear();
+        self.steady_time += i64::from(frames_count);
+        status_from_clap(status).ok_or(Error::Process)
+    }
+}
+
+impl Drop for PluginInstance {
+    fn drop(&mut self) {
+        if self.is_active() {
+            let _ = self.deactivate();
+        }
+        // SAFETY: The plugin is not active, and it is not used after this call.
+        unsafe { self.clap_plugin().destroy.unwrap()(self.clap_plugin) };
+    }
+}
+
+/// Typed access to the extensions of a plugin.
+pub struct PluginExtensions<'a> {
+    instance: &'a PluginInstance,
+}
+
+impl<'a> PluginExtensions<'a> {
+    fn get_extension_ptr(&self, extension_id: &CStr) -> Option<*const c_void> {
+        let clap_plugin = self.instance.clap_plugin();
+        // SAFETY: By construction of PluginInstance, get_extension() is
+        // non-null, and the plugin is initialized.
+        let ext_ptr =
+            unsafe { clap_plugin.get_extension.unwrap()(clap_plugin, extension_id.as_ptr()) };
+        (!ext_ptr.is_null()).then_some(ext_ptr)
+    }
+
+    pub fn audio_ports(&self) -> Result<PluginAudioPorts<'a>, Error> {
+        let clap_plugin_audio_ports = self
+            .get_extension_ptr(CLAP_EXT_AUDIO_PORTS)
+            .ok_or(Error::ExtensionNotFound("audio_ports"))?;
+
+        // SAFETY: We just checked if the pointer to clap_plugin_audio_ports is
+        // non-null. The extension remains valid until the plugin is destroyed.
+        let clap_plugin_audio_ports: &clap_plugin_audio_ports =
+            unsafe { &*clap_plugin_audio_ports.cast() };
+
+        let _ = clap_plugin_audio_ports
+            .count
+            .ok_or(Error::Callback("count"))?;
+        let _ = clap_plugin_audio_ports.get.ok_or(Error::Callback("get"))?;
+
+        // SAFETY: We just checked if the methods are non-null (Some).
+        Ok(unsafe { PluginAudioPorts::new_unchecked(self.instance, clap_plugin_audio_ports) })
+    }
+
+    pub fn note_ports(&self) -> Result<PluginNotePorts<'a>, Error> {
+        let clap_plugin_note_ports = self
+            .get_extension_ptr(CLAP_EXT_NOTE_PORTS)
+            .ok_or(Error::ExtensionNotFound("note_ports"))?;
+
+        // SAFETY: We just checked if the pointer to clap_plugin_note_ports is
+        // non-null. The extension remains valid until the plugin is destroyed.
+        let clap_plugin_note_ports: &clap_plugin_note_ports =
+            unsafe { &*clap_plugin_note_ports.cast() };
+
+        let _ = clap_plugin_note_ports
+            .count
+            .ok_or(Error::Callback("count"))?;
+        let _ = clap_plugin_note_ports.get.ok_or(Error::Callback("get"))?;
+
+        // SAFETY: We just checked if the methods are non-null (Some).
+        Ok(unsafe { PluginNotePorts::new_unchecked(self.instance, clap_plugin_note_ports) })
+    }
+
+    pub fn params(&self) -> Result<PluginParams<'a>, Error> {
+        let clap_plugin_params = self
+            .get_extension_ptr(CLAP_EXT_PARAMS)
+            .ok_or(Error::ExtensionNotFound("params"))?;
+
+        // SAFETY: We just checked if the pointer to clap_plugin_params is
+        // non-null. The extension remains valid until the plugin is destroyed.
+        let clap_plugin_params: &clap_plugin_params = unsafe { &*clap_plugin_params.cast() };
+
+        let _ = clap_plugin_params.count.ok_or(Error::Callback("count"))?;
+        let _ = clap_plugin_params
+            .get_info
+            .ok_or(Error::Callback("get_info"))?;
+        let _ = clap_plugin_params
+            .get_value
+            .ok_or(Error::Callback("get_value"))?;
+        let _ = clap_plugin_params
+            .value_to_text
+            .ok_or(Error::Callback("value_to_text"))?;
+        let _ = clap_plugin_params
+            .text_to_value
+            .ok_or(Error::Callback("text_to_value"))?;
+        let _ = clap_plugin_params.flush.ok_or(Error::Callback("flush"))?;
+
+        // SAFETY: We just checked if the methods are non-null (Some).
+        Ok(unsafe { PluginParams::new_unchecked(self.instance, clap_plugin_params) })
+    }
+
+    pub fn state(&self) -> Result<PluginState<'a>, Error> {
+        let clap_plugin_state = self
+            .get_extension_ptr(CLAP_EXT_STATE)
+            .ok_or(Error::ExtensionNotFound("state"))?;
+
+        // SAFETY: We just checked if the pointer to clap_plugin_state is
+        // non-null. The extension remains valid until the plugin is destroyed.
+        let clap_plugin_state: &clap_plugin_state = unsafe { &*clap_plugin_state.cast() };
+
+        let _ = clap_plugin_state.save.ok_or(Error::Callback("save"))?;
+        let _ = clap_plugin_state.load.ok_or(Error::Callback("load"))?;
+
+        // SAFETY: We just checked if the methods are non-null (Some).
+        Ok(unsafe { PluginState::new_unchecked(self.instance, clap_plugin_state) })
+    }
+}
+
+pub(crate) fn status_from_clap(status: clap_process_status) -> Option<Status> {
+    match status {
+        CLAP_PROCESS_CONTINUE => Some(Status::Continue),
+        CLAP_PROCESS_CONTINUE_IF_NOT_QUIET => Some(Status::ContinueIfNotQuiet),
+        CLAP_PROCESS_TAIL => Some(Status::Tail),
+        CLAP_PROCESS_SLEEP => Some(Status::Sleep),
+        _ => None,
+    }
+}
+
+pub(crate) fn severity_from_clap(severity: clap_log_severity) -> Option<Severity> {
+    match severity {
+        CLAP_LOG_DEBUG => Some(Severity::Debug),
+        CLAP_LOG_INFO => Some(Severity::Info),
+        CLAP_LOG_WARNING => Some(Severity::Warning),
+        CLAP_LOG_ERROR => Some(Severity::Error),
+        CLAP_LOG_FATAL => Some(Severity::Fatal),
+        CLAP_LOG_HOST_MISBEHAVING => Some(Severity::HostMisbehaving),
+        CLAP_LOG_PLUGIN_MISBEHAVING => Some(Severity::PluginMisbehaving),
+        _ => None,
+    }
+}
+
+mod ffi {
+    use super::*;
+
+    /// # Safety
+    ///
+    /// `host` must be non-null and must point to the `clap_host` field of
+    /// `HostData<H>`.
+    unsafe fn host_data<'a, H>(host: *const clap_host) -> &'a HostData<H> {
+        unsafe { &*host.cast::<HostData<H>>() }
+    }
+
+    pub(super) extern "C-unwind" fn get_extension<H: HostHandler>(
+        host: *const clap_host,
+        extension_id: *const c_char,
+    ) -> *const c_void {
+        if host.is_null() || extension_id.is_null() {
+            return null();
+        }
+        // SAFETY: We just checked that the pointer is non-null. The plugin
+        // passes a valid C string.
+        let extension_id = unsafe { CStr::from_ptr(extension_id) };
+        if extension_id == CLAP_EXT_LOG {
+            // SAFETY: We just checked that the pointer is non-null. It was
+            // created by Bundle::instantiate().
+            let host_data = unsafe { host_data::<H>(host) };
+            &raw const host_data.clap_host_log as *const _
+        } else {
+            null()
+        }
+    }
+
+    pub(super) extern "C-unwind" fn request_restart<H: HostHandler>(host: *const clap_host) {
+        if host.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by Bundle::instantiate().
+        unsafe { host_data::<H>(host) }.handler.request_restart();
+    }
+
+    pub(super) extern "C-unwind" fn request_process<H: HostHandler>(host: *const clap_host) {
+        if host.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by Bundle::instantiate().
+        unsafe { host_data::<H>(host) }.handler.request_process();
+    }
+
+    pub(super) extern "C-unwind" fn request_callback<H: HostHandler>(host: *const clap_host) {
+        if host.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by Bundle::instantiate().
+        unsafe { host_data::<H>(host) }.handler.request_callback();
+    }
+
+    pub(super) extern "C-unwind" fn log<H: HostHandler>(
+        host: *const clap_host,
+        severity: clap_log_severity,
+        msg: *const c_char,
+    ) {
+        if host.is_null() || msg.is_null() {
+            return;
+        }
+        let Some(severity) = severity_from_clap(severity) else {
+            return;
+        };
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by Bundle::instantiate().
+        let host_data = unsafe { host_data::<H>(host) };
+        // SAFETY: We just checked that the pointer is non-null. The plugin
+        // passes a valid C string.
+        let msg = unsafe { CStr::from_ptr(msg) }.to_string_lossy();
+        host_data.handler.log(severity, &msg);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Activate,
+    AlreadyActive,
+    AlreadyInitialized,
+    AlreadyProcessing,
+    Callback(&'static str),
+    ClapVersion(u32, u32, u32),
+    EntryInit,
+    EntryNotFound,
+    ExtensionNotFound(&'static str),
+    FactoryNotFound,
+    Failed(&'static str),
+    Init,
+    Library(String),
+    NotActive,
+    NotInitialized,
+    NotProcessing,
+    NulString,
+    Params(params::Error),
+    PluginNotFound(String),
+    Process,
+    StartProcessing,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Activate => write!(f, "plugin activation failed"),
+            Error::AlreadyActive => write!(f, "plugin already active"),
+            Error::AlreadyInitialized => write!(f, "plugin already initialized"),
+            Error::AlreadyProcessing => write!(f, "plugin already processing"),
+            Error::Callback(name) => write!(f, "callback not found: {name}"),
+            Error::ClapVersion(major, minor, revision) => {
+                write!(f, "incompatible CLAP version: {major}.{minor}.{revision}")
+            }
+            Error::EntryInit => write!(f, "entry initialization failed"),
+            Error::EntryNotFound => write!(f, "symbol not found: clap_entry"),
+            Error::ExtensionNotFound(name) => write!(f, "extension not found: {name}"),
+            Error::FactoryNotFound => write!(f, "plugin factory not found"),
+            Error::Failed(name) => write!(f, "call failed: {name}"),
+            Error::Init => write!(f, "plugin initialization failed"),
+            Error::Library(msg) => write!(f, "cannot load library: {msg}"),
+            Error::NotActive => write!(f, "plugin not active"),
+            Error::NotInitialized => write!(f, "plugin not initialized"),
+            Error::NotProcessing => write!(f, "plugin not processing"),
+            Error::NulString => write!(f, "string contains a null byte"),
+            Error::Params(e) => write!(f, "params: {e}"),
+            Error::PluginNotFound(id) => write!(f, "plugin not found: {id}"),
+            Error::Process => write!(f, "processing failed"),
+            Error::StartProcessing => write!(f, "starting processing failed"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<params::Error> for Error {
+    fn from(value: params::Error) -> Self {
+        Self::Params(value)
+    }
+}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::Error::Hosting(value)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    ext::audio_ports::AudioPortInfo,
+    ffi::{clap_audio_port_info, clap_plugin_audio_ports},
+    hosting::{Error, PluginInstance},
+};
+
+pub struct PluginAudioPorts<'a> {
+    instance: &'a PluginInstance,
+    clap_plugin_audio_ports: &'a clap_plugin_audio_ports,
+}
+
+impl<'a> PluginAudioPorts<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some).
+    pub(super) const unsafe fn new_unchecked(
+        instance: &'a PluginInstance,
+        clap_plugin_audio_ports: &'a clap_plugin_audio_ports,
+    ) -> Self {
+        Self {
+            instance,
+            clap_plugin_audio_ports,
+        }
+    }
+
+    pub fn count(&self, is_input: bool) -> u32 {
+        let clap_plugin = self.instance.clap_plugin();
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread.
+        unsafe { self.clap_plugin_audio_ports.count.unwrap()(clap_plugin, is_input) }
+    }
+
+    pub fn get(&self, index: u32, is_input: bool) -> Result<AudioPortInfo, Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let mut info = clap_audio_port_info {
+            id: 0,
+            name: [0; 256],
+            flags: 0,
+            channel_count: 0,
+            port_type: std::ptr::null(),
+            in_place_pair: 0,
+        };
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread.
+        if !unsafe {
+            self.clap_plugin_audio_ports.get.unwrap()(clap_plugin, index, is_input, &mut info)
+        } {
+            return Err(Error::Failed("get"));
+        }
+        // SAFETY: The plugin sets port_type to null or a static C string.
+        Ok(unsafe { AudioPortInfo::from_unchecked(&info) })
+    }
+}
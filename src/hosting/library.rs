@@ -0,0 +1,141 @@
+//! Dynamic libraries.
+//!
+//! A thin wrapper around `dlopen()` on Unix, and `LoadLibraryW()` on Windows.
+
+use std::{
+    ffi::{CStr, c_void},
+    path::{Path, PathBuf},
+    ptr::NonNull,
+};
+
+use crate::hosting::Error;
+
+pub(crate) struct Library(NonNull<c_void>);
+
+impl Library {
+    pub(crate) fn open(path: &Path) -> Result<Self, Error> {
+        let path = binary_path(path);
+        // SAFETY: Loading a library runs its initialization routines. The
+        // caller of Bundle::load() trusts the library.
+        unsafe { sys::open(&path) }.map(Self)
+    }
+
+    /// Look up the address of a symbol.
+    pub(crate) fn symbol(&self, name: &CStr) -> Option<NonNull<c_void>> {
+        // SAFETY: The handle is valid until the library is dropped.
+        NonNull::new(unsafe { sys::symbol(self.0, name) })
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        // SAFETY: The handle is valid and not used after this call.
+        unsafe { sys::close(self.0) };
+    }
+}
+
+// SAFETY: Library handles can be used from any thread.
+unsafe impl Send for Library {}
+// SAFETY: Library handles can be used from any thread.
+unsafe impl Sync for Library {}
+
+/// On macOS, a `.clap` bundle is a directory with the binary inside.
+fn binary_path(path: &Path) -> PathBuf {
+    if cfg!(target_os = "macos") && path.is_dir() {
+        if let Some(stem) = path.file_stem() {
+            return path.join("Contents").join("MacOS").join(stem);
+        }
+    }
+    path.to_path_buf()
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::{
+        ffi::{CStr, CString, c_char, c_int, c_void},
+        os::unix::ffi::OsStrExt,
+        path::Path,
+        ptr::NonNull,
+    };
+
+    use crate::hosting::Error;
+
+    const RTLD_NOW: c_int = 2;
+    #[cfg(target_vendor = "apple")]
+    const RTLD_LOCAL: c_int = 4;
+    #[cfg(not(target_vendor = "apple"))]
+    const RTLD_LOCAL: c_int = 0;
+
+    unsafe extern "C" {
+        fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+        fn dlclose(handle: *mut c_void) -> c_int;
+        fn dlerror() -> *mut c_char;
+    }
+
+    pub(super) unsafe fn open(path: &Path) -> Result<NonNull<c_void>, Error> {
+        let filename = CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| Error::Library(format!("invalid path: {}", path.display())))?;
+        // SAFETY: The file name is a valid C string.
+        let handle = unsafe { dlopen(filename.as_ptr(), RTLD_NOW | RTLD_LOCAL) };
+        NonNull::new(handle).ok_or_else(|| {
+            // SAFETY: dlerror() returns null, or a valid C string describing
+            // the last error.
+            let msg = unsafe { dlerror() };
+            Error::Library(if msg.is_null() {
+                format!("cannot open: {}", path.display())
+            } else {
+                unsafe { CStr::from_ptr(msg) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        })
+    }
+
+    pub(super) unsafe fn symbol(handle: NonNull<c_void>, name: &CStr) -> *mut c_void {
+        unsafe { dlsym(handle.as_ptr(), name.as_ptr()) }
+    }
+
+    pub(super) unsafe fn close(handle: NonNull<c_void>) {
+        unsafe { dlclose(handle.as_ptr()) };
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::{
+        ffi::{CStr, c_char, c_int, c_void},
+        os::windows::ffi::OsStrExt,
+        path::Path,
+        ptr::NonNull,
+    };
+
+    use crate::hosting::Error;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn LoadLibraryW(filename: *const u16) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+        fn FreeLibrary(module: *mut c_void) -> c_int;
+        fn GetLastError() -> u32;
+    }
+
+    pub(super) unsafe fn open(path: &Path) -> Result<NonNull<c_void>, Error> {
+        let filename: Vec<u16> = path.as_os_str().encode_wide().chain([0]).collect();
+        // SAFETY: The file name is a valid, null-terminated wide string.
+        let handle = unsafe { LoadLibraryW(filename.as_ptr()) };
+        NonNull::new(handle).ok_or_else(|| {
+            // SAFETY: Always safe to call.
+            let code = unsafe { GetLastError() };
+            Error::Library(format!("cannot open: {} (error {code})", path.display()))
+        })
+    }
+
+    pub(super) unsafe fn symbol(handle: NonNull<c_void>, name: &CStr) -> *mut c_void {
+        unsafe { GetProcAddress(handle.as_ptr(), name.as_ptr()) }
+    }
+
+    pub(super) unsafe fn close(handle: NonNull<c_void>) {
+        unsafe { FreeLibrary(handle.as_ptr()) };
+    }
+}
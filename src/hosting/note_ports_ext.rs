@@ -0,0 +1,50 @@
+use crate::{
+    ext::note_ports::NotePortInfo,
+    ffi::{clap_note_port_info, clap_plugin_note_ports},
+    hosting::{Error, PluginInstance},
+};
+
+pub struct PluginNotePorts<'a> {
+    instance: &'a PluginInstance,
+    clap_plugin_note_ports: &'a clap_plugin_note_ports,
+}
+
+impl<'a> PluginNotePorts<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some).
+    pub(super) const unsafe fn new_unchecked(
+        instance: &'a PluginInstance,
+        clap_plugin_note_ports: &'a clap_plugin_note_ports,
+    ) -> Self {
+        Self {
+            instance,
+            clap_plugin_note_ports,
+        }
+    }
+
+    pub fn count(&self, is_input: bool) -> u32 {
+        let clap_plugin = self.instance.clap_plugin();
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread.
+        unsafe { self.clap_plugin_note_ports.count.unwrap()(clap_plugin, is_input) }
+    }
+
+    pub fn get(&self, index: u32, is_input: bool) -> Result<NotePortInfo, Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let mut info = clap_note_port_info {
+            id: 0,
+            supported_dialects: 0,
+            preferred_dialect: 0,
+            name: [0; 256],
+        };
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread.
+        if !unsafe {
+            self.clap_plugin_note_ports.get.unwrap()(clap_plugin, index, is_input, &mut info)
+        } {
+            return Err(Error::Failed("get"));
+        }
+        Ok(NotePortInfo::from(&info))
+    }
+}
@@ -0,0 +1,136 @@
+use std::ffi::{CStr, CString, c_char};
+
+use crate::{
+    events::EventQueue,
+    ext::params::ParamInfo,
+    ffi::{clap_param_info, clap_plugin_params},
+    hosting::{Error, PluginInstance},
+    id::ClapId,
+};
+
+pub struct PluginParams<'a> {
+    instance: &'a PluginInstance,
+    clap_plugin_params: &'a clap_plugin_params,
+}
+
+impl<'a> PluginParams<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some).
+    pub(super) const unsafe fn new_unchecked(
+        instance: &'a PluginInstance,
+        clap_plugin_params: &'a clap_plugin_params,
+    ) -> Self {
+        Self {
+            instance,
+            clap_plugin_params,
+        }
+    }
+
+    pub fn count(&self) -> u32 {
+        let clap_plugin = self.instance.clap_plugin();
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread.
+        unsafe { self.clap_plugin_params.count.unwrap()(clap_plugin) }
+    }
+
+    pub fn get_info(&self, param_index: u32) -> Result<ParamInfo, Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let mut info = clap_param_info {
+            id: 0,
+            flags: 0,
+            cookie: std::ptr::null_mut(),
+            name: [0; 256],
+            module: [0; 1024],
+            min_value: 0.0,
+            max_value: 0.0,
+            default_value: 0.0,
+        };
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread.
+        if !unsafe {
+            self.clap_plugin_params.get_info.unwrap()(clap_plugin, param_index, &mut info)
+        } {
+            return Err(Error::Failed("get_info"));
+        }
+        // Make sure the strings are null-terminated.
+        info.name[info.name.len() - 1] = 0;
+        info.module[info.module.len() - 1] = 0;
+        // SAFETY: We just terminated the strings.
+        Ok(unsafe { ParamInfo::try_from_unchecked(info) }?)
+    }
+
+    pub fn get_value(&self, param_id: ClapId) -> Result<f64, Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let mut value = 0.0;
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread.
+        if unsafe {
+            self.clap_plugin_params.get_value.unwrap()(clap_plugin, param_id.into(), &mut value)
+        } {
+            Ok(value)
+        } else {
+            Err(Error::Failed("get_value"))
+        }
+    }
+
+    pub fn value_to_text(&self, param_id: ClapId, value: f64) -> Result<String, Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let mut buf: [c_char; 1024] = [0; 1024];
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread. The buffer capacity is
+        // the length of the buffer.
+        if !unsafe {
+            self.clap_plugin_params.value_to_text.unwrap()(
+                clap_plugin,
+                param_id.into(),
+                value,
+                buf.as_mut_ptr(),
+                buf.len() as u32,
+            )
+        } {
+            return Err(Error::Failed("value_to_text"));
+        }
+        // SAFETY: c_char and u8 have the same size and alignment.
+        let buf = unsafe { &*(&raw const buf).cast::<[u8; 1024]>() };
+        Ok(CStr::from_bytes_until_nul(buf)
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default())
+    }
+
+    pub fn text_to_value(&self, param_id: ClapId, text: &str) -> Result<f64, Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let text = CString::new(text).map_err(|_| Error::NulString)?;
+        let mut value = 0.0;
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread. The text is a valid C
+        // string.
+        if unsafe {
+            self.clap_plugin_params.text_to_value.unwrap()(
+                clap_plugin,
+                param_id.into(),
+                text.as_ptr(),
+                &mut value,
+            )
+        } {
+            Ok(value)
+        } else {
+            Err(Error::Failed("text_to_value"))
+        }
+    }
+
+    /// Send parameter events to the plugin outside of `process()`, and collect
+    /// the events that the plugin sends back.
+    pub fn flush(&self, in_events: &EventQueue, out_events: &mut EventQueue) {
+        let clap_plugin = self.instance.clap_plugin();
+        // SAFETY: By construction, the method is non-null. The instance is
+        // borrowed, so the plugin is not processing at the same time.
+        unsafe {
+            self.clap_plugin_params.flush.unwrap()(
+                clap_plugin,
+                in_events.as_clap_input_events(),
+                out_events.as_clap_output_events(),
+            )
+        }
+    }
+}
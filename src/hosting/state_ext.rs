@@ -0,0 +1,105 @@
+use std::ffi::c_void;
+
+use crate::{
+    ffi::{clap_istream, clap_ostream, clap_plugin_state},
+    hosting::{Error, PluginInstance},
+};
+
+pub struct PluginState<'a> {
+    instance: &'a PluginInstance,
+    clap_plugin_state: &'a clap_plugin_state,
+}
+
+impl<'a> PluginState<'a> {
+    /// # Safety
+    ///
+    /// All extension interface function pointers must be non-null (Some).
+    pub(super) const unsafe fn new_unchecked(
+        instance: &'a PluginInstance,
+        clap_plugin_state: &'a clap_plugin_state,
+    ) -> Self {
+        Self {
+            instance,
+            clap_plugin_state,
+        }
+    }
+
+    /// Save the plugin state.
+    pub fn save(&self) -> Result<Vec<u8>, Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let mut buf = Vec::new();
+        let stream = clap_ostream {
+            ctx: (&raw mut buf).cast(),
+            write: Some(ffi::write),
+        };
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread. The stream is valid for
+        // the duration of the call.
+        if unsafe { self.clap_plugin_state.save.unwrap()(clap_plugin, &stream) } {
+            Ok(buf)
+        } else {
+            Err(Error::Failed("save"))
+        }
+    }
+
+    /// Load the plugin state saved with [`save()`](Self::save).
+    pub fn load(&self, state: &[u8]) -> Result<(), Error> {
+        let clap_plugin = self.instance.clap_plugin();
+        let mut rest = state;
+        let stream = clap_istream {
+            ctx: (&raw mut rest).cast(),
+            read: Some(ffi::read),
+        };
+        // SAFETY: By construction, the method is non-null. The plugin is
+        // initialized, and we are on the main thread. The stream is valid for
+        // the duration of the call.
+        if unsafe { self.clap_plugin_state.load.unwrap()(clap_plugin, &stream) } {
+            Ok(())
+        } else {
+            Err(Error::Failed("load"))
+        }
+    }
+}
+
+mod ffi {
+    use super::*;
+
+    pub(super) extern "C-unwind" fn write(
+        stream: *const clap_ostream,
+        buffer: *const c_void,
+        size: u64,
+    ) -> i64 {
+        let Ok(size) = usize::try_from(size) else {
+            return -1;
+        };
+        if stream.is_null() || (buffer.is_null() && size > 0) {
+            return -1;
+        }
+        // SAFETY: The stream was created by PluginState::save(), and its
+        // context points to a Vec<u8>.
+        let buf = unsafe { &mut *(*stream).ctx.cast::<Vec<u8>>() };
+        if size > 0 {
+            // SAFETY: The plugin passes a buffer of at least `size` bytes.
+            buf.extend_from_slice(unsafe { std::slice::from_raw_parts(buffer.cast(), size) });
+        }
+        size as i64
+    }
+
+    pub(super) extern "C-unwind" fn read(
+        stream: *const clap_istream,
+        buffer: *mut c_void,
+        size: u64,
+    ) -> i64 {
+        if stream.is_null() || buffer.is_null() {
+            return -1;
+        }
+        // SAFETY: The stream was created by PluginState::load(), and its
+        // context points to a byte slice.
+        let rest = unsafe { &mut *(*stream).ctx.cast::<&[u8]>() };
+        let n = rest.len().min(usize::try_from(size).unwrap_or(usize::MAX));
+        // SAFETY: The plugin passes a buffer of at least `size` bytes.
+        unsafe { std::ptr::copy_nonoverlapping(rest.as_ptr(), buffer.cast(), n) };
+        *rest = &rest[n..];
+        n as i64
+    }
+}
@@ -1,6 +1,7 @@
 //! A CLAP plugin runtime. ⧉⧉⧉
 
 pub mod audio_buffer;
+pub mod color;
 #[doc(hidden)]
 pub mod entry;
 pub mod events;
@@ -11,31 +12,55 @@ pub mod factory;
 pub mod ffi;
 pub mod fixedpoint;
 pub mod host;
+pub mod hosting;
 pub mod id;
 pub mod plugin;
 pub mod plugin_features;
 pub mod process;
+#[cfg(feature = "testing")]
+pub mod render;
 pub mod stream;
 pub mod string_sizes;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod timestamp;
+pub mod validate;
 pub mod version;
 
 pub mod prelude {
     #[doc(inline)]
     pub use crate::{
-        Error, entry,
-        events::{self, Event, EventBuilder, InputEvents, OutputEvents},
+        Error,
+        color::Color,
+        entry,
+        events::{
+            self, Event, EventBuilder, EventQueue, EventRef, EventSpaceId, InputEvents,
+            OutputEvents,
+        },
         ext::{
             self, Extensions,
             audio_ports::{
                 self, AudioPortFlags, AudioPortInfo, AudioPortType, AudioPorts, MonoPorts,
                 StereoPorts,
             },
+            event_registry::{self, HostEventRegistry},
+            gui::{self, Gui, HostGui, ResizeHints, Window, WindowApi},
             latency::{self, HostLatency, Latency},
             log::{self, Severity},
+            note_name::{self, HostNoteName, NoteName, NoteNameInfo},
             note_ports::{self, NoteDialect, NotePortInfo, NotePorts},
-            params::{self, ParamInfo, Params},
+            param_indication::{self, AutomationState, ParamIndication},
+            params::{
+                self, BoolParam, EnumParam, FloatParam, IntParam, Param, ParamEnum, ParamInfo,
+                ParamSet, ParamStore, Params, ParamsOf, ValueFormat,
+            },
+            posix_fd_support::{self, FdFlags, HostPosixFdSupport, PosixFdSupport},
+            remote_controls::{self, HostRemoteControls, RemoteControls, RemoteControlsPage},
+            render::{self, Render, RenderMode},
             state::{self, State},
+            thread_pool::{self, HostThreadPool, ThreadPool},
+            timer_support::{self, HostTimerSupport, TimerId, TimerSupport},
+            voice_info::{self, HostVoiceInfo, VoiceInfo, VoiceInfoData, VoiceInfoFlags},
         },
         host::{self, Host},
         id::ClapId,
@@ -79,9 +104,14 @@ pub enum Error {
     Extension(ext::Error),
     Factory(factory::Error),
     Host(host::Error),
+    Hosting(hosting::Error),
     Id(id::Error),
     IO(std::io::Error),
+    #[cfg(feature = "testing")]
+    Offline(render::offline::Error),
     Plugin(plugin::Error),
+    #[cfg(feature = "testing")]
+    Testing(testing::Error),
     User(Box<dyn std::error::Error + Send + 'static>),
 }
 
@@ -93,9 +123,14 @@ impl std::fmt::Display for Error {
             Extension(e) => write!(f, "extension:  {e}"),
             Factory(e) => write!(f, "factory: {e}"),
             Host(e) => write!(f, "host: {e}"),
+            Hosting(e) => write!(f, "hosting: {e}"),
             Id(e) => write!(f, "id : {e}"),
             IO(e) => write!(f, "I/O: {e}"),
+            #[cfg(feature = "testing")]
+            Offline(e) => write!(f, "offline render: {e}"),
             Plugin(e) => write!(f, "plugin: {e}"),
+            #[cfg(feature = "testing")]
+            Testing(e) => write!(f, "testing: {e}"),
             User(e) => write!(f, "user: {e}"),
         }
     }
@@ -87,22 +87,42 @@ impl<P: Plugin> AudioThread<P> for () {
 
 struct PluginExtensions<P> {
     audio_ports: Option<PluginAudioPorts<P>>,
+    gui: Option<PluginGui<P>>,
     latency: Option<PluginLatency<P>>,
+    note_name: Option<PluginNoteName<P>>,
     note_ports: Option<PluginNotePorts<P>>,
+    param_indication: Option<PluginParamIndication<P>>,
     params: Option<PluginParams<P>>,
+    posix_fd_support: Option<PluginPosixFdSupport<P>>,
+    remote_controls: Option<PluginRemoteControls<P>>,
+    render: Option<PluginRender<P>>,
     state: Option<PluginState<P>>,
     tail: Option<PluginTail<P>>,
+    thread_pool: Option<PluginThreadPool<P>>,
+    timer_support: Option<PluginTimerSupport<P>>,
+    voice_info: Option<PluginVoiceInfo<P>>,
 }
 
 impl<P: Plugin> PluginExtensions<P> {
     fn new() -> Self {
         Self {
             audio_ports: <P as Extensions<P>>::audio_ports().map(PluginAudioPorts::new),
+            gui: <P as Extensions<P>>::gui().map(PluginGui::new),
             latency: <P as Extensions<P>>::latency().map(PluginLatency::new),
+            note_name: <P as Extensions<P>>::note_name().map(PluginNoteName::new),
             note_ports: <P as Extensions<P>>::note_ports().map(PluginNotePorts::new),
+            param_indication: <P as Extensions<P>>::param_indication()
+                .map(PluginParamIndication::new),
             params: <P as Extensions<P>>::params().map(PluginParams::new),
+            posix_fd_support: <P as Extensions<P>>::posix_fd_support()
+                .map(PluginPosixFdSupport::new),
+            remote_controls: <P as Extensions<P>>::remote_controls().map(PluginRemoteControls::new),
+            render: <P as Extensions<P>>::render().map(PluginRender::new),
             state: <P as Extensions<P>>::state().map(PluginState::new),
             tail: <P as Extensions<P>>::tail().map(PluginTail::new),
+            thread_pool: <P as Extensions<P>>::thread_pool().map(PluginThreadPool::new),
+            timer_support: <P as Extensions<P>>::timer_support().map(PluginTimerSupport::new),
+            voice_info: <P as Extensions<P>>::voice_info().map(PluginVoiceInfo::new),
         }
     }
 }
@@ -232,6 +252,17 @@ impl<P: Plugin> ClapPlugin<P> {
         unsafe { &mut (*runtime).audio_thread }.as_mut()
     }
 
+    /// Obtain a shared reference to the host.
+    ///
+    /// Only the `host` field of the runtime is borrowed, so this is safe to
+    /// call from any thread while the audio thread is borrowed mutably.
+    pub(crate) fn host(&self) -> &Host {
+        let runtime: *const Runtime<P> = unsafe { *self.clap_plugin }.plugin_data as *const _;
+        // SAFETY: The runtime outlives the ClapPlugin, and the host field is
+        // never borrowed mutably after initialization.
+        unsafe { &(*runtime).host }
+    }
+
     /// Obtain a mutex to plugin extensions.
     const fn plugin_extensions(&mut self) -> &Mutex<PluginExtensions<P>> {
         let runtime: *mut Runtime<P> = unsafe { *self.clap_plugin }.plugin_data as *mut _;
@@ -324,8 +355,11 @@ mod desc {
 pub use desc::PluginDescriptor;
 
 use crate::ext::{
-    latency::PluginLatency, note_ports::PluginNotePorts, params::PluginParams, state::PluginState,
-    tail::PluginTail,
+    gui::PluginGui, latency::PluginLatency, note_name::PluginNoteName, note_ports::PluginNotePorts,
+    param_indication::PluginParamIndication, params::PluginParams,
+    posix_fd_support::PluginPosixFdSupport, remote_controls::PluginRemoteControls,
+    render::PluginRender, state::PluginState, tail::PluginTail, thread_pool::PluginThreadPool,
+    timer_support::PluginTimerSupport, voice_info::PluginVoiceInfo,
 };
 
 mod ffi {
@@ -338,9 +372,12 @@ mod ffi {
 
     use crate::{
         ffi::{
-            CLAP_EXT_AUDIO_PORTS, CLAP_EXT_LATENCY, CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAMS,
-            CLAP_EXT_STATE, CLAP_EXT_TAIL, CLAP_PROCESS_ERROR, clap_plugin, clap_process,
-            clap_process_status,
+            CLAP_EXT_AUDIO_PORTS, CLAP_EXT_GUI, CLAP_EXT_LATENCY, CLAP_EXT_NOTE_NAME,
+            CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAM_INDICATION, CLAP_EXT_PARAM_INDICATION_COMPAT,
+            CLAP_EXT_PARAMS, CLAP_EXT_POSIX_FD_SUPPORT, CLAP_EXT_REMOTE_CONTROLS,
+            CLAP_EXT_REMOTE_CONTROLS_COMPAT, CLAP_EXT_RENDER, CLAP_EXT_STATE, CLAP_EXT_TAIL,
+            CLAP_EXT_THREAD_POOL, CLAP_EXT_TIMER_SUPPORT, CLAP_EXT_VOICE_INFO, CLAP_PROCESS_ERROR,
+            clap_plugin, clap_process, clap_process_status,
         },
         plugin::{AudioThread, ClapPlugin, Plugin, Runtime},
         process::Process,
@@ -547,6 +584,14 @@ mod ffi {
             if let Some(ext) = &extensions.audio_ports {
                 return (&raw const *ext).cast();
             }
+        } else if id == CLAP_EXT_GUI {
+            if let Some(ext) = &extensions.gui {
+                return (&raw const *ext).cast();
+            }
+        } else if id == CLAP_EXT_NOTE_NAME {
+            if let Some(ext) = &extensions.note_name {
+                return (&raw const *ext).cast();
+            }
         } else if id == CLAP_EXT_NOTE_PORTS {
             if let Some(ext) = &extensions.note_ports {
                 return (&raw const *ext).cast();
@@ -555,10 +600,26 @@ mod ffi {
             if let Some(ext) = &extensions.latency {
                 return (&raw const *ext).cast();
             }
+        } else if id == CLAP_EXT_PARAM_INDICATION || id == CLAP_EXT_PARAM_INDICATION_COMPAT {
+            if let Some(ext) = &extensions.param_indication {
+                return (&raw const *ext).cast();
+            }
         } else if id == CLAP_EXT_PARAMS {
             if let Some(ext) = &extensions.params {
                 return (&raw const *ext).cast();
             }
+        } else if id == CLAP_EXT_POSIX_FD_SUPPORT {
+            if let Some(ext) = &extensions.posix_fd_support {
+                return (&raw const *ext).cast();
+            }
+        } else if id == CLAP_EXT_REMOTE_CONTROLS || id == CLAP_EXT_REMOTE_CONTROLS_COMPAT {
+            if let Some(ext) = &extensions.remote_controls {
+                return (&raw const *ext).cast();
+            }
+        } else if id == CLAP_EXT_RENDER {
+            if let Some(ext) = &extensions.render {
+                return (&raw const *ext).cast();
+            }
         } else if id == CLAP_EXT_STATE {
             if let Some(ext) = &extensions.state {
                 return (&raw const *ext).cast();
@@ -567,6 +628,18 @@ mod ffi {
             if let Some(ext) = &extensions.tail {
                 return (&raw const *ext).cast();
             }
+        } else if id == CLAP_EXT_THREAD_POOL {
+            if let Some(ext) = &extensions.thread_pool {
+                return (&raw const *ext).cast();
+            }
+        } else if id == CLAP_EXT_TIMER_SUPPORT {
+            if let Some(ext) = &extensions.timer_support {
+                return (&raw const *ext).cast();
+            }
+        } else if id == CLAP_EXT_VOICE_INFO {
+            if let Some(ext) = &extensions.voice_info {
+                return (&raw const *ext).cast();
+            }
         }
 
         null()
@@ -2,11 +2,11 @@
 //!
 //! The facilities here are mostly const functions to access audio buffers
 //! and event lists in a safe way.
-use std::ptr::NonNull;
+use std::{ops::Range, ptr::NonNull};
 
 use crate::{
     audio_buffer::{AudioBuffer, AudioBufferMut},
-    events::{Header, InputEvents, OutputEvents, Transport},
+    events::{Error, Event, EventBuilder, EventRef, Header, InputEvents, OutputEvents, Transport},
     ffi::{
         CLAP_PROCESS_CONTINUE, CLAP_PROCESS_CONTINUE_IF_NOT_QUIET, CLAP_PROCESS_SLEEP,
         CLAP_PROCESS_TAIL, clap_process, clap_process_status,
@@ -154,22 +154,239 @@ impl Process {
         unsafe { self.audio_outputs_unchecked(n) }
     }
 
-    pub const fn in_events(&self) -> InputEvents {
+    pub const fn in_events(&self) -> InputEvents<'_> {
         // SAFETY: By construction, the pointer is non-null.
         let in_events = unsafe { &*self.clap_process().in_events };
         // SAFETY: By construction, the pointers to `in_events` methods are Some.
         unsafe { InputEvents::new_unchecked(in_events) }
     }
 
-    pub fn out_events(&self) -> OutputEvents {
+    pub fn out_events(&self) -> OutputEvents<'_> {
         // SAFETY: By construction, the pointer is non-null.
         let out_events = unsafe { &*self.clap_process().out_events };
         // SAFETY: By construction, the pointer to `out_events` method is Some.
         unsafe { OutputEvents::new_unchecked(out_events) }
     }
+
+    /// Split the frames of this process call into blocks at the times of the
+    /// input events.
+    ///
+    /// See [`Blocks`] for details.
+    pub fn split_at_events(&mut self) -> Blocks<'_> {
+        Blocks::new(self)
+    }
+}
+
+/// Sample-accurate block splitting.
+///
+/// Each [`Block`] starts either at frame 0 or at the time of an input event,
+/// and ends just before the time of the next event, or at the end of the
+/// frames of the process call. The events that fall at the start of a block
+/// are available via [`Block::events()`]. Optionally, the blocks can be
+/// limited to a maximum size, e.g. to update parameter smoothing at a
+/// fixed rate.
+///
+/// Events timed past the last frame are delivered with the last block. If the
+/// process call has no frames, there is a single empty block holding all the
+/// events.
+///
+/// Blocks borrow the process mutably, so `Blocks` is not an [`Iterator`].
+/// Use it with `while let`:
+///
+/// ```no_run
+/// # use clap_clap::{events::EventRef, process::Process};
+/// # fn process(process: &mut Process) {
+/// let mut blocks = process.split_at_events().max_block_size(32);
+/// while let Some(mut block) = blocks.next_block() {
+///     for event in block.events() {
+///         if let EventRef::ParamValue(_) = event {
+///             // Update the parameter.
+///         }
+///     }
+///     for sample in block.audio_outputs(0).data32(0) {
+///         *sample = 0.0;
+///     }
+/// }
+/// # }
+/// ```
+pub struct Blocks<'a> {
+    process: &'a mut Process,
+    max_block_size: Option<u32>,
+    frame: u32,
+    event: u32,
+    events_count: u32,
+    done: bool,
+}
+
+impl<'a> Blocks<'a> {
+    fn new(process: &'a mut Process) -> Self {
+        let events_count = process.in_events().size();
+        Self {
+            process,
+            max_block_size: None,
+            frame: 0,
+            event: 0,
+            events_count,
+            done: false,
+        }
+    }
+
+    /// Limit the number of frames in each block.
+    ///
+    /// # Panic
+    ///
+    /// Panic if `max_block_size` is zero.
+    pub fn max_block_size(self, max_block_size: u32) -> Self {
+        assert!(max_block_size > 0, "block size must be greater than zero");
+        Self {
+            max_block_size: Some(max_block_size),
+            ..self
+        }
+    }
+
+    fn event_time(&self, index: u32) -> u32 {
+        // SAFETY: The index is checked by the caller against the list size.
+        unsafe { self.process.in_events().get_unchecked(index) }.time()
+    }
+
+    /// Get the next block, or None if all the frames have been covered.
+    pub fn next_block(&mut self) -> Option<Block<'_>> {
+        if self.done {
+            return None;
+        }
+
+        let start = self.frame;
+        let first_event = self.event;
+        while self.event < self.events_count && self.event_time(self.event) <= start {
+            self.event += 1;
+        }
+
+        let frames_count = self.process.frames_count();
+        let mut end = if self.event < self.events_count {
+            self.event_time(self.event).min(frames_count)
+        } else {
+            frames_count
+        };
+        if let Some(max_block_size) = self.max_block_size {
+            end = end.min(start.saturating_add(max_block_size));
+        }
+        if end >= frames_count {
+            end = frames_count;
+            self.event = self.events_count;
+            self.done = true;
+        }
+        self.frame = end;
+
+        Some(Block {
+            process: self.process,
+            frames: start..end,
+            events: first_event..self.event,
+        })
+    }
+}
+
+/// A range of frames of the process call, together with the input events
+/// that fall at its start.
+pub struct Block<'a> {
+    process: &'a mut Process,
+    frames: Range<u32>,
+    events: Range<u32>,
+}
+
+impl Block<'_> {
+    /// The first frame of the block, relative to the process call.
+    pub const fn frame_offset(&self) -> u32 {
+        self.frames.start
+    }
+
+    pub const fn frames_count(&self) -> u32 {
+        self.frames.end - self.frames.start
+    }
+
+    /// The input events that fall at the start of the block.
+    pub fn events(&self) -> impl ExactSizeIterator<Item = EventRef<'_>> {
+        let in_events = self.process.in_events();
+        self.events.clone().map(move |index| {
+            // SAFETY: By construction, the range of events lies within the list.
+            unsafe { in_events.get_unchecked(index) }.into()
+        })
+    }
+
+    pub const fn audio_inputs_count(&self) -> u32 {
+        self.process.audio_inputs_count()
+    }
+
+    /// # Panic
+    ///
+    /// This function will panic if `n` is greater or equal
+    /// to `self.audio_input_counts()`.
+    pub const fn audio_inputs(&self, n: u32) -> AudioBuffer<'_> {
+        let buffer = self.process.audio_inputs(n);
+        // SAFETY: By construction, the frames of the block lie within the frames
+        // of the process call.
+        unsafe { buffer.slice_frames_unchecked(self.frames.start, self.frames_count()) }
+    }
+
+    pub const fn audio_outputs_count(&self) -> u32 {
+        self.process.audio_outputs_count()
+    }
+
+    /// # Panic
+    ///
+    /// This function will panic if `n` is larger or equal
+    /// `self.audio_output_counts()`.
+    pub const fn audio_outputs(&mut self, n: u32) -> AudioBufferMut<'_> {
+        let (offset, frames_count) = (self.frames.start, self.frames_count());
+        let buffer = self.process.audio_outputs(n);
+        // SAFETY: By construction, the frames of the block lie within the frames
+        // of the process call.
+        unsafe { buffer.slice_frames_unchecked(offset, frames_count) }
+    }
+
+    /// The output events, with times relative to the start of the block.
+    pub fn out_events(&self) -> BlockOutputEvents<'_> {
+        BlockOutputEvents {
+            out_events: self.process.out_events(),
+            frame_offset: self.frame_offset(),
+        }
+    }
+}
+
+/// Output events of a [`Block`].
+///
+/// The events are pushed with times relative to the start of the block. Their
+/// times are rebased by the block's frame offset, so that they are relative to
+/// the process call, as the host expects.
+pub struct BlockOutputEvents<'a> {
+    out_events: OutputEvents<'a>,
+    frame_offset: u32,
+}
+
+impl BlockOutputEvents<'_> {
+    pub const fn frame_offset(&self) -> u32 {
+        self.frame_offset
+    }
+
+    /// Build the event with its time rebased by the frame offset, and push it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use clap_clap::{events::{EventBuilder, Note, NoteKind}, process::Block};
+    /// # fn process(block: &Block) {
+    /// // The note ends at the second frame of the block.
+    /// let note = Note::build(NoteKind::End).key(60).time(1);
+    /// block.out_events().try_push(note).unwrap();
+    /// # }
+    /// ```
+    pub fn try_push(&mut self, event: impl EventBuilder) -> Result<(), Error> {
+        let time = event.event().header().time();
+        let event = event.time(time.saturating_add(self.frame_offset));
+        self.out_events.try_push(event.event())
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Status {
     Continue,
     ContinueIfNotQuiet,
@@ -0,0 +1,9 @@
+//! Rendering audio through plugins outside of a DAW.
+//!
+//! Plugins are driven by the host from the module [`testing`], so this module
+//! also requires the feature: `testing`.
+//!
+//! [`testing`]: crate::testing
+
+pub mod offline;
+mod wav;
@@ -0,0 +1,398 @@
+//! Render audio through a plugin, faster than real time.
+//!
+//! [`Renderer`] instantiates a plugin with [`TestHost`], or loads it from a
+//! `.clap` bundle. It feeds the plugin the input audio block by block together
+//! with a timeline of events, and collects the output. This makes it possible
+//! to compare the output of a plugin against reference renders, e.g. in CI.
+//!
+//! The plugin is given one audio input port (if the input has any channels)
+//! and one audio output port. Event times in the timeline are absolute
+//! positions in frames since the start of the render. They are split into
+//! blocks and passed to the plugin with times relative to each block.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use clap_clap::prelude::*;
+//! # use clap_clap::render::offline::{Audio, Renderer};
+//! #[derive(Default)]
+//! struct Silence;
+//!
+//! impl Extensions<Self> for Silence {}
+//!
+//! impl Plugin for Silence {
+//!     type AudioThread = ();
+//!     const ID: &'static str = "com.example.silence";
+//!     const NAME: &'static str = "Silence";
+//!
+//!     fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<(), Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let input = Audio::new(48000, 2, 1000);
+//! let output = Renderer::new(48000, 256)
+//!     .render::<Silence>(&input)
+//!     .unwrap();
+//!
+//! assert_eq!(output.channel_count(), 2);
+//! assert_eq!(output.frames_count(), 1000);
+//! ```
+
+use std::{
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    events,
+    events::{EventQueue, EventRef},
+    ext::audio_ports::AudioPortFlags,
+    hosting,
+    hosting::Bundle,
+    plugin::Plugin,
+    render::wav,
+    testing,
+    testing::TestHost,
+};
+
+/// Non-interleaved, 32-bit audio data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audio {
+    sample_rate: u32,
+    frames_count: u32,
+    channels: Vec<Vec<f32>>,
+}
+
+impl Audio {
+    /// Silence of the given length.
+    pub fn new(sample_rate: u32, channel_count: u32, frames_count: u32) -> Self {
+        Self {
+            sample_rate,
+            frames_count,
+            channels: vec![vec![0.0; frames_count as usize]; channel_count as usize],
+        }
+    }
+
+    /// # Panic
+    ///
+    /// Panic if the channels are not of equal length, or if the length
+    /// doesn't fit into `u32`.
+    pub fn from_channels(sample_rate: u32, channels: Vec<Vec<f32>>) -> Self {
+        let frames_count = channels.first().map_or(0, Vec::len);
+        assert!(
+            channels.iter().all(|c| c.len() == frames_count),
+            "channels must be of equal length"
+        );
+        Self {
+            sample_rate,
+            frames_count: u32::try_from(frames_count).expect("number of frames must fit into u32"),
+            channels,
+        }
+    }
+
+    /// Decode a WAV file.
+    pub fn read_wav(reader: impl Read) -> Result<Self, Error> {
+        wav::read(reader)
+    }
+
+    /// Encode the audio as a WAV file with 32-bit float samples.
+    pub fn write_wav(&self, writer: impl Write) -> Result<(), Error> {
+        wav::write(self, writer)
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::read_wav(BufReader::new(File::open(path)?))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        self.write_wav(BufWriter::new(File::create(path)?))
+    }
+
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channel_count(&self) -> u32 {
+        self.channels.len() as u32
+    }
+
+    pub const fn frames_count(&self) -> u32 {
+        self.frames_count
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `channel` is greater or equal to `self.channel_count()`.
+    pub fn channel(&self, channel: u32) -> &[f32] {
+        &self.channels[channel as usize]
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `channel` is greater or equal to `self.channel_count()`.
+    pub fn channel_mut(&mut self, channel: u32) -> &mut [f32] {
+        &mut self.channels[channel as usize]
+    }
+
+    pub fn into_channels(self) -> Vec<Vec<f32>> {
+        self.channels
+    }
+}
+
+/// Render audio through a plugin.
+pub struct Renderer {
+    sample_rate: u32,
+    block_size: u32,
+    output_channels: Option<u32>,
+    tail: u32,
+    events: EventQueue,
+}
+
+impl Renderer {
+    /// # Panic
+    ///
+    /// Panic if `block_size` is zero.
+    pub fn new(sample_rate: u32, block_size: u32) -> Self {
+        assert!(block_size > 0, "block size must be greater than zero");
+        Self {
+            sample_rate,
+            block_size,
+            output_channels: None,
+            tail: 0,
+            events: EventQueue::new(),
+        }
+    }
+
+    /// The number of output channels.
+    ///
+    /// By default, this is the number of channels of the plugin's main audio
+    /// output port. If the plugin doesn't have one, it is the number of
+    /// channels of the input, or two if the input has no channels.
+    pub fn output_channels(self, channel_count: u32) -> Self {
+        Self {
+            output_channels: Some(channel_count),
+            ..self
+        }
+    }
+
+    /// Render `frames_count` more frames after the input ends.
+    pub fn tail(self, frames_count: u32) -> Self {
+        Self {
+            tail: frames_count,
+            ..self
+        }
+    }
+
+    /// The timeline of events, e.g. notes or parameter automation.
+    ///
+    /// Event times are positions in frames since the start of the render.
+    /// Rendering fails if an event is not earlier than the end of the render:
+    /// the length of the input plus the tail.
+    pub fn events(self, events: EventQueue) -> Self {
+        Self { events, ..self }
+    }
+
+    /// Process the input through a new instance of plugin `P`.
+    ///
+    /// The output has the length of the input plus the tail. To render a
+    /// plugin without audio inputs, e.g. an instrument, pass the input with
+    /// no channels: `Audio::new(sample_rate, 0, frames_count)`.
+    pub fn render<P: Plugin>(&self, input: &Audio) -> Result<Audio, Error> {
+        let mut instance = TestHost::new().instantiate::<P>()?;
+        instance.init()?;
+        self.render_instance(instance.into_instance(), input)
+    }
+
+    /// Process the input through a new instance of the plugin with the given
+    /// id, loaded from a `.clap` bundle.
+    ///
+    /// See [`Renderer::render()`].
+    pub fn render_bundle(
+        &self,
+        bundle: &Bundle,
+        plugin_id: &str,
+        input: &Audio,
+    ) -> Result<Audio, Error> {
+        let instance = bundle.instantiate(plugin_id, Arc::new(()))?;
+        self.render_instance(instance, input)
+    }
+
+    /// Process the input through an initialized plugin instance.
+    fn render_instance(
+        &self,
+        mut instance: hosting::PluginInstance,
+        input: &Audio,
+    ) -> Result<Audio, Error> {
+        if input.sample_rate() != self.sample_rate {
+            return Err(Error::SampleRate(input.sample_rate()));
+        }
+        let frames_count = input
+            .frames_count()
+            .checked_add(self.tail)
+            .ok_or(Error::TooLong)?;
+        // The timeline is sorted, so check only the last event.
+        if let Some(header) = self
+            .events
+            .len()
+            .checked_sub(1)
+            .and_then(|i| self.events.get(i))
+            && header.time() >= frames_count
+        {
+            return Err(Error::EventTime(header.time()));
+        }
+        let output_channels = self
+            .output_channels
+            .or_else(|| main_output_channels(&instance))
+            .unwrap_or(match input.channel_count() {
+                0 => 2,
+                n => n,
+            });
+
+        let inputs: &[u32] = if input.channel_count() > 0 {
+            &[input.channel_count()]
+        } else {
+            &[]
+        };
+        instance.set_audio_ports(inputs, &[output_channels]);
+        instance.activate(f64::from(self.sample_rate), 1, self.block_size)?;
+        instance.start_processing()?;
+
+        let mut output = Audio::new(self.sample_rate, output_channels, frames_count);
+        let mut next_event = 0;
+        let mut start = 0;
+        while start < frames_count {
+            let len = self.block_size.min(frames_count - start);
+            let end = start + len;
+
+            for channel in 0..input.channel_count() {
+                let buffer = instance.audio_input_mut(0).channel_mut(channel);
+                buffer.fill(0.0);
+                let from = (start.min(input.frames_count()) as usize)
+                    ..(end.min(input.frames_count()) as usize);
+                buffer[..from.len()].copy_from_slice(&input.channel(channel)[from]);
+            }
+
+            while let Some(header) = self.events.get(next_event) {
+                if header.time() >= end {
+                    break;
+                }
+                let time = header.time().saturating_sub(start);
+                instance
+                    .in_events()
+                    .try_push_at(EventRef::from(header), time)?;
+                next_event += 1;
+            }
+
+            instance.process(len)?;
+
+            for channel in 0..output_channels {
+                let (start, end) = (start as usize, end as usize);
+                output.channel_mut(channel)[start..end]
+                    .copy_from_slice(&instance.audio_output(0).channel(channel)[..end - start]);
+            }
+            start = end;
+        }
+
+        Ok(output)
+    }
+
+    /// Process a WAV file through a new instance of plugin `P`, and save the
+    /// output as a WAV file.
+    pub fn render_wav<P: Plugin>(
+        &self,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<(), Error> {
+        let input = Audio::open(input)?;
+        self.render::<P>(&input)?.save(output)
+    }
+
+    /// Process a WAV file through a new instance of the plugin with the given
+    /// id, loaded from a `.clap` bundle, and save the output as a WAV file.
+    pub fn render_bundle_wav(
+        &self,
+        bundle: &Bundle,
+        plugin_id: &str,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<(), Error> {
+        let input = Audio::open(input)?;
+        self.render_bundle(bundle, plugin_id, &input)?.save(output)
+    }
+}
+
+/// The number of channels of the plugin's main audio output port.
+fn main_output_channels(instance: &hosting::PluginInstance) -> Option<u32> {
+    let audio_ports = instance.get_extension().audio_ports().ok()?;
+    if audio_ports.count(false) == 0 {
+        return None;
+    }
+    // The main port must be at index 0.
+    let info = audio_ports.get(0, false).ok()?;
+    AudioPortFlags::IsMain
+        .is_set(info.flags)
+        .then_some(info.channel_count)
+}
+
+#[derive(Debug)]
+pub enum Error {
+    EventTime(u32),
+    Events(events::Error),
+    Hosting(hosting::Error),
+    IO(std::io::Error),
+    SampleRate(u32),
+    Testing(testing::Error),
+    TooLong,
+    Wav(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::EventTime(time) => write!(f, "event after the end of the render: {time}"),
+            Error::Events(e) => write!(f, "events: {e}"),
+            Error::Hosting(e) => write!(f, "hosting: {e}"),
+            Error::IO(e) => write!(f, "I/O: {e}"),
+            Error::SampleRate(rate) => write!(f, "sample rate of the input doesn't match: {rate}"),
+            Error::Testing(e) => write!(f, "testing: {e}"),
+            Error::TooLong => write!(f, "render too long"),
+            Error::Wav(msg) => write!(f, "WAV: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<events::Error> for Error {
+    fn from(value: events::Error) -> Self {
+        Self::Events(value)
+    }
+}
+
+impl From<hosting::Error> for Error {
+    fn from(value: hosting::Error) -> Self {
+        Self::Hosting(value)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::IO(value)
+    }
+}
+
+impl From<testing::Error> for Error {
+    fn from(value: testing::Error) -> Self {
+        Self::Testing(value)
+    }
+}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::Error::Offline(value)
+    }
+}
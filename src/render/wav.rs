@@ -0,0 +1,169 @@
+//! Minimal WAV (RIFF/WAVE) reader and writer.
+//!
+//! Reads integer PCM (8, 16, 24 and 32 bits) and IEEE float (32 and 64 bits)
+//! samples, including the `WAVE_FORMAT_EXTENSIBLE` variants. Writes 32-bit
+//! float samples.
+
+use std::io::{Read, Write};
+
+use crate::render::offline::{Audio, Error};
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+struct Format {
+    tag: u16,
+    channel_count: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    u16::from_le_bytes([bytes[0], bytes[1]])
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn parse_format(chunk: &[u8]) -> Result<Format, Error> {
+    if chunk.len() < 16 {
+        return Err(Error::Wav("fmt chunk too short"));
+    }
+    let mut tag = read_u16(&chunk[0..]);
+    if tag == WAVE_FORMAT_EXTENSIBLE {
+        // cbSize, valid bits, channel mask, then the sub-format GUID, whose
+        // first two bytes hold the format tag.
+        if chunk.len() < 40 {
+            return Err(Error::Wav("extensible fmt chunk too short"));
+        }
+        tag = read_u16(&chunk[24..]);
+    }
+    Ok(Format {
+        tag,
+        channel_count: read_u16(&chunk[2..]),
+        sample_rate: read_u32(&chunk[4..]),
+        bits_per_sample: read_u16(&chunk[14..]),
+    })
+}
+
+fn decode_sample(format: &Format, bytes: &[u8]) -> Result<f32, Error> {
+    Ok(match (format.tag, format.bits_per_sample) {
+        (WAVE_FORMAT_PCM, 8) => (f32::from(bytes[0]) - 128.0) / 128.0,
+        (WAVE_FORMAT_PCM, 16) => f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32768.0,
+        (WAVE_FORMAT_PCM, 24) => {
+            let sample = i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8;
+            sample as f32 / 8_388_608.0
+        }
+        (WAVE_FORMAT_PCM, 32) => {
+            let sample = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            (f64::from(sample) / 2_147_483_648.0) as f32
+        }
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => {
+            f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        }
+        (WAVE_FORMAT_IEEE_FLOAT, 64) => {
+            let mut sample = [0; 8];
+            sample.copy_from_slice(&bytes[..8]);
+            f64::from_le_bytes(sample) as f32
+        }
+        _ => return Err(Error::Wav("unsupported sample format")),
+    })
+}
+
+pub(crate) fn read(mut reader: impl Read) -> Result<Audio, Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(Error::Wav("not a RIFF/WAVE file"));
+    }
+
+    let mut format = None;
+    let mut data = None;
+    let mut rest = &bytes[12..];
+    while rest.len() >= 8 {
+        let id = &rest[0..4];
+        let size = read_u32(&rest[4..]) as usize;
+        let body = rest
+            .get(8..8 + size)
+            .ok_or(Error::Wav("chunk exceeds file size"))?;
+        match id {
+            b"fmt " => format = Some(parse_format(body)?),
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even number of bytes.
+        let next = (8 + size + size % 2).min(rest.len());
+        rest = &rest[next..];
+    }
+
+    let format = format.ok_or(Error::Wav("missing fmt chunk"))?;
+    let data = data.ok_or(Error::Wav("missing data chunk"))?;
+
+    let sample_size = usize::from(format.bits_per_sample / 8);
+    let channel_count = usize::from(format.channel_count);
+    if sample_size == 0 || channel_count == 0 {
+        return Err(Error::Wav("invalid fmt chunk"));
+    }
+    let frames_count = data.len() / (sample_size * channel_count);
+
+    let mut channels = vec![Vec::with_capacity(frames_count); channel_count];
+    for frame in data.chunks_exact(sample_size * channel_count) {
+        for (channel, sample) in channels.iter_mut().zip(frame.chunks_exact(sample_size)) {
+            channel.push(decode_sample(&format, sample)?);
+        }
+    }
+
+    Ok(Audio::from_channels(format.sample_rate, channels))
+}
+
+pub(crate) fn write(audio: &Audio, mut writer: impl Write) -> Result<(), Error> {
+    let channel_count =
+        u16::try_from(audio.channel_count()).map_err(|_| Error::Wav("too many channels"))?;
+    let block_align =
+        u16::try_from(u32::from(channel_count) * 4).map_err(|_| Error::Wav("too many channels"))?;
+    let byte_rate = audio
+        .sample_rate()
+        .checked_mul(u32::from(block_align))
+        .ok_or(Error::Wav("sample rate too high"))?;
+    let data_size = u32::try_from(u64::from(audio.frames_count()) * u64::from(block_align))
+        .ok()
+        .filter(|size| *size <= u32::MAX - 50)
+        .ok_or(Error::Wav("audio too long"))?;
+
+    let mut header = Vec::with_capacity(58);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(50 + data_size).to_le_bytes());
+    header.extend_from_slice(b"WAVE");
+
+    header.extend_from_slice(b"fmt ");
+    header.extend_from_slice(&18u32.to_le_bytes());
+    header.extend_from_slice(&WAVE_FORMAT_IEEE_FLOAT.to_le_bytes());
+    header.extend_from_slice(&channel_count.to_le_bytes());
+    header.extend_from_slice(&audio.sample_rate().to_le_bytes());
+    header.extend_from_slice(&byte_rate.to_le_bytes());
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&32u16.to_le_bytes());
+    header.extend_from_slice(&0u16.to_le_bytes());
+
+    header.extend_from_slice(b"fact");
+    header.extend_from_slice(&4u32.to_le_bytes());
+    header.extend_from_slice(&audio.frames_count().to_le_bytes());
+
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_size.to_le_bytes());
+    writer.write_all(&header)?;
+
+    let mut frame = Vec::with_capacity(usize::from(block_align));
+    for i in 0..audio.frames_count() as usize {
+        frame.clear();
+        for channel in 0..audio.channel_count() {
+            frame.extend_from_slice(&audio.channel(channel)[i].to_le_bytes());
+        }
+        writer.write_all(&frame)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,509 @@
+//! A host for testing plugins without a DAW.
+//!
+//! [`TestHost`] implements a CLAP host that records the requests made by
+//! plugins, and the messages they send to the host's log. Plugins are
+//! instantiated with [`TestHost::instantiate()`], and then driven through
+//! their lifecycle with [`PluginInstance`]. The instance owns its audio
+//! buffers and event queues, so that the plugin's real processing paths can be
+//! exercised from unit tests.
+//!
+//! This module is available with the feature: `testing`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use clap_clap::prelude::*;
+//! # use clap_clap::testing::TestHost;
+//! #[derive(Default)]
+//! struct Gain;
+//!
+//! impl Extensions<Self> for Gain {}
+//!
+//! impl Plugin for Gain {
+//!     type AudioThread = GainThread;
+//!     const ID: &'static str = "com.example.gain";
+//!     const NAME: &'static str = "Gain";
+//!
+//!     fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<GainThread, Error> {
+//!         Ok(GainThread)
+//!     }
+//! }
+//!
+//! struct GainThread;
+//!
+//! impl AudioThread<Gain> for GainThread {
+//!     fn process(&mut self, process: &mut Process) -> Result<Status, Error> {
+//!         let n = process.frames_count() as usize;
+//!         let input = process.audio_inputs(0).data32(0)[..n].to_vec();
+//!         let mut output = process.audio_outputs(0);
+//!         for (o, i) in output.data32(0).iter_mut().zip(input) {
+//!             *o = i * 0.5;
+//!         }
+//!         Ok(Continue)
+//!     }
+//! }
+//!
+//! let host = TestHost::new();
+//! let mut instance = host.instantiate::<Gain>().unwrap();
+//! instance.set_audio_ports(&[1], &[1]);
+//!
+//! instance.init().unwrap();
+//! instance.activate(48000.0, 1, 64).unwrap();
+//! instance.start_processing().unwrap();
+//!
+//! instance.audio_input_mut(0).channel_mut(0).fill(1.0);
+//! instance.process(64).unwrap();
+//! assert!(instance.audio_output(0).channel(0).iter().all(|&s| s == 0.5));
+//! ```
+
+use std::{
+    ffi::{CStr, c_char, c_void},
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+    ptr::{null, null_mut},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+
+use crate::{
+    events::EventQueue,
+    ext::log::Severity,
+    factory,
+    factory::{FactoryHost, FactoryPlugin, FactoryPluginPrototype},
+    ffi::{CLAP_EXT_LOG, CLAP_VERSION, clap_host, clap_host_log, clap_log_severity, clap_plugin},
+    hosting::{self, OwnedAudioBuffer, severity_from_clap},
+    plugin::{ClapPlugin, Plugin},
+    process::Status,
+};
+
+#[repr(C)]
+struct HostData {
+    // This field must be first: callbacks cast the pointer to clap_host into a
+    // pointer to HostData.
+    clap_host: clap_host,
+    request_restart: AtomicU32,
+    request_process: AtomicU32,
+    request_callback: AtomicU32,
+    log: Mutex<Vec<(Severity, String)>>,
+}
+
+// SAFETY: The raw pointers held by clap_host point to static strings.
+unsafe impl Send for HostData {}
+// SAFETY: The raw pointers held by clap_host point to static strings.
+unsafe impl Sync for HostData {}
+
+/// A CLAP host that records the plugins' requests.
+///
+/// Cloning the host yields a handle to the same recorded data.
+#[derive(Clone)]
+pub struct TestHost(Arc<HostData>);
+
+impl TestHost {
+    pub fn new() -> Self {
+        Self(Arc::new(HostData {
+            clap_host: clap_host {
+                clap_version: CLAP_VERSION,
+                host_data: null_mut(),
+                name: c"clap-clap test host".as_ptr(),
+                vendor: c"clap-clap".as_ptr(),
+                url: c"".as_ptr(),
+                version: c"".as_ptr(),
+                get_extension: Some(ffi::get_extension),
+                request_restart: Some(ffi::request_restart),
+                request_process: Some(ffi::request_process),
+                request_callback: Some(ffi::request_callback),
+            },
+            request_restart: AtomicU32::new(0),
+            request_process: AtomicU32::new(0),
+            request_callback: AtomicU32::new(0),
+            log: Mutex::new(Vec::new()),
+        }))
+    }
+
+    pub fn clap_host(&self) -> &clap_host {
+        &self.0.clap_host
+    }
+
+    /// The number of calls to `request_restart()`.
+    pub fn request_restart_count(&self) -> u32 {
+        self.0.request_restart.load(Ordering::Acquire)
+    }
+
+    /// The number of calls to `request_process()`.
+    pub fn request_process_count(&self) -> u32 {
+        self.0.request_process.load(Ordering::Acquire)
+    }
+
+    /// The number of calls to `request_callback()`.
+    pub fn request_callback_count(&self) -> u32 {
+        self.0.request_callback.load(Ordering::Acquire)
+    }
+
+    /// Messages sent to the host's log, in the order they were received.
+    pub fn log_messages(&self) -> Vec<(Severity, String)> {
+        self.0.log.lock().unwrap().clone()
+    }
+
+    /// Reset all counters and remove recorded log messages.
+    pub fn clear(&self) {
+        self.0.request_restart.store(0, Ordering::Release);
+        self.0.request_process.store(0, Ordering::Release);
+        self.0.request_callback.store(0, Ordering::Release);
+        self.0.log.lock().unwrap().clear();
+    }
+
+    /// Create a new instance of the plugin.
+    ///
+    /// The plugin is created by the plugin factory, the same way a DAW would
+    /// create it. The instance is not initialized.
+    pub fn instantiate<P: Plugin>(&self) -> Result<PluginInstance<P>, Error> {
+        let prototype = FactoryPluginPrototype::<P>::build()?;
+        // SAFETY: The pointer to clap_host and all its methods are non-null.
+        // The host data is kept alive by the instance.
+        let factory_host = unsafe { FactoryHost::new_unchecked(self.clap_host()) };
+        // SAFETY: The returned plugin holds its own copy of the descriptor, so
+        // the pointer remains valid after the prototype is dropped.
+        let clap_plugin = unsafe { prototype.clap_plugin(factory_host) }?;
+        // SAFETY: All methods of a plugin built by the factory are non-null.
+        // The instance keeps a handle to the host data.
+        let instance = unsafe {
+            hosting::PluginInstance::new_unchecked(clap_plugin, Box::new(self.clone()), None)
+        };
+
+        Ok(PluginInstance {
+            host: self.clone(),
+            instance,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl Default for TestHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An owned audio buffer with 32-bit channels.
+pub type TestAudioBuffer = OwnedAudioBuffer;
+
+/// A plugin instantiated by [`TestHost`].
+///
+/// The instance follows the lifecycle of a CLAP plugin: it must be
+/// initialized, then activated, and then started processing, before the
+/// plugin's audio thread can process audio. Calling the methods out of order
+/// returns an error and the plugin is not called.
+///
+/// When dropped, the instance stops processing, deactivates and destroys the
+/// plugin.
+///
+/// This is a typed wrapper around [`hosting::PluginInstance`], that gives
+/// access to the plugin and its audio thread.
+pub struct PluginInstance<P: Plugin> {
+    host: TestHost,
+    instance: hosting::PluginInstance,
+    _marker: PhantomData<P>,
+}
+
+impl<P: Plugin> PluginInstance<P> {
+    pub fn host(&self) -> &TestHost {
+        &self.host
+    }
+
+    pub const fn clap_plugin(&self) -> &clap_plugin {
+        self.instance.clap_plugin()
+    }
+
+    pub(crate) fn into_instance(self) -> hosting::PluginInstance {
+        self.instance
+    }
+
+    /// Obtain a mutable reference to the plugin.
+    pub fn plugin(&mut self) -> &mut P {
+        // SAFETY: The pointer was obtained from the factory for plugin type P.
+        // The instance drives the plugin from a single thread, so we have
+        // exclusive access to the plugin for the lifetime of &mut self.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(self.instance.as_ptr()) };
+        unsafe { &mut *(clap_plugin.plugin() as *mut P) }
+    }
+
+    /// Obtain a mutable reference to the audio thread, if the plugin is active.
+    pub fn audio_thread(&mut self) -> Option<&mut P::AudioThread> {
+        // SAFETY: The pointer was obtained from the factory for plugin type P.
+        // The instance drives the plugin from a single thread, so we have
+        // exclusive access to the audio thread for the lifetime of &mut self.
+        let mut clap_plugin = unsafe { ClapPlugin::<P>::new_unchecked(self.instance.as_ptr()) };
+        unsafe { clap_plugin.audio_thread() }.map(|a| unsafe { &mut *(a as *mut _) })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.instance.is_active()
+    }
+
+    pub fn is_processing(&self) -> bool {
+        self.instance.is_processing()
+    }
+
+    pub fn init(&mut self) -> Result<(), Error> {
+        Ok(self.instance.init()?)
+    }
+
+    /// Activate the plugin.
+    ///
+    /// The audio buffers are resized to hold `max_frames_count` frames.
+    pub fn activate(
+        &mut self,
+        sample_rate: f64,
+        min_frames_count: u32,
+        max_frames_count: u32,
+    ) -> Result<(), Error> {
+        Ok(self
+            .instance
+            .activate(sample_rate, min_frames_count, max_frames_count)?)
+    }
+
+    /// Deactivate the plugin, stopping processing first if needed.
+    pub fn deactivate(&mut self) -> Result<(), Error> {
+        Ok(self.instance.deactivate()?)
+    }
+
+    pub fn start_processing(&mut self) -> Result<(), Error> {
+        Ok(self.instance.start_processing()?)
+    }
+
+    pub fn stop_processing(&mut self) -> Result<(), Error> {
+        Ok(self.instance.stop_processing()?)
+    }
+
+    pub fn reset(&mut self) -> Result<(), Error> {
+        Ok(self.instance.reset()?)
+    }
+
+    /// Call the plugin's `on_main_thread()`, as if the host answered
+    /// `request_callback()`.
+    pub fn on_main_thread(&mut self) {
+        self.instance.on_main_thread();
+    }
+
+    /// Set the number of channels for each audio input and output port.
+    ///
+    /// The previous buffers are discarded. If the plugin is active, the
+    /// buffers hold the maximal number of frames the plugin was activated
+    /// with.
+    pub fn set_audio_ports(&mut self, inputs: &[u32], outputs: &[u32]) {
+        self.instance.set_audio_ports(inputs, outputs);
+    }
+
+    pub fn audio_inputs_count(&self) -> u32 {
+        self.instance.audio_inputs_count()
+    }
+
+    pub fn audio_outputs_count(&self) -> u32 {
+        self.instance.audio_outputs_count()
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_inputs_count()`.
+    pub fn audio_input(&self, port: u32) -> &TestAudioBuffer {
+        self.instance.audio_input(port)
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_inputs_count()`.
+    pub fn audio_input_mut(&mut self, port: u32) -> &mut TestAudioBuffer {
+        self.instance.audio_input_mut(port)
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_outputs_count()`.
+    pub fn audio_output(&self, port: u32) -> &TestAudioBuffer {
+        self.instance.audio_output(port)
+    }
+
+    /// # Panic
+    ///
+    /// Panic if `port` is greater or equal to `self.audio_outputs_count()`.
+    pub fn audio_output_mut(&mut self, port: u32) -> &mut TestAudioBuffer {
+        self.instance.audio_output_mut(port)
+    }
+
+    /// Events to be sent to the plugin with the next call to `process()`.
+    pub fn in_events(&mut self) -> &mut EventQueue {
+        self.instance.in_events()
+    }
+
+    /// Events that the plugin sent during the last call to `process()`.
+    pub fn out_events(&self) -> &EventQueue {
+        self.instance.out_events()
+    }
+
+    /// Process `frames_count` frames.
+    ///
+    /// The input events are consumed: the queue is empty after the call. The
+    /// output events from the previous call are discarded. The steady time
+    /// starts at zero when the plugin is activated and advances by
+    /// `frames_count` with each call.
+    ///
+    /// # Panic
+    ///
+    /// Panic if `frames_count` is greater than the maximal number of frames
+    /// the plugin was activated with.
+    pub fn process(&mut self, frames_count: u32) -> Result<Status, Error> {
+        Ok(self.instance.process(frames_count)?)
+    }
+}
+
+mod ffi {
+    use super::*;
+
+    static TEST_HOST_LOG: clap_host_log = clap_host_log { log: Some(log) };
+
+    /// # Safety
+    ///
+    /// `host` must be non-null and must point to the `clap_host` field of
+    /// `HostData`.
+    unsafe fn host_data<'a>(host: *const clap_host) -> &'a HostData {
+        unsafe { &*host.cast::<HostData>() }
+    }
+
+    pub(super) extern "C-unwind" fn get_extension(
+        host: *const clap_host,
+        extension_id: *const c_char,
+    ) -> *const c_void {
+        if host.is_null() || extension_id.is_null() {
+            return null();
+        }
+        // SAFETY: We just checked that the pointer is non-null. The plugin
+        // passes a valid C string.
+        let extension_id = unsafe { CStr::from_ptr(extension_id) };
+        if extension_id == CLAP_EXT_LOG {
+            &raw const TEST_HOST_LOG as *const _
+        } else {
+            null()
+        }
+    }
+
+    pub(super) extern "C-unwind" fn request_restart(host: *const clap_host) {
+        if host.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by TestHost.
+        let host_data = unsafe { host_data(host) };
+        host_data.request_restart.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(super) extern "C-unwind" fn request_process(host: *const clap_host) {
+        if host.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by TestHost.
+        let host_data = unsafe { host_data(host) };
+        host_data.request_process.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(super) extern "C-unwind" fn request_callback(host: *const clap_host) {
+        if host.is_null() {
+            return;
+        }
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by TestHost.
+        let host_data = unsafe { host_data(host) };
+        host_data.request_callback.fetch_add(1, Ordering::AcqRel);
+    }
+
+    extern "C-unwind" fn log(
+        host: *const clap_host,
+        severity: clap_log_severity,
+        msg: *const c_char,
+    ) {
+        if host.is_null() || msg.is_null() {
+            return;
+        }
+        let Some(severity) = severity_from_clap(severity) else {
+            return;
+        };
+        // SAFETY: We just checked that the pointer is non-null. It was created
+        // by TestHost.
+        let host_data = unsafe { host_data(host) };
+        // SAFETY: We just checked that the pointer is non-null. The plugin
+        // passes a valid C string.
+        let msg = unsafe { CStr::from_ptr(msg) }
+            .to_string_lossy()
+            .into_owned();
+        host_data.log.lock().unwrap().push((severity, msg));
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Factory(factory::Error),
+    Init,
+    Activate,
+    StartProcessing,
+    Process,
+    AlreadyInitialized,
+    NotInitialized,
+    AlreadyActive,
+    NotActive,
+    AlreadyProcessing,
+    NotProcessing,
+    Hosting(hosting::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Factory(e) => write!(f, "factory: {e}"),
+            Error::Init => write!(f, "plugin initialization failed"),
+            Error::Activate => write!(f, "plugin activation failed"),
+            Error::StartProcessing => write!(f, "starting processing failed"),
+            Error::Process => write!(f, "processing failed"),
+            Error::AlreadyInitialized => write!(f, "plugin already initialized"),
+            Error::NotInitialized => write!(f, "plugin not initialized"),
+            Error::AlreadyActive => write!(f, "plugin already active"),
+            Error::NotActive => write!(f, "plugin not active"),
+            Error::AlreadyProcessing => write!(f, "plugin already processing"),
+            Error::NotProcessing => write!(f, "plugin not processing"),
+            Error::Hosting(e) => write!(f, "hosting: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<factory::Error> for Error {
+    fn from(value: factory::Error) -> Self {
+        Self::Factory(value)
+    }
+}
+
+impl From<hosting::Error> for Error {
+    fn from(value: hosting::Error) -> Self {
+        match value {
+            hosting::Error::Init => Self::Init,
+            hosting::Error::Activate => Self::Activate,
+            hosting::Error::StartProcessing => Self::StartProcessing,
+            hosting::Error::Process => Self::Process,
+            hosting::Error::AlreadyInitialized => Self::AlreadyInitialized,
+            hosting::Error::NotInitialized => Self::NotInitialized,
+            hosting::Error::AlreadyActive => Self::AlreadyActive,
+            hosting::Error::NotActive => Self::NotActive,
+            hosting::Error::AlreadyProcessing => Self::AlreadyProcessing,
+            hosting::Error::NotProcessing => Self::NotProcessing,
+            e => Self::Hosting(e),
+        }
+    }
+}
+
+impl From<Error> for crate::Error {
+    fn from(value: Error) -> Self {
+        crate::Error::Testing(value)
+    }
+}
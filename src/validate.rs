@@ -0,0 +1,539 @@
+//! Check plugins for conformance with the CLAP specification.
+//!
+//! [`validate()`] takes a [`Bundle`], loaded from a file or made from the
+//! static entry exported by [`entry!`], and runs a series of checks against
+//! every plugin in it. Each plugin is checked with fresh instances, and each
+//! check is recorded in the [`Report`] as passed, failed or skipped.
+//!
+//! The checks are:
+//!
+//! | Check          | What is checked                                           |
+//! |----------------|-----------------------------------------------------------|
+//! | `factory`      | descriptors can be queried and plugin ids are unique      |
+//! | `descriptor`   | descriptor strings, CLAP version and features             |
+//! | `instantiate`  | the plugin can be created and initialized                 |
+//! | `extensions`   | `get_extension()` returns stable, complete vtables        |
+//! | `params`       | parameter info, values and text conversions round-trip    |
+//! | `state`        | saving and loading the state is idempotent                |
+//! | `activate`     | activate and deactivate cycles with various sample rates  |
+//! | `process`      | processing blocks of audio and events; output event order |
+//!
+//! # Example
+//!
+//! ```rust
+//! # use clap_clap::prelude::*;
+//! # use clap_clap::{hosting::Bundle, validate};
+//! #[derive(Default)]
+//! struct Silence;
+//!
+//! impl Extensions<Self> for Silence {}
+//!
+//! impl Plugin for Silence {
+//!     type AudioThread = ();
+//!     const ID: &'static str = "com.example.silence";
+//!     const NAME: &'static str = "Silence";
+//!
+//!     fn features() -> impl Iterator<Item = &'static str> {
+//!         [plugin_features::AUDIO_EFFECT].into_iter()
+//!     }
+//!
+//!     fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<(), Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! entry!(Silence);
+//!
+//! # fn main() {
+//! // SAFETY: The entry is not initialized anywhere else.
+//! let bundle = unsafe { Bundle::from_entry(&_clap_entry::clap_entry, "silence.clap") }.unwrap();
+//! let report = validate::validate(&bundle);
+//!
+//! assert!(report.passed(), "{report}");
+//! # }
+//! ```
+//!
+//! [`entry!`]: crate::entry
+
+use std::{
+    collections::HashSet,
+    ffi::CStr,
+    fmt::{Display, Formatter},
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::Arc,
+};
+
+use crate::{
+    events::{EventBuilder, EventQueue, NoteBuilder, NoteKind, ParamValueBuilder},
+    ffi::{
+        CLAP_EXT_AMBISONIC, CLAP_EXT_AUDIO_PORTS, CLAP_EXT_AUDIO_PORTS_ACTIVATION,
+        CLAP_EXT_AUDIO_PORTS_CONFIG, CLAP_EXT_CONFIGURABLE_AUDIO_PORTS, CLAP_EXT_CONTEXT_MENU,
+        CLAP_EXT_GUI, CLAP_EXT_LATENCY, CLAP_EXT_NOTE_NAME, CLAP_EXT_NOTE_PORTS,
+        CLAP_EXT_PARAM_INDICATION, CLAP_EXT_PARAMS, CLAP_EXT_POSIX_FD_SUPPORT,
+        CLAP_EXT_PRESET_LOAD, CLAP_EXT_REMOTE_CONTROLS, CLAP_EXT_RENDER, CLAP_EXT_STATE,
+        CLAP_EXT_STATE_CONTEXT, CLAP_EXT_SURROUND, CLAP_EXT_TAIL, CLAP_EXT_THREAD_POOL,
+        CLAP_EXT_TIMER_SUPPORT, CLAP_EXT_TRACK_INFO, CLAP_EXT_VOICE_INFO,
+    },
+    hosting::{self, Bundle, Descriptor, PluginInstance},
+    plugin_features::{ANALYZER, AUDIO_EFFECT, INSTRUMENT, NOTE_DETECTOR, NOTE_EFFECT},
+};
+
+/// Plugin extensions that the check: `extensions` queries.
+const EXTENSION_IDS: [&CStr; 24] = [
+    CLAP_EXT_AMBISONIC,
+    CLAP_EXT_AUDIO_PORTS,
+    CLAP_EXT_AUDIO_PORTS_ACTIVATION,
+    CLAP_EXT_AUDIO_PORTS_CONFIG,
+    CLAP_EXT_CONFIGURABLE_AUDIO_PORTS,
+    CLAP_EXT_CONTEXT_MENU,
+    CLAP_EXT_GUI,
+    CLAP_EXT_LATENCY,
+    CLAP_EXT_NOTE_NAME,
+    CLAP_EXT_NOTE_PORTS,
+    CLAP_EXT_PARAMS,
+    CLAP_EXT_PARAM_INDICATION,
+    CLAP_EXT_POSIX_FD_SUPPORT,
+    CLAP_EXT_PRESET_LOAD,
+    CLAP_EXT_REMOTE_CONTROLS,
+    CLAP_EXT_RENDER,
+    CLAP_EXT_STATE,
+    CLAP_EXT_STATE_CONTEXT,
+    CLAP_EXT_SURROUND,
+    CLAP_EXT_TAIL,
+    CLAP_EXT_THREAD_POOL,
+    CLAP_EXT_TIMER_SUPPORT,
+    CLAP_EXT_TRACK_INFO,
+    CLAP_EXT_VOICE_INFO,
+];
+
+const MAIN_CATEGORIES: [&str; 5] = [
+    INSTRUMENT,
+    AUDIO_EFFECT,
+    NOTE_EFFECT,
+    NOTE_DETECTOR,
+    ANALYZER,
+];
+
+const SAMPLE_RATES: [f64; 3] = [44100.0, 48000.0, 96000.0];
+const BLOCK_SIZE: u32 = 256;
+const BLOCKS_COUNT: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail(String),
+    Skip(String),
+}
+
+/// The outcome of a single check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    /// The plugin checked, or `None` for checks of the whole bundle.
+    pub plugin_id: Option<String>,
+    pub name: &'static str,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// True if no check failed.
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &Check> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.outcome, Outcome::Fail(_)))
+    }
+
+    /// Find the check by the plugin id and the name of the check.
+    pub fn get(&self, plugin_id: Option<&str>, name: &str) -> Option<&Check> {
+        self.checks
+            .iter()
+            .find(|check| check.plugin_id.as_deref() == plugin_id && check.name == name)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for check in &self.checks {
+            let plugin_id = check.plugin_id.as_deref().unwrap_or("*");
+            match &check.outcome {
+                Outcome::Pass => writeln!(f, "PASS {plugin_id} {}", check.name)?,
+                Outcome::Fail(msg) => writeln!(f, "FAIL {plugin_id} {}: {msg}", check.name)?,
+                Outcome::Skip(msg) => writeln!(f, "SKIP {plugin_id} {}: {msg}", check.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run all checks against every plugin in the bundle.
+pub fn validate(bundle: &Bundle) -> Report {
+    let mut report = Report::default();
+    let mut record = |plugin_id: Option<&str>, name, check: &mut dyn FnMut() -> Checked| {
+        let outcome = match catch_unwind(AssertUnwindSafe(check)) {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(msg)) => Outcome::Fail(msg),
+            Err(_) => Outcome::Fail("panicked".to_string()),
+        };
+        let passed = outcome == Outcome::Pass;
+        report.checks.push(Check {
+            plugin_id: plugin_id.map(str::to_owned),
+            name,
+            outcome,
+        });
+        passed
+    };
+
+    let mut descriptors = Vec::new();
+    record(None, "factory", &mut || {
+        descriptors = check_factory(bundle)?;
+        Ok(Outcome::Pass)
+    });
+
+    for descriptor in &descriptors {
+        let plugin_id = Some(descriptor.id.as_str());
+        record(plugin_id, "descriptor", &mut || {
+            check_descriptor(descriptor)
+        });
+
+        let instantiated = record(plugin_id, "instantiate", &mut || {
+            check_instantiate(bundle, descriptor)
+        });
+        let checks: [(&'static str, CheckFn); 5] = [
+            ("extensions", check_extensions),
+            ("params", check_params),
+            ("state", check_state),
+            ("activate", check_activate),
+            ("process", check_process),
+        ];
+        for (name, check) in checks {
+            record(plugin_id, name, &mut || {
+                if instantiated {
+                    check(bundle, &descriptor.id)
+                } else {
+                    Ok(Outcome::Skip("plugin cannot be instantiated".to_string()))
+                }
+            });
+        }
+    }
+
+    report
+}
+
+/// The outcome of a check, with failures as `Err`.
+type Checked = Result<Outcome, String>;
+
+type CheckFn = fn(&Bundle, &str) -> Checked;
+
+macro_rules! ensure {
+    ($cond:expr, $($msg:tt)+) => {
+        if !$cond {
+            return Err(format!($($msg)+));
+        }
+    };
+}
+
+fn instantiate(bundle: &Bundle, plugin_id: &str) -> Result<PluginInstance, String> {
+    bundle
+        .instantiate(plugin_id, Arc::new(()))
+        .map_err(|e| e.to_string())
+}
+
+fn check_factory(bundle: &Bundle) -> Result<Vec<Descriptor>, String> {
+    let plugin_count = bundle.plugin_count();
+    let mut descriptors = Vec::new();
+    for index in 0..plugin_count {
+        let descriptor = bundle
+            .descriptor(index)
+            .ok_or_else(|| format!("no descriptor for index: {index}"))?;
+        ensure!(
+            descriptors
+                .iter()
+                .all(|d: &Descriptor| d.id != descriptor.id),
+            "duplicate plugin id: {}",
+            descriptor.id
+        );
+        descriptors.push(descriptor);
+    }
+    ensure!(
+        bundle.descriptor(plugin_count).is_none(),
+        "descriptor for index out of bounds: {plugin_count}"
+    );
+    Ok(descriptors)
+}
+
+fn check_descriptor(descriptor: &Descriptor) -> Checked {
+    ensure!(!descriptor.id.is_empty(), "empty plugin id");
+    ensure!(!descriptor.name.is_empty(), "empty plugin name");
+    ensure!(
+        descriptor.clap_version.0 >= 1,
+        "incompatible CLAP version: {:?}",
+        descriptor.clap_version
+    );
+    ensure!(
+        descriptor.invalid_utf8.is_empty(),
+        "invalid UTF-8 in descriptor fields: {}",
+        descriptor.invalid_utf8.join(", ")
+    );
+
+    let mut features = HashSet::new();
+    for feature in &descriptor.features {
+        ensure!(
+            !feature.is_empty() && !feature.contains(char::is_whitespace),
+            "invalid feature: {feature:?}"
+        );
+        ensure!(features.insert(feature), "duplicate feature: {feature}");
+    }
+    ensure!(
+        MAIN_CATEGORIES
+            .iter()
+            .any(|category| features.contains(&category.to_string())),
+        "no main category in features: {:?}",
+        descriptor.features
+    );
+    Ok(Outcome::Pass)
+}
+
+fn check_instantiate(bundle: &Bundle, descriptor: &Descriptor) -> Checked {
+    let instance = instantiate(bundle, &descriptor.id)?;
+    ensure!(
+        instance.descriptor() == *descriptor,
+        "plugin descriptor differs from the factory's"
+    );
+    Ok(Outcome::Pass)
+}
+
+fn check_extensions(bundle: &Bundle, plugin_id: &str) -> Checked {
+    let instance = instantiate(bundle, plugin_id)?;
+    let clap_plugin = instance.clap_plugin();
+    let get_extension = |id: &CStr| {
+        // SAFETY: The plugin is initialized, and get_extension() is non-null
+        // by construction of PluginInstance.
+        unsafe { clap_plugin.get_extension.unwrap()(clap_plugin, id.as_ptr()) }
+    };
+
+    for id in EXTENSION_IDS {
+        ensure!(
+            get_extension(id) == get_extension(id),
+            "extension pointer not stable: {}",
+            id.to_string_lossy()
+        );
+    }
+    ensure!(
+        get_extension(c"clap-clap.validate.unknown").is_null(),
+        "unknown extension is not null"
+    );
+
+    let extensions = instance.get_extension();
+    let complete = |result: Result<(), hosting::Error>| match result {
+        Ok(()) | Err(hosting::Error::ExtensionNotFound(_)) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    };
+    complete(extensions.audio_ports().map(|_| ()))?;
+    complete(extensions.note_ports().map(|_| ()))?;
+    complete(extensions.params().map(|_| ()))?;
+    complete(extensions.state().map(|_| ()))?;
+    Ok(Outcome::Pass)
+}
+
+fn check_params(bundle: &Bundle, plugin_id: &str) -> Checked {
+    let instance = instantiate(bundle, plugin_id)?;
+    let Ok(params) = instance.get_extension().params() else {
+        return Ok(Outcome::Skip("no params extension".to_string()));
+    };
+
+    let mut ids = Vec::new();
+    for index in 0..params.count() {
+        let info = params.get_info(index).map_err(|e| e.to_string())?;
+        let id = info.id;
+        ensure!(id.is_valid(), "invalid param id at index: {index}");
+        ensure!(!ids.contains(&id), "duplicate param id: {id:?}");
+        ids.push(id);
+        ensure!(
+            info.min_value.is_finite()
+                && info.max_value.is_finite()
+                && info.min_value <= info.default_value
+                && info.default_value <= info.max_value,
+            "invalid range of param {id:?}: {} <= {} <= {}",
+            info.min_value,
+            info.default_value,
+            info.max_value
+        );
+
+        let value = params.get_value(id).map_err(|e| e.to_string())?;
+        ensure!(
+            (info.min_value..=info.max_value).contains(&value),
+            "value of param {id:?} out of range: {value}"
+        );
+
+        for value in [info.min_value, info.default_value, info.max_value] {
+            // Text conversion is optional.
+            let Ok(text) = params.value_to_text(id, value) else {
+                continue;
+            };
+            let parsed = params
+                .text_to_value(id, &text)
+                .map_err(|e| format!("param {id:?}: cannot convert {text:?} to value: {e}"))?;
+            let round_trip = params
+                .value_to_text(id, parsed)
+                .map_err(|e| format!("param {id:?}: cannot convert {parsed} to text: {e}"))?;
+            ensure!(
+                text == round_trip,
+                "param {id:?}: text conversion doesn't round-trip: {text:?} -> {parsed} -> \
+                 {round_trip:?}"
+            );
+        }
+    }
+    Ok(Outcome::Pass)
+}
+
+fn check_state(bundle: &Bundle, plugin_id: &str) -> Checked {
+    let source = instantiate(bundle, plugin_id)?;
+    let Ok(state) = source.get_extension().state() else {
+        return Ok(Outcome::Skip("no state extension".to_string()));
+    };
+
+    let saved = state.save().map_err(|e| e.to_string())?;
+    state.load(&saved).map_err(|e| e.to_string())?;
+    ensure!(
+        state.save().map_err(|e| e.to_string())? == saved,
+        "state differs after loading it into the same instance"
+    );
+
+    let target = instantiate(bundle, plugin_id)?;
+    let state = target.get_extension().state().map_err(|e| e.to_string())?;
+    state.load(&saved).map_err(|e| e.to_string())?;
+    ensure!(
+        state.save().map_err(|e| e.to_string())? == saved,
+        "state differs after loading it into a new instance"
+    );
+    Ok(Outcome::Pass)
+}
+
+fn check_activate(bundle: &Bundle, plugin_id: &str) -> Checked {
+    let mut instance = instantiate(bundle, plugin_id)?;
+    for sample_rate in SAMPLE_RATES {
+        let cycle = |instance: &mut PluginInstance| -> Result<(), hosting::Error> {
+            instance.activate(sample_rate, 1, BLOCK_SIZE)?;
+            instance.start_processing()?;
+            instance.stop_processing()?;
+            instance.reset()?;
+            instance.deactivate()
+        };
+        cycle(&mut instance).map_err(|e| format!("sample rate {sample_rate}: {e}"))?;
+    }
+    Ok(Outcome::Pass)
+}
+
+fn check_process(bundle: &Bundle, plugin_id: &str) -> Checked {
+    let mut instance = instantiate(bundle, plugin_id)?;
+
+    let extensions = instance.get_extension();
+    let (audio_inputs, audio_outputs) = match extensions.audio_ports() {
+        Ok(ports) => {
+            let channels = |is_input| -> Result<Vec<u32>, String> {
+                (0..ports.count(is_input))
+                    .map(|i| ports.get(i, is_input).map(|port| port.channel_count))
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| e.to_string())
+            };
+            (channels(true)?, channels(false)?)
+        }
+        Err(_) => (Vec::new(), Vec::new()),
+    };
+    let note_inputs = extensions.note_ports().map_or(0, |ports| ports.count(true));
+    let param = match extensions.params() {
+        Ok(params) if params.count() > 0 => Some(params.get_info(0).map_err(|e| e.to_string())?),
+        _ => None,
+    };
+
+    instance.set_audio_ports(&audio_inputs, &audio_outputs);
+    instance
+        .activate(48000.0, 1, BLOCK_SIZE)
+        .map_err(|e| e.to_string())?;
+    instance.start_processing().map_err(|e| e.to_string())?;
+
+    let mut phase = 0.0f32;
+    for block in 0..BLOCKS_COUNT {
+        // Vary the block size, including blocks of a single frame.
+        let frames_count = match block % 3 {
+            0 => BLOCK_SIZE,
+            1 => 1,
+            _ => BLOCK_SIZE / 3,
+        };
+
+        for port in 0..instance.audio_inputs_count() {
+            for channel in 0..instance.audio_input(port).channel_count() {
+                let mut p = phase;
+                for sample in instance.audio_input_mut(port).channel_mut(channel) {
+                    *sample = p.sin();
+                    p += 0.05;
+                }
+            }
+        }
+        phase += 0.05 * frames_count as f32;
+
+        let in_events = instance.in_events();
+        if note_inputs > 0 {
+            let on = NoteBuilder::new(NoteKind::On).key(60).velocity(0.8);
+            let off = NoteBuilder::new(NoteKind::Off).key(60);
+            in_events
+                .try_push(on.time(0).event())
+                .and_then(|_| in_events.try_push(off.time(frames_count - 1).event()))
+                .map_err(|e| e.to_string())?;
+        }
+        if let Some(param) = &param {
+            let value = ParamValueBuilder::new()
+                .param_id(param.id)
+                .value(param.default_value)
+                .time(frames_count / 2);
+            in_events
+                .try_push(value.event())
+                .map_err(|e| e.to_string())?;
+        }
+
+        instance
+            .process(frames_count)
+            .map_err(|e| format!("block {block}: {e}"))?;
+
+        check_out_events(instance.out_events(), frames_count)
+            .map_err(|e| format!("block {block}: {e}"))?;
+        for port in 0..instance.audio_outputs_count() {
+            let output = instance.audio_output(port);
+            for channel in 0..output.channel_count() {
+                ensure!(
+                    output.channel(channel)[..frames_count as usize]
+                        .iter()
+                        .all(|sample| sample.is_finite()),
+                    "block {block}: non-finite sample in output port {port}, channel {channel}"
+                );
+            }
+        }
+    }
+
+    instance.deactivate().map_err(|e| e.to_string())?;
+    Ok(Outcome::Pass)
+}
+
+fn check_out_events(out_events: &EventQueue, frames_count: u32) -> Result<(), String> {
+    // The queue sorts the events, so check the order in which they were pushed.
+    if let Some(index) = out_events.unordered_output() {
+        return Err(format!("output events out of order at index {index}"));
+    }
+    for index in 0..out_events.len() {
+        let header = out_events.get(index).unwrap();
+        ensure!(
+            header.time() < frames_count.max(1),
+            "output event time out of block: {}",
+            header.time()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+fn cargo_clap(args: &[&str]) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_cargo-clap"));
+    cmd.current_dir(env!("CARGO_MANIFEST_DIR")).args(args);
+    cmd
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("cargo-clap-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn bundle_path(dir: &Path) -> PathBuf {
+    dir.join("gain.clap")
+}
+
+#[test]
+fn bundle_example() {
+    let out_dir = temp_dir("bundle");
+    let output = cargo_clap(&["clap", "bundle", "--example", "gain", "--out-dir"])
+        .arg(&out_dir)
+        .output()
+        .unwrap();
+
+    let stdout = stdout(&output);
+    assert!(bundle_path(&out_dir).exists());
+    assert!(stdout.contains("com.your-company.YourPlugin\n"), "{stdout}");
+    assert!(stdout.contains("features: fx, stereo, gain\n"), "{stdout}");
+
+    let output = cargo_clap(&["info"])
+        .arg(bundle_path(&out_dir))
+        .output()
+        .unwrap();
+    assert_eq!(self::stdout(&output), stdout);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn bundle_install() {
+    let home = temp_dir("install");
+    let user_home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+    let mut cmd = cargo_clap(&["bundle", "--example", "gain", "--install", "--out-dir"]);
+    cmd.arg(temp_dir("install-out")).env("HOME", &home);
+    // Keep the toolchain where it is, since cargo looks for it in HOME.
+    for (var, dir) in [("CARGO_HOME", ".cargo"), ("RUSTUP_HOME", ".rustup")] {
+        let path = env::var_os(var).map_or_else(|| user_home.join(dir), PathBuf::from);
+        cmd.env(var, path);
+    }
+
+    stdout(&cmd.output().unwrap());
+    assert!(bundle_path(&home.join(".clap")).exists());
+}
+
+#[test]
+fn no_cdylib() {
+    let output = cargo_clap(&["bundle"]).output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no cdylib built"));
+}
+
+#[test]
+fn unknown_command() {
+    let output = cargo_clap(&["frobnicate"]).output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown command: frobnicate"));
+}
@@ -0,0 +1,483 @@
+use std::{
+    io::{Read, Write},
+    ptr::null,
+    sync::{
+        Arc, LazyLock, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use clap_clap::{
+    Error,
+    events::{EventBuilder, EventQueue, EventRef, InputEvents, OutputEvents, ParamValue},
+    ext::{
+        Extensions,
+        audio_ports::{AudioPortFlags, AudioPortType, AudioPorts, StereoPorts},
+        log::Severity,
+        note_ports::{NoteDialect, NotePortInfo, NotePorts},
+        params::{ParamInfo, Params},
+        state::State,
+    },
+    ffi::{CLAP_VERSION, clap_plugin_descriptor},
+    host::Host,
+    hosting::{self, Bundle, HostHandler},
+    id::ClapId,
+    plugin::{AudioThread, Plugin},
+    process::{Process, Status, Status::Continue},
+    render::offline::{Audio, Renderer},
+    stream::{IStream, OStream},
+};
+
+#[derive(Default)]
+struct Amp {
+    gain: Arc<AtomicU64>,
+}
+
+impl Amp {
+    fn gain(&self) -> f64 {
+        f64::from_bits(self.gain.load(Ordering::Acquire))
+    }
+
+    fn set_gain(&self, value: f64) {
+        self.gain.store(value.to_bits(), Ordering::Release);
+    }
+
+    fn apply(&self, in_events: &InputEvents) {
+        for event in in_events {
+            if let EventRef::ParamValue(param) = event {
+                self.set_gain(param.value());
+            }
+        }
+    }
+}
+
+impl Extensions<Self> for Amp {
+    fn audio_ports() -> Option<impl AudioPorts<Self>> {
+        Some(StereoPorts::<1, 1>)
+    }
+
+    fn note_ports() -> Option<impl NotePorts<Self>> {
+        Some(AmpNotePorts)
+    }
+
+    fn params() -> Option<impl Params<Self>> {
+        Some(AmpParams)
+    }
+
+    fn state() -> Option<impl State<Self>> {
+        Some(AmpState)
+    }
+}
+
+impl Plugin for Amp {
+    type AudioThread = AmpThread;
+    const ID: &'static str = "clap.plugin.amp";
+    const NAME: &'static str = "Amp";
+    const VENDOR: &'static str = "clap-clap";
+    const VERSION: &'static str = "0.1.0";
+
+    fn features() -> impl Iterator<Item = &'static str> {
+        ["audio-effect", "stereo"].into_iter()
+    }
+
+    fn init(&mut self, host: Arc<Host>) -> Result<(), Error> {
+        host.get_extension().log()?.info("init")?;
+        host.request_callback();
+        Ok(())
+    }
+
+    fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<AmpThread, Error> {
+        Ok(AmpThread {
+            gain: self.gain.clone(),
+        })
+    }
+}
+
+struct AmpThread {
+    gain: Arc<AtomicU64>,
+}
+
+impl AudioThread<Amp> for AmpThread {
+    fn process(&mut self, process: &mut Process) -> Result<Status, Error> {
+        for event in &process.in_events() {
+            if let EventRef::ParamValue(param) = event {
+                self.gain.store(param.value().to_bits(), Ordering::Release);
+            }
+        }
+        let gain = f64::from_bits(self.gain.load(Ordering::Acquire)) as f32;
+
+        let n = process.frames_count() as usize;
+        for ch in 0..2 {
+            let input = process.audio_inputs(0).data32(ch)[..n].to_vec();
+            let mut output = process.audio_outputs(0);
+            for (o, i) in output.data32(ch).iter_mut().zip(input) {
+                *o = gain * i;
+            }
+        }
+        Ok(Continue)
+    }
+}
+
+struct AmpNotePorts;
+
+impl NotePorts<Amp> for AmpNotePorts {
+    fn count(_: &Amp, is_input: bool) -> u32 {
+        if is_input { 1 } else { 0 }
+    }
+
+    fn get(_: &Amp, index: u32, is_input: bool) -> Option<NotePortInfo> {
+        (is_input && index == 0).then(|| NotePortInfo {
+            id: ClapId::from(3),
+            supported_dialects: NoteDialect::Clap.set(NoteDialect::Midi as u32),
+            preferred_dialect: NoteDialect::Clap as u32,
+            name: "Notes".to_string(),
+        })
+    }
+}
+
+struct AmpParams;
+
+impl Params<Amp> for AmpParams {
+    fn count(_: &Amp) -> u32 {
+        1
+    }
+
+    fn get_info(_: &Amp, param_index: u32) -> Option<ParamInfo> {
+        (param_index == 0).then(|| ParamInfo {
+            id: ClapId::from(7),
+            flags: 0,
+            name: "Gain".to_string(),
+            module: "amp".to_string(),
+            min_value: 0.0,
+            max_value: 2.0,
+            default_value: 0.0,
+        })
+    }
+
+    fn get_value(plugin: &Amp, param_id: ClapId) -> Option<f64> {
+        (param_id == ClapId::from(7)).then(|| plugin.gain())
+    }
+
+    fn value_to_text(_: &Amp, _: ClapId, value: f64, mut out_buf: &mut [u8]) -> Result<(), Error> {
+        Ok(write!(out_buf, "{value:.2} x")?)
+    }
+
+    fn text_to_value(_: &Amp, _: ClapId, text: &str) -> Result<f64, Error> {
+        Ok(text.trim_end_matches(" x").parse()?)
+    }
+
+    fn flush_inactive(plugin: &Amp, in_events: &InputEvents, _: &mut OutputEvents) {
+        plugin.apply(in_events);
+    }
+
+    fn flush(_: &AmpThread, _: &InputEvents, _: &mut OutputEvents) {}
+}
+
+struct AmpState;
+
+impl State<Amp> for AmpState {
+    fn save(plugin: &Amp, stream: &mut OStream) -> Result<(), Error> {
+        Ok(stream.write_all(&plugin.gain().to_le_bytes())?)
+    }
+
+    fn load(plugin: &Amp, stream: &mut IStream) -> Result<(), Error> {
+        let mut buf = [0; 8];
+        stream.read_exact(&mut buf)?;
+        plugin.set_gain(f64::from_le_bytes(buf));
+        Ok(())
+    }
+}
+
+clap_clap::entry!(Amp);
+
+static BUNDLE: LazyLock<Bundle> = LazyLock::new(|| {
+    // SAFETY: The entry is initialized only here.
+    unsafe { Bundle::from_entry(&_clap_entry::clap_entry, "amp.clap") }.unwrap()
+});
+
+#[derive(Default)]
+struct Recorder {
+    callbacks: Mutex<Vec<String>>,
+}
+
+impl HostHandler for Recorder {
+    fn name(&self) -> &str {
+        "recorder"
+    }
+
+    fn request_callback(&self) {
+        self.callbacks.lock().unwrap().push("callback".to_string());
+    }
+
+    fn log(&self, severity: Severity, msg: &str) {
+        self.callbacks
+            .lock()
+            .unwrap()
+            .push(format!("{severity:?}: {msg}"));
+    }
+}
+
+fn instance() -> hosting::PluginInstance {
+    BUNDLE.instantiate("clap.plugin.amp", Arc::new(())).unwrap()
+}
+
+#[test]
+fn bundle_descriptors() {
+    assert_eq!(BUNDLE.path(), std::path::Path::new("amp.clap"));
+    assert_eq!(BUNDLE.plugin_count(), 1);
+    assert!(BUNDLE.descriptor(1).is_none());
+
+    let descriptors = BUNDLE.descriptors();
+    assert_eq!(descriptors.len(), 1);
+    assert_eq!(descriptors[0].id, "clap.plugin.amp");
+    assert_eq!(descriptors[0].name, "Amp");
+    assert_eq!(descriptors[0].vendor, "clap-clap");
+    assert_eq!(descriptors[0].version, "0.1.0");
+    assert_eq!(descriptors[0].features, ["audio-effect", "stereo"]);
+    assert_eq!(instance().descriptor(), descriptors[0]);
+}
+
+#[test]
+fn descriptor_invalid_utf8() {
+    let features = [c"audio-effect".as_ptr(), c"st\xffereo".as_ptr(), null()];
+    let desc = clap_plugin_descriptor {
+        clap_version: CLAP_VERSION,
+        id: c"clap.plugin.amp".as_ptr(),
+        name: c"\xc3(mp".as_ptr(),
+        vendor: null(),
+        url: null(),
+        manual_url: null(),
+        support_url: null(),
+        version: c"0.1.0".as_ptr(),
+        description: c"\u{fffd}".as_ptr(),
+        features: features.as_ptr(),
+    };
+    // SAFETY: All strings are null or valid C strings.
+    let descriptor = unsafe { hosting::Descriptor::from_unchecked(&desc) };
+
+    assert_eq!(descriptor.name, "\u{fffd}(mp");
+    assert_eq!(descriptor.vendor, "");
+    // A valid replacement character is not an encoding error.
+    assert_eq!(descriptor.description, "\u{fffd}");
+    assert_eq!(descriptor.invalid_utf8, ["name", "features"]);
+}
+
+#[test]
+fn instantiate_unknown_plugin() {
+    let err = BUNDLE
+        .instantiate("clap.plugin.unknown", Arc::new(()))
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        err,
+        hosting::Error::PluginNotFound("clap.plugin.unknown".to_string())
+    );
+}
+
+#[test]
+fn host_handler() {
+    let recorder = Arc::new(Recorder::default());
+    let _instance = BUNDLE
+        .instantiate("clap.plugin.amp", recorder.clone())
+        .unwrap();
+
+    assert_eq!(
+        *recorder.callbacks.lock().unwrap(),
+        ["Info: init", "callback"]
+    );
+}
+
+#[test]
+fn load_missing_library() {
+    let err = Bundle::load("/nonexistent/plugin.clap").err().unwrap();
+
+    assert!(matches!(err, hosting::Error::Library(_)));
+}
+
+#[test]
+fn lifecycle_out_of_order() {
+    let mut instance = instance();
+
+    assert_eq!(instance.start_processing(), Err(hosting::Error::NotActive));
+    assert_eq!(instance.process(0), Err(hosting::Error::NotProcessing));
+    assert_eq!(instance.deactivate(), Err(hosting::Error::NotActive));
+
+    instance.activate(48000.0, 1, 16).unwrap();
+    assert_eq!(
+        instance.activate(48000.0, 1, 16),
+        Err(hosting::Error::AlreadyActive)
+    );
+    instance.start_processing().unwrap();
+    assert_eq!(
+        instance.start_processing(),
+        Err(hosting::Error::AlreadyProcessing)
+    );
+
+    instance.deactivate().unwrap();
+    assert!(!instance.is_active());
+    assert!(!instance.is_processing());
+}
+
+#[test]
+fn process() {
+    let mut instance = instance();
+    instance.set_audio_ports(&[2], &[2]);
+    instance.activate(48000.0, 1, 16).unwrap();
+    instance.start_processing().unwrap();
+
+    instance.audio_input_mut(0).channel_mut(0).fill(1.0);
+    instance.audio_input_mut(0).channel_mut(1).fill(-0.5);
+    let event = ParamValue::build().param_id(ClapId::from(7)).value(2.0);
+    instance.in_events().try_push(event.event()).unwrap();
+
+    assert_eq!(instance.process(8), Ok(Continue));
+    assert!(instance.in_events().is_empty());
+    assert_eq!(instance.audio_output(0).channel(0)[..8], [2.0; 8]);
+    assert_eq!(instance.audio_output(0).channel(1)[..8], [-1.0; 8]);
+    assert_eq!(instance.audio_output(0).channel(0)[8..], [0.0; 8]);
+}
+
+#[test]
+fn audio_ports() {
+    let instance = instance();
+    let audio_ports = instance.get_extension().audio_ports().unwrap();
+
+    assert_eq!(audio_ports.count(true), 1);
+    assert_eq!(audio_ports.count(false), 1);
+    let port = audio_ports.get(0, false).unwrap();
+    assert_eq!(port.channel_count, 2);
+    assert_eq!(port.port_type, Some(AudioPortType::Stereo));
+    assert!(AudioPortFlags::IsMain.is_set(port.flags));
+    assert_eq!(
+        audio_ports.get(1, false),
+        Err(hosting::Error::Failed("get"))
+    );
+}
+
+#[test]
+fn note_ports() {
+    let instance = instance();
+    let note_ports = instance.get_extension().note_ports().unwrap();
+
+    assert_eq!(note_ports.count(true), 1);
+    assert_eq!(note_ports.count(false), 0);
+    let port = note_ports.get(0, true).unwrap();
+    assert_eq!(port.id, ClapId::from(3));
+    assert_eq!(port.name, "Notes");
+    assert!(NoteDialect::Midi.is_set(port.supported_dialects));
+    assert_eq!(port.preferred_dialect, NoteDialect::Clap as u32);
+    assert!(note_ports.get(0, false).is_err());
+}
+
+#[test]
+fn params() {
+    let instance = instance();
+    let params = instance.get_extension().params().unwrap();
+
+    assert_eq!(params.count(), 1);
+    let info = params.get_info(0).unwrap();
+    assert_eq!(info.id, ClapId::from(7));
+    assert_eq!(info.name, "Gain");
+    assert_eq!(info.module, "amp");
+    assert_eq!(info.max_value, 2.0);
+    assert_eq!(
+        params.get_info(1).err(),
+        Some(hosting::Error::Failed("get_info"))
+    );
+
+    assert_eq!(params.get_value(ClapId::from(7)), Ok(0.0));
+    assert!(params.get_value(ClapId::from(8)).is_err());
+    assert_eq!(
+        params.value_to_text(ClapId::from(7), 1.5).unwrap(),
+        "1.50 x"
+    );
+    assert_eq!(params.text_to_value(ClapId::from(7), "0.25 x"), Ok(0.25));
+    assert!(params.text_to_value(ClapId::from(7), "loud").is_err());
+}
+
+#[test]
+fn params_flush() {
+    let instance = instance();
+    let params = instance.get_extension().params().unwrap();
+
+    let mut in_events = EventQueue::new();
+    let event = ParamValue::build().param_id(ClapId::from(7)).value(1.25);
+    in_events.try_push(event.event()).unwrap();
+    params.flush(&in_events, &mut EventQueue::new());
+
+    assert_eq!(params.get_value(ClapId::from(7)), Ok(1.25));
+}
+
+#[test]
+fn state() {
+    let source = instance();
+    let params = source.get_extension().params().unwrap();
+    let mut in_events = EventQueue::new();
+    let event = ParamValue::build().param_id(ClapId::from(7)).value(0.75);
+    in_events.try_push(event.event()).unwrap();
+    params.flush(&in_events, &mut EventQueue::new());
+
+    let saved = source.get_extension().state().unwrap().save().unwrap();
+    assert_eq!(saved, 0.75f64.to_le_bytes());
+
+    let target = instance();
+    let state = target.get_extension().state().unwrap();
+    state.load(&saved).unwrap();
+    assert_eq!(
+        target
+            .get_extension()
+            .params()
+            .unwrap()
+            .get_value(ClapId::from(7)),
+        Ok(0.75)
+    );
+    assert_eq!(state.load(&saved[..4]), Err(hosting::Error::Failed("load")));
+}
+
+#[test]
+fn render_bundle() {
+    let input = Audio::from_channels(48000, vec![vec![1.0, 2.0, 3.0], vec![-1.0, -2.0, -3.0]]);
+    let mut timeline = EventQueue::new();
+    let event = ParamValue::build().param_id(ClapId::from(0)).value(0.5);
+    timeline.try_push(event.event()).unwrap();
+
+    let output = Renderer::new(48000, 2)
+        .events(timeline)
+        .render_bundle(&BUNDLE, "clap.plugin.amp", &input)
+        .unwrap();
+
+    assert_eq!(output.channel_count(), 2);
+    assert_eq!(output.channel(0), [0.5, 1.0, 1.5]);
+    assert_eq!(output.channel(1), [-0.5, -1.0, -1.5]);
+}
+
+/// Load the `gain` example, if it has been built, e.g. by `cargo test`.
+#[test]
+fn load_example_bundle() {
+    let exe = std::env::current_exe().unwrap();
+    let path = exe.parent().unwrap().parent().unwrap().join(format!(
+        "examples/{}gain{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    ));
+    if !path.exists() {
+        return;
+    }
+
+    let bundle = Bundle::load(&path).unwrap();
+    let descriptors = bundle.descriptors();
+    assert_eq!(descriptors.len(), 1);
+    assert_eq!(descriptors[0].features, ["fx", "stereo", "gain"]);
+
+    let mut instance = bundle
+        .instantiate(&descriptors[0].id, Arc::new(()))
+        .unwrap();
+    let params = instance.get_extension().params().unwrap();
+    assert_eq!(params.get_info(0).unwrap().name, "Gain");
+
+    instance.set_audio_ports(&[2], &[2]);
+    instance.activate(48000.0, 1, 64).unwrap();
+    instance.start_processing().unwrap();
+    assert_eq!(instance.process(64), Ok(Continue));
+}
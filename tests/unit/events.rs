@@ -1,3 +1,5 @@
+mod queue;
+
 use clap_clap::{
     events::Header,
     ffi::{CLAP_EVENT_MIDI, clap_event_header, clap_event_midi},
@@ -559,6 +561,108 @@ mod param_mod {
     }
 }
 
+mod param_gesture {
+    use clap_clap::{
+        events,
+        events::{EventBuilder, GestureKind, Header, ParamGesture},
+        ffi::{
+            CLAP_EVENT_NOTE_CHOKE, CLAP_EVENT_PARAM_GESTURE_BEGIN, CLAP_EVENT_PARAM_GESTURE_END,
+            clap_event_header, clap_event_param_gesture,
+        },
+        id::ClapId,
+    };
+
+    #[test]
+    fn try_01() {
+        let event = clap_event_param_gesture {
+            header: clap_event_header {
+                size: 33,
+                time: 0,
+                space_id: 0,
+                r#type: CLAP_EVENT_PARAM_GESTURE_BEGIN as u16,
+                flags: 0,
+            },
+            param_id: 0,
+        };
+
+        let header = unsafe { Header::new_unchecked(&event.header) };
+        assert_eq!(Err(events::Error::PayloadSize(33)), header.param_gesture())
+    }
+
+    #[test]
+    fn try_02() {
+        let event = clap_event_param_gesture {
+            header: clap_event_header {
+                size: size_of::<clap_event_param_gesture>() as u32,
+                time: 0,
+                space_id: 0,
+                r#type: CLAP_EVENT_NOTE_CHOKE as u16,
+                flags: 0,
+            },
+            param_id: 0,
+        };
+
+        let header = unsafe { Header::new_unchecked(&event.header) };
+        assert_eq!(
+            Err(events::Error::OtherType(CLAP_EVENT_NOTE_CHOKE as u16)),
+            header.param_gesture()
+        )
+    }
+
+    #[test]
+    fn try_03() {
+        for (r#type, kind) in [
+            (CLAP_EVENT_PARAM_GESTURE_BEGIN, GestureKind::Begin),
+            (CLAP_EVENT_PARAM_GESTURE_END, GestureKind::End),
+        ] {
+            let event = clap_event_param_gesture {
+                header: clap_event_header {
+                    size: size_of::<clap_event_param_gesture>() as u32,
+                    time: 0,
+                    space_id: 0,
+                    r#type: r#type as u16,
+                    flags: 0,
+                },
+                param_id: 87,
+            };
+
+            let header = unsafe { Header::new_unchecked(&event.header) };
+            let _ = header.param_value().unwrap_err();
+            let event = header.param_gesture().unwrap();
+            assert_eq!(event.kind, kind);
+            assert_eq!(event.param_id(), ClapId::from(87));
+        }
+    }
+
+    #[test]
+    fn build() {
+        let gesture1 = ParamGesture::build(GestureKind::Begin).param_id(ClapId::from(1));
+        let gesture2 = gesture1.kind(GestureKind::End);
+
+        let event1 = gesture1.event();
+        let event2 = gesture2.event();
+
+        assert_eq!(event1.param_id(), ClapId::from(1));
+        assert_eq!(event2.param_id(), ClapId::from(1));
+
+        assert_eq!(event1.kind, GestureKind::Begin);
+        assert_eq!(event2.kind, GestureKind::End);
+    }
+
+    #[test]
+    fn update() {
+        let gesture1 = ParamGesture::build(GestureKind::Begin).param_id(ClapId::from(1));
+        let event1 = gesture1.event();
+
+        let gesture2 = ParamGesture::update(&event1).param_id(ClapId::from(3));
+        let event2 = gesture2.event();
+
+        assert_eq!(event1.param_id(), ClapId::from(1));
+        assert_eq!(event2.param_id(), ClapId::from(3));
+        assert_eq!(event2.kind, GestureKind::Begin);
+    }
+}
+
 mod transport {
     use clap_clap::{
         events,
@@ -891,6 +995,267 @@ mod midi2 {
     }
 }
 
+mod midi_sysex {
+    use std::ptr::null;
+
+    use clap_clap::{
+        events,
+        events::{EventBuilder, Header, MidiSysex},
+        ffi::{
+            CLAP_EVENT_MIDI_SYSEX, CLAP_EVENT_NOTE_CHOKE, clap_event_header, clap_event_midi_sysex,
+        },
+    };
+
+    #[test]
+    fn try_midi_sysex_01() {
+        let event = clap_event_midi_sysex {
+            header: clap_event_header {
+                size: 3,
+                time: 0,
+                space_id: 0,
+                r#type: CLAP_EVENT_MIDI_SYSEX as u16,
+                flags: 0,
+            },
+            port_index: 0,
+            buffer: null(),
+            size: 0,
+        };
+
+        let header = unsafe { Header::new_unchecked(&event.header) };
+        assert_eq!(Err(events::Error::PayloadSize(3)), header.midi_sysex())
+    }
+
+    #[test]
+    fn try_midi_sysex_02() {
+        let event = clap_event_midi_sysex {
+            header: clap_event_header {
+                size: size_of::<clap_event_midi_sysex>() as u32,
+                time: 0,
+                space_id: 0,
+                r#type: CLAP_EVENT_NOTE_CHOKE as u16,
+                flags: 0,
+            },
+            port_index: 0,
+            buffer: null(),
+            size: 0,
+        };
+
+        let header = unsafe { Header::new_unchecked(&event.header) };
+        assert_eq!(
+            Err(events::Error::OtherType(CLAP_EVENT_NOTE_CHOKE as u16)),
+            header.midi_sysex()
+        )
+    }
+
+    #[test]
+    fn try_midi_sysex_03() {
+        let payload = [0xf0, 1, 2, 3, 0xf7];
+        let event = clap_event_midi_sysex {
+            header: clap_event_header {
+                size: size_of::<clap_event_midi_sysex>() as u32,
+                time: 0,
+                space_id: 0,
+                r#type: CLAP_EVENT_MIDI_SYSEX as u16,
+                flags: 0,
+            },
+            port_index: 87,
+            buffer: payload.as_ptr(),
+            size: payload.len() as u32,
+        };
+
+        let header = unsafe { Header::new_unchecked(&event.header) };
+        let sysex = header.midi_sysex().unwrap();
+        assert_eq!(sysex.buffer(), &payload);
+        assert_eq!(sysex.port_index(), 87);
+    }
+
+    #[test]
+    fn try_midi_sysex_null_buffer() {
+        let event = clap_event_midi_sysex {
+            header: clap_event_header {
+                size: size_of::<clap_event_midi_sysex>() as u32,
+                time: 0,
+                space_id: 0,
+                r#type: CLAP_EVENT_MIDI_SYSEX as u16,
+                flags: 0,
+            },
+            port_index: 0,
+            buffer: null(),
+            size: 12,
+        };
+
+        let header = unsafe { Header::new_unchecked(&event.header) };
+        assert!(header.midi_sysex().unwrap().buffer().is_empty());
+    }
+
+    #[test]
+    fn build() {
+        let sysex1 = MidiSysex::build().port_index(1).buffer([0xf0, 5, 0xf7]);
+        let sysex2 = sysex1.clone().port_index(3);
+
+        let event1 = sysex1.event();
+        let event2 = sysex2.event();
+
+        assert_eq!(event1.buffer(), &[0xf0, 5, 0xf7]);
+        assert_eq!(event2.buffer(), &[0xf0, 5, 0xf7]);
+        assert_ne!(event1.buffer().as_ptr(), event2.buffer().as_ptr());
+
+        assert_eq!(event1.port_index(), 1);
+        assert_eq!(event2.port_index(), 3);
+    }
+
+    #[test]
+    fn build_empty() {
+        let sysex = MidiSysex::build();
+
+        assert!(sysex.event().buffer().is_empty());
+    }
+
+    #[test]
+    fn update() {
+        let sysex1 = MidiSysex::build().port_index(1).buffer(vec![0xf0, 5, 0xf7]);
+        let event1 = sysex1.event();
+
+        let sysex2 = MidiSysex::update(&event1).port_index(3);
+        drop(sysex1);
+        let event2 = sysex2.event();
+
+        assert_eq!(event2.buffer(), &[0xf0, 5, 0xf7]);
+        assert_eq!(event2.port_index(), 3);
+    }
+}
+
+mod custom {
+    use clap_clap::{
+        events,
+        events::{Custom, CustomPayload, Event, EventBuilder, EventSpaceId, Midi},
+    };
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    #[repr(C)]
+    struct Cue {
+        number: u32,
+        gain: f64,
+    }
+
+    unsafe impl CustomPayload for Cue {
+        const TYPE: u16 = 3;
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    #[repr(C)]
+    struct Mark([u8; 5]);
+
+    unsafe impl CustomPayload for Mark {
+        const TYPE: u16 = 3;
+    }
+
+    fn space() -> EventSpaceId {
+        EventSpaceId::try_from(1234).unwrap()
+    }
+
+    #[test]
+    fn space_id() {
+        assert_eq!(EventSpaceId::try_from(0), Ok(EventSpaceId::CORE));
+        assert_eq!(
+            EventSpaceId::try_from(u16::MAX),
+            Err(events::Error::InvalidSpaceId)
+        );
+    }
+
+    #[test]
+    fn build() {
+        let cue = Custom::build(
+            space(),
+            Cue {
+                number: 1,
+                gain: 0.5,
+            },
+        )
+        .time(11);
+        let event = cue.event();
+
+        assert_eq!(event.header().space_id(), 1234);
+        assert_eq!(event.header().r#type(), 3);
+        assert_eq!(event.header().time(), 11);
+        assert_eq!(
+            event.payload(),
+            &Cue {
+                number: 1,
+                gain: 0.5
+            }
+        );
+    }
+
+    #[test]
+    fn update() {
+        let cue1 = Custom::build(
+            space(),
+            Cue {
+                number: 1,
+                gain: 0.5,
+            },
+        );
+        let event1 = cue1.event();
+
+        let cue2 = event1.update().payload(Cue {
+            number: 2,
+            gain: 1.0,
+        });
+        let event2 = cue2.event();
+
+        assert_eq!(event1.payload().number, 1);
+        assert_eq!(event2.payload().number, 2);
+        assert_eq!(event2.header().space_id(), 1234);
+    }
+
+    #[test]
+    fn try_custom() {
+        let cue = Custom::build(
+            space(),
+            Cue {
+                number: 1,
+                gain: 0.5,
+            },
+        );
+        let event = cue.event();
+        let header = event.header();
+
+        assert_eq!(header.custom::<Cue>(space()).unwrap(), event);
+        assert_eq!(
+            header.custom::<Cue>(EventSpaceId::try_from(7).unwrap()),
+            Err(events::Error::OtherSpace(1234))
+        );
+        assert_eq!(
+            header.custom::<Mark>(space()),
+            Err(events::Error::PayloadSize(header.size()))
+        );
+        let _ = header.midi().unwrap_err();
+    }
+
+    #[test]
+    fn try_custom_core_space() {
+        let midi = Midi::build();
+        let event = midi.event();
+        assert_eq!(
+            event.header().custom::<Mark>(EventSpaceId::CORE),
+            Err(events::Error::OtherSpace(0))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "custom event in core space")]
+    fn build_core_space() {
+        let _ = Custom::build(EventSpaceId::CORE, Mark([0; 5]));
+    }
+
+    #[test]
+    #[should_panic(expected = "custom event in core space")]
+    fn set_core_space() {
+        let _ = Custom::build(space(), Mark([0; 5])).space_id(0);
+    }
+}
+
 pub mod input_events {
     use std::{
         marker::PhantomPinned,
@@ -900,11 +1265,14 @@ pub mod input_events {
 
     use clap_clap::{
         events::{
-            Event, EventBuilder, InputEvents, Midi, Midi2, Midi2Builder, MidiBuilder, ParamMod,
-            ParamModBuilder, ParamValue, ParamValueBuilder, Transport, TransportBuilder,
+            Event, EventBuilder, EventRef, GestureKind, Header, InputEvents, Midi, Midi2,
+            Midi2Builder, MidiBuilder, MidiSysex, Note, NoteKind, ParamGesture,
+            ParamGestureBuilder, ParamMod, ParamModBuilder, ParamValue, ParamValueBuilder,
+            Transport, TransportBuilder,
         },
         ffi::{
-            CLAP_CORE_EVENT_SPACE_ID, CLAP_EVENT_MIDI, CLAP_EVENT_MIDI2, CLAP_EVENT_PARAM_VALUE,
+            CLAP_CORE_EVENT_SPACE_ID, CLAP_EVENT_MIDI, CLAP_EVENT_MIDI2,
+            CLAP_EVENT_PARAM_GESTURE_BEGIN, CLAP_EVENT_PARAM_GESTURE_END, CLAP_EVENT_PARAM_VALUE,
             clap_event_header, clap_event_midi, clap_event_param_value, clap_input_events,
             clap_process,
         },
@@ -1050,6 +1418,14 @@ pub mod input_events {
                 if type_id == CLAP_EVENT_MIDI2 {
                     assert_eq!(known.header().midi2().unwrap(), ev.midi2().unwrap());
                 }
+                if type_id == CLAP_EVENT_PARAM_GESTURE_BEGIN
+                    || type_id == CLAP_EVENT_PARAM_GESTURE_END
+                {
+                    assert_eq!(
+                        known.header().param_gesture().unwrap(),
+                        ev.param_gesture().unwrap()
+                    );
+                }
                 if type_id == CLAP_EVENT_PARAM_VALUE {
                     assert_eq!(
                         known.header().param_value().unwrap(),
@@ -1095,6 +1471,7 @@ pub mod input_events {
     pub enum KnownEvent {
         Midi(MidiBuilder),
         Midi2(Midi2Builder),
+        ParamGesture(ParamGestureBuilder),
         ParamMod(ParamModBuilder),
         ParamValue(ParamValueBuilder),
         Transport(TransportBuilder),
@@ -1106,6 +1483,7 @@ pub mod input_events {
             match known {
                 KnownEvent::Midi(ev) => bed.as_mut().push_event(ev.event()),
                 KnownEvent::Midi2(ev) => bed.as_mut().push_event(ev.event()),
+                KnownEvent::ParamGesture(ev) => bed.as_mut().push_event(ev.event()),
                 KnownEvent::ParamMod(ev) => bed.as_mut().push_event(ev.event()),
                 KnownEvent::ParamValue(ev) => bed.as_mut().push_event(ev.event()),
                 KnownEvent::Transport(ev) => bed.as_mut().push_event(ev.event()),
@@ -1127,7 +1505,9 @@ pub mod input_events {
         let events = [
             KnownEvent::Midi(Midi::build().port_index(1)),
             KnownEvent::Midi2(Midi2::build().port_index(2)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::Begin).param_id(3.into())),
             KnownEvent::ParamMod(ParamMod::build().amount(12.345)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::End).param_id(3.into())),
             KnownEvent::ParamValue(ParamValue::build().value(12.34)),
             KnownEvent::Transport(Transport::build().bar_number(11)),
         ];
@@ -1139,7 +1519,9 @@ pub mod input_events {
         let events = [
             KnownEvent::Midi(Midi::build().port_index(1)),
             KnownEvent::Midi2(Midi2::build().port_index(2)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::Begin).param_id(3.into())),
             KnownEvent::ParamMod(ParamMod::build().amount(12.345)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::End).param_id(3.into())),
             KnownEvent::ParamValue(ParamValue::build().value(12.34)),
             KnownEvent::Transport(Transport::build().bar_number(11)),
         ];
@@ -1150,6 +1532,106 @@ pub mod input_events {
         check_input_events(&events);
     }
 
+    #[test]
+    fn iter_events_01() {
+        let bed = TestBed::new();
+        let input_events = bed.input_events();
+        assert_eq!(input_events.iter().len(), 0);
+        assert!(input_events.iter().next().is_none());
+    }
+
+    #[test]
+    fn iter_events_02() {
+        let note_on = Note::build(NoteKind::On).key(60);
+        let note_end = Note::build(NoteKind::End).key(61);
+        let gesture = ParamGesture::build(GestureKind::End).param_id(3.into());
+        let param_value = ParamValue::build().value(0.5);
+        let midi = Midi::build().data([1, 2, 3]);
+        let sysex = MidiSysex::build().buffer([0xf0, 0xf7]);
+
+        let mut bed = TestBed::new();
+        bed.as_mut().push_event(note_on.event());
+        bed.as_mut().push_event(note_end.event());
+        bed.as_mut().push_event(gesture.event());
+        bed.as_mut().push_event(param_value.event());
+        bed.as_mut().push_event(midi.event());
+        bed.as_mut().push_event(sysex.event());
+        let input_events = bed.input_events();
+
+        let mut iter = input_events.iter();
+        assert_eq!(iter.len(), 6);
+
+        let Some(EventRef::NoteOn(note)) = iter.next() else {
+            panic!()
+        };
+        assert_eq!(note.key(), 60);
+        let Some(EventRef::NoteEnd(note)) = iter.next() else {
+            panic!()
+        };
+        assert_eq!(note.key(), 61);
+        assert_eq!(
+            iter.next(),
+            Some(EventRef::ParamGestureEnd(gesture.event()))
+        );
+        assert_eq!(iter.next(), Some(EventRef::ParamValue(param_value.event())));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some(EventRef::Midi(midi.event())));
+        let Some(EventRef::MidiSysex(retrieved)) = iter.next() else {
+            panic!()
+        };
+        assert_eq!(retrieved.buffer(), &[0xf0, 0xf7]);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn iter_events_unknown() {
+        let other_space = Midi::build().space_id(CLAP_CORE_EVENT_SPACE_ID + 1);
+        let param_value = clap_event_param_value {
+            header: clap_event_header {
+                size: 3,
+                time: 0,
+                space_id: CLAP_CORE_EVENT_SPACE_ID,
+                r#type: CLAP_EVENT_PARAM_VALUE as u16,
+                flags: 0,
+            },
+            param_id: 0,
+            cookie: null_mut(),
+            note_id: 0,
+            port_index: 0,
+            channel: 0,
+            key: 0,
+            value: 0.0,
+        };
+        let bad_size = unsafe { Header::new_unchecked(&param_value.header) };
+
+        let mut bed = TestBed::new();
+        bed.as_mut().push_event(other_space.event());
+        bed.as_mut().push_event(Raw(bad_size));
+        let input_events = bed.input_events();
+
+        let events: Vec<_> = (&input_events).into_iter().collect();
+        assert_eq!(events.len(), 2);
+
+        let EventRef::Unknown(header) = events[0] else {
+            panic!()
+        };
+        assert_eq!(header.space_id(), CLAP_CORE_EVENT_SPACE_ID + 1);
+        assert_eq!(events[0].header().r#type(), CLAP_EVENT_MIDI as u16);
+
+        let EventRef::Unknown(header) = events[1] else {
+            panic!()
+        };
+        assert_eq!(header.size(), 3);
+    }
+
+    struct Raw<'a>(&'a Header);
+
+    impl Event for Raw<'_> {
+        fn header(&self) -> &Header {
+            self.0
+        }
+    }
+
     #[test]
     fn process_input_events() {
         let event = Midi2::build().port_index(2);
@@ -1187,13 +1669,15 @@ mod output_events {
 
     use clap_clap::{
         events::{
-            EventBuilder, Header, Midi, Midi2, OutputEvents, ParamMod, ParamValue, Transport,
+            EventBuilder, GestureKind, Header, Midi, Midi2, MidiSysex, OutputEvents, ParamGesture,
+            ParamMod, ParamValue, Transport,
         },
         ffi::{
-            CLAP_EVENT_MIDI, CLAP_EVENT_MIDI2, CLAP_EVENT_PARAM_MOD, CLAP_EVENT_PARAM_VALUE,
+            CLAP_EVENT_MIDI, CLAP_EVENT_MIDI2, CLAP_EVENT_PARAM_GESTURE_BEGIN,
+            CLAP_EVENT_PARAM_GESTURE_END, CLAP_EVENT_PARAM_MOD, CLAP_EVENT_PARAM_VALUE,
             CLAP_EVENT_TRANSPORT, clap_event_header, clap_event_midi, clap_event_midi2,
-            clap_event_param_mod, clap_event_param_value, clap_event_transport, clap_output_events,
-            clap_process,
+            clap_event_param_gesture, clap_event_param_mod, clap_event_param_value,
+            clap_event_transport, clap_output_events, clap_process,
         },
         prelude::Process,
     };
@@ -1205,6 +1689,7 @@ mod output_events {
     struct TestBed {
         clap_output_events: clap_output_events,
         pushed: Vec<Box<[u8]>>,
+        sysex_payloads: Vec<Vec<u8>>,
         capacity: usize,
     }
 
@@ -1221,6 +1706,9 @@ mod output_events {
                     let header = unsafe { Header::new_unchecked(event) };
                     let bytes = header.to_bytes().to_owned().into_boxed_slice();
 
+                    if let Ok(sysex) = header.midi_sysex() {
+                        test_bed.sysex_payloads.push(sysex.buffer().to_vec());
+                    }
                     test_bed.pushed.push(bytes);
                     true
                 } else {
@@ -1234,6 +1722,7 @@ mod output_events {
                     try_push: Some(try_push),
                 },
                 pushed: vec![],
+                sysex_payloads: vec![],
                 capacity,
             });
 
@@ -1260,6 +1749,11 @@ mod output_events {
                     unsafe { ptr::read_unaligned(self.pushed[index].as_ptr() as *const _) };
                 return Some(KnownEvent::ParamValue(event.into()));
             }
+            if x == CLAP_EVENT_PARAM_GESTURE_BEGIN || x == CLAP_EVENT_PARAM_GESTURE_END {
+                let event: clap_event_param_gesture =
+                    unsafe { ptr::read_unaligned(self.pushed[index].as_ptr() as *const _) };
+                return Some(KnownEvent::ParamGesture(event.into()));
+            }
             if x == CLAP_EVENT_PARAM_MOD {
                 let event: clap_event_param_mod =
                     unsafe { ptr::read_unaligned(self.pushed[index].as_ptr() as *const _) };
@@ -1321,6 +1815,7 @@ mod output_events {
             match known {
                 KnownEvent::Midi(ev) => output_events.try_push(ev.event()).unwrap(),
                 KnownEvent::Midi2(ev) => output_events.try_push(ev.event()).unwrap(),
+                KnownEvent::ParamGesture(ev) => output_events.try_push(ev.event()).unwrap(),
                 KnownEvent::ParamMod(ev) => output_events.try_push(ev.event()).unwrap(),
                 KnownEvent::ParamValue(ev) => output_events.try_push(ev.event()).unwrap(),
                 KnownEvent::Transport(ev) => output_events.try_push(ev.event()).unwrap(),
@@ -1342,7 +1837,9 @@ mod output_events {
         let events = [
             KnownEvent::Midi(Midi::build().port_index(1)),
             KnownEvent::Midi2(Midi2::build().port_index(2)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::Begin).param_id(3.into())),
             KnownEvent::ParamMod(ParamMod::build().amount(12.345)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::End).param_id(3.into())),
             KnownEvent::ParamValue(ParamValue::build().value(12.34)),
             KnownEvent::Transport(Transport::build().bar_number(11)),
         ];
@@ -1355,7 +1852,9 @@ mod output_events {
         let events = [
             KnownEvent::Midi(Midi::build().port_index(1)),
             KnownEvent::Midi2(Midi2::build().port_index(2)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::Begin).param_id(3.into())),
             KnownEvent::ParamMod(ParamMod::build().amount(12.345)),
+            KnownEvent::ParamGesture(ParamGesture::build(GestureKind::End).param_id(3.into())),
             KnownEvent::ParamValue(ParamValue::build().value(12.34)),
             KnownEvent::Transport(Transport::build().bar_number(11)),
         ];
@@ -1393,4 +1892,24 @@ mod output_events {
 
         assert_eq!(retrieved, event);
     }
+
+    #[test]
+    fn push_midi_sysex() {
+        let mut bed = TestBed::new(2);
+        let mut output_events = unsafe { OutputEvents::new_unchecked(bed.clap_output_events()) };
+
+        for payload in [vec![0xf0, 1, 2, 0xf7], vec![0xf0, 0xf7]] {
+            let sysex = MidiSysex::build().port_index(5).buffer(payload);
+            output_events.try_push(sysex.event()).unwrap();
+        }
+
+        let sysex = MidiSysex::build().buffer([0xf0, 0xf7]);
+        let _ = output_events.try_push(sysex.event()).unwrap_err();
+
+        assert_eq!(
+            bed.sysex_payloads,
+            [vec![0xf0, 1, 2, 0xf7], vec![0xf0, 0xf7]]
+        );
+        assert_eq!(bed.pushed.len(), 2);
+    }
 }
@@ -0,0 +1,280 @@
+use clap_clap::{
+    events::{
+        Error, Event, EventBuilder, EventQueue, EventRef, Midi, MidiSysex, Note, NoteBuilder,
+        NoteKind, ParamValue,
+    },
+    ffi::{clap_event_midi, clap_event_note},
+    id::ClapId,
+};
+
+fn note(kind: NoteKind, key: i16, time: u32) -> NoteBuilder {
+    Note::build(kind).key(key).time(time)
+}
+
+fn keys(queue: &EventQueue) -> Vec<(u32, i16)> {
+    queue
+        .iter()
+        .map(|event| match event {
+            EventRef::NoteOn(note) | EventRef::NoteOff(note) => (note.header().time(), note.key()),
+            _ => panic!("unexpected event"),
+        })
+        .collect()
+}
+
+#[test]
+fn new_empty() {
+    let queue = EventQueue::new();
+
+    assert!(queue.is_empty());
+    assert_eq!(queue.len(), 0);
+    assert!(queue.get(0).is_none());
+    assert_eq!(queue.iter().len(), 0);
+}
+
+#[test]
+fn with_capacity() {
+    let queue = EventQueue::with_capacity(100, 3);
+
+    assert_eq!(queue.capacity(), 104);
+    assert_eq!(queue.max_events(), 3);
+}
+
+#[test]
+fn push_sorted() {
+    let mut queue = EventQueue::new();
+
+    queue.try_push(note(NoteKind::On, 1, 10).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 2, 0).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 3, 5).event()).unwrap();
+
+    assert_eq!(queue.len(), 3);
+    assert_eq!(keys(&queue), [(0, 2), (5, 3), (10, 1)]);
+}
+
+#[test]
+fn push_same_time_keeps_order() {
+    let mut queue = EventQueue::new();
+
+    queue.try_push(note(NoteKind::On, 1, 7).event()).unwrap();
+    queue.try_push(note(NoteKind::Off, 2, 7).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 3, 0).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 4, 7).event()).unwrap();
+
+    assert_eq!(keys(&queue), [(0, 3), (7, 1), (7, 2), (7, 4)]);
+}
+
+#[test]
+fn push_mixed_events() {
+    let mut queue = EventQueue::new();
+
+    queue
+        .try_push(
+            ParamValue::build()
+                .param_id(ClapId::from(4))
+                .value(0.5)
+                .time(3)
+                .event(),
+        )
+        .unwrap();
+    queue
+        .try_push(Midi::build().data([1, 2, 3]).time(1).event())
+        .unwrap();
+
+    let header = queue.get(0).unwrap();
+    assert_eq!(header.midi().unwrap().data(), &[1, 2, 3]);
+
+    let header = queue.get(1).unwrap();
+    let param_value = header.param_value().unwrap();
+    assert_eq!(param_value.param_id(), ClapId::from(4));
+    assert_eq!(param_value.value(), 0.5);
+}
+
+#[test]
+fn push_at() {
+    let mut queue = EventQueue::new();
+
+    let note = note(NoteKind::On, 1, 10);
+    queue.try_push_at(note.event(), 3).unwrap();
+    queue.try_push(note.event()).unwrap();
+
+    assert_eq!(note.event().header().time(), 10);
+    assert_eq!(keys(&queue), [(3, 1), (10, 1)]);
+}
+
+#[test]
+fn full_max_events() {
+    let mut queue = EventQueue::with_capacity(1024, 2);
+
+    queue.try_push(note(NoteKind::On, 1, 0).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 2, 0).event()).unwrap();
+
+    assert_eq!(
+        queue.try_push(note(NoteKind::On, 3, 0).event()),
+        Err(Error::QueueFull)
+    );
+    assert_eq!(queue.len(), 2);
+}
+
+#[test]
+fn full_capacity() {
+    let size = size_of::<clap_event_note>() + size_of::<clap_event_midi>().next_multiple_of(8);
+    let mut queue = EventQueue::with_capacity(size, 100);
+
+    queue.try_push(note(NoteKind::On, 1, 0).event()).unwrap();
+    assert_eq!(
+        queue.try_push(note(NoteKind::On, 2, 0).event()),
+        Err(Error::QueueFull)
+    );
+
+    // A smaller event still fits.
+    queue.try_push(Midi::build().event()).unwrap();
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.try_push(Midi::build().event()), Err(Error::QueueFull));
+}
+
+#[test]
+fn clear_reuse() {
+    let mut queue = EventQueue::with_capacity(size_of::<clap_event_note>(), 1);
+
+    queue.try_push(note(NoteKind::On, 1, 0).event()).unwrap();
+    queue.clear();
+    assert!(queue.is_empty());
+    assert_eq!(queue.capacity(), size_of::<clap_event_note>());
+
+    queue.try_push(note(NoteKind::On, 2, 0).event()).unwrap();
+    assert_eq!(keys(&queue), [(0, 2)]);
+}
+
+#[test]
+fn merge() {
+    let mut queue = EventQueue::new();
+    queue.try_push(note(NoteKind::On, 1, 0).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 2, 8).event()).unwrap();
+
+    let mut other = EventQueue::new();
+    other.try_push(note(NoteKind::On, 3, 4).event()).unwrap();
+    other.try_push(note(NoteKind::On, 4, 8).event()).unwrap();
+    other.try_push(note(NoteKind::On, 5, 9).event()).unwrap();
+
+    queue.merge(&other).unwrap();
+
+    assert_eq!(keys(&queue), [(0, 1), (4, 3), (8, 2), (8, 4), (9, 5)]);
+    assert_eq!(other.len(), 3);
+}
+
+#[test]
+fn merge_full() {
+    let mut queue = EventQueue::with_capacity(1024, 3);
+    queue.try_push(note(NoteKind::On, 1, 0).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 2, 8).event()).unwrap();
+
+    let mut other = EventQueue::new();
+    other.try_push(note(NoteKind::On, 3, 4).event()).unwrap();
+    other.try_push(note(NoteKind::On, 4, 8).event()).unwrap();
+
+    assert_eq!(queue.merge(&other), Err(Error::QueueFull));
+    assert_eq!(keys(&queue), [(0, 1), (8, 2)]);
+}
+
+#[test]
+fn midi_sysex_buffer_copied() {
+    let mut queue = EventQueue::new();
+
+    {
+        let sysex = MidiSysex::build().buffer(vec![0xF0, 1, 2, 3, 0xF7]).time(2);
+        queue.try_push(sysex.event()).unwrap();
+    }
+    queue.try_push(MidiSysex::build().time(1).event()).unwrap();
+
+    let empty = queue.get(0).unwrap().midi_sysex().unwrap();
+    assert!(empty.buffer().is_empty());
+
+    let sysex = queue.get(1).unwrap().midi_sysex().unwrap();
+    assert_eq!(sysex.buffer(), &[0xF0, 1, 2, 3, 0xF7]);
+}
+
+#[test]
+fn input_events() {
+    let mut queue = EventQueue::new();
+    queue.try_push(note(NoteKind::On, 1, 3).event()).unwrap();
+    queue.try_push(note(NoteKind::Off, 2, 1).event()).unwrap();
+
+    let input_events = queue.input_events();
+    assert_eq!(input_events.size(), 2);
+    assert_eq!(input_events.get(0).note().unwrap().key(), 2);
+    assert_eq!(input_events.get(1).note().unwrap().key(), 1);
+
+    let list = queue.as_clap_input_events();
+    let header = unsafe { list.get.unwrap()(list, 2) };
+    assert!(header.is_null());
+}
+
+#[test]
+fn output_events() {
+    let mut queue = EventQueue::new();
+
+    let mut output_events = queue.output_events();
+    output_events
+        .try_push(note(NoteKind::On, 1, 0).event())
+        .unwrap();
+    output_events
+        .try_push(note(NoteKind::Off, 1, 5).event())
+        .unwrap();
+
+    assert_eq!(keys(&queue), [(0, 1), (5, 1)]);
+    assert_eq!(queue.unordered_output(), None);
+}
+
+#[test]
+fn output_events_unordered() {
+    let mut queue = EventQueue::new();
+
+    let mut output_events = queue.output_events();
+    for (key, time) in [(1, 0), (2, 5), (3, 2), (4, 1)] {
+        // SAFETY: The queue accepts events out of order.
+        unsafe { output_events.try_push_unchecked(note(NoteKind::On, key, time).event()) }.unwrap();
+    }
+
+    assert_eq!(keys(&queue), [(0, 1), (1, 4), (2, 3), (5, 2)]);
+    assert_eq!(queue.unordered_output(), Some(2));
+
+    queue.clear();
+    assert_eq!(queue.unordered_output(), None);
+}
+
+#[test]
+fn output_events_full() {
+    let mut queue = EventQueue::with_capacity(1024, 1);
+
+    let mut output_events = queue.output_events();
+    output_events
+        .try_push(note(NoteKind::On, 1, 0).event())
+        .unwrap();
+    assert_eq!(
+        output_events.try_push(note(NoteKind::On, 2, 0).event()),
+        Err(Error::TryPush)
+    );
+}
+
+#[test]
+fn send() {
+    let mut queue = EventQueue::new();
+    queue.try_push(note(NoteKind::On, 1, 6).event()).unwrap();
+    queue.try_push(note(NoteKind::On, 2, 2).event()).unwrap();
+
+    let mut out = EventQueue::new();
+    queue.send(&mut out.output_events()).unwrap();
+
+    assert_eq!(keys(&out), [(2, 2), (6, 1)]);
+}
+
+#[test]
+fn moved_queue() {
+    let mut queue = EventQueue::new();
+    queue.try_push(note(NoteKind::On, 1, 0).event()).unwrap();
+
+    let queues = [queue];
+    let input_events = queues[0].input_events();
+    assert_eq!(input_events.size(), 1);
+    assert_eq!(input_events.get(0).note().unwrap().key(), 1);
+}
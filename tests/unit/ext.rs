@@ -1,10 +1,20 @@
 mod audio_ports;
+mod event_registry;
+mod gui;
 mod latency;
 mod log;
+mod note_name;
 mod note_ports;
+mod param_indication;
 mod params;
+mod posix_fd_support;
+mod remote_controls;
+mod render;
 mod state;
 mod tail;
+mod thread_pool;
+mod timer_support;
+mod voice_info;
 
 use std::{
     ffi::{CStr, CString, c_void},
@@ -17,16 +27,26 @@ use std::{
 use clap_clap::{
     ext::{
         audio_ports::AudioPortInfo,
+        gui::{ResizeHints, Window, WindowApi},
+        note_name::NoteNameInfo,
         note_ports::NotePortInfo,
         params::{Error, ParamInfo},
+        remote_controls::RemoteControlsPage,
+        voice_info::VoiceInfoData,
     },
     factory::{Factory, FactoryHost, FactoryPluginPrototype},
     ffi::{
-        CLAP_EXT_AUDIO_PORTS, CLAP_EXT_LATENCY, CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAMS,
-        CLAP_EXT_STATE, CLAP_EXT_TAIL, clap_audio_port_info, clap_event_header, clap_input_events,
-        clap_istream, clap_note_port_info, clap_ostream, clap_output_events, clap_plugin,
-        clap_plugin_audio_ports, clap_plugin_latency, clap_plugin_note_ports, clap_plugin_params,
-        clap_plugin_state, clap_plugin_tail,
+        CLAP_EXT_AUDIO_PORTS, CLAP_EXT_GUI, CLAP_EXT_LATENCY, CLAP_EXT_NOTE_NAME,
+        CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAM_INDICATION, CLAP_EXT_PARAMS, CLAP_EXT_POSIX_FD_SUPPORT,
+        CLAP_EXT_REMOTE_CONTROLS, CLAP_EXT_RENDER, CLAP_EXT_STATE, CLAP_EXT_TAIL,
+        CLAP_EXT_THREAD_POOL, CLAP_EXT_TIMER_SUPPORT, CLAP_EXT_VOICE_INFO, clap_audio_port_info,
+        clap_color, clap_event_header, clap_gui_resize_hints, clap_input_events, clap_istream,
+        clap_note_name, clap_note_port_info, clap_ostream, clap_output_events, clap_plugin,
+        clap_plugin_audio_ports, clap_plugin_gui, clap_plugin_latency, clap_plugin_note_name,
+        clap_plugin_note_ports, clap_plugin_param_indication, clap_plugin_params,
+        clap_plugin_posix_fd_support, clap_plugin_remote_controls, clap_plugin_render,
+        clap_plugin_state, clap_plugin_tail, clap_plugin_thread_pool, clap_plugin_timer_support,
+        clap_plugin_voice_info, clap_remote_controls_page, clap_voice_info,
     },
     id::ClapId,
     plugin::{ClapPlugin, Plugin},
@@ -66,11 +86,20 @@ where
 {
     clap_plugin: *const clap_plugin,
     pub ext_audio_ports: Option<ExtAudioPorts>,
+    pub ext_gui: Option<ExtGui>,
     pub ext_latency: Option<ExtLatency>,
+    pub ext_note_name: Option<ExtNoteName>,
     pub ext_note_ports: Option<ExtNotePorts>,
+    pub ext_param_indication: Option<ExtParamIndication>,
     pub ext_params: Option<ExtParams>,
+    pub ext_posix_fd_support: Option<ExtPosixFdSupport>,
+    pub ext_remote_controls: Option<ExtRemoteControls>,
+    pub ext_render: Option<ExtRender>,
     pub ext_state: Option<ExtState>,
     pub ext_tail: Option<ExtTail>,
+    pub ext_thread_pool: Option<ExtThreadPool>,
+    pub ext_timer_support: Option<ExtTimerSupport>,
+    pub ext_voice_info: Option<ExtVoiceInfo>,
     _marker: PhantomData<P>,
 }
 
@@ -97,17 +126,26 @@ where
 
         let mut wrapper = unsafe { ClapPlugin::new_unchecked(clap_plugin) };
         let plugin: &mut P = unsafe { wrapper.plugin() };
-        plugin.initialize(&config);
+        plugin.initialize(config);
 
         unsafe {
             Self {
                 clap_plugin,
                 ext_audio_ports: ExtAudioPorts::try_new_unchecked(clap_plugin),
+                ext_gui: ExtGui::try_new_unchecked(clap_plugin),
                 ext_latency: ExtLatency::try_new_unchecked(clap_plugin),
+                ext_note_name: ExtNoteName::try_new_unchecked(clap_plugin),
                 ext_note_ports: ExtNotePorts::try_new_unchecked(clap_plugin),
+                ext_param_indication: ExtParamIndication::try_new_unchecked(clap_plugin),
                 ext_params: ExtParams::try_new_unchecked(clap_plugin),
+                ext_posix_fd_support: ExtPosixFdSupport::try_new_unchecked(clap_plugin),
+                ext_remote_controls: ExtRemoteControls::try_new_unchecked(clap_plugin),
+                ext_render: ExtRender::try_new_unchecked(clap_plugin),
                 ext_state: ExtState::try_new_unchecked(clap_plugin),
                 ext_tail: ExtTail::try_new_unchecked(clap_plugin),
+                ext_thread_pool: ExtThreadPool::try_new_unchecked(clap_plugin),
+                ext_timer_support: ExtTimerSupport::try_new_unchecked(clap_plugin),
+                ext_voice_info: ExtVoiceInfo::try_new_unchecked(clap_plugin),
                 _marker: PhantomData,
             }
         }
@@ -193,6 +231,153 @@ impl ExtAudioPorts {
     }
 }
 
+#[derive(Debug)]
+pub struct ExtGui {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_gui: *const clap_plugin_gui,
+}
+
+impl ExtGui {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension =
+            unsafe { (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_GUI.as_ptr()) };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_gui: (&raw const *ext).cast(),
+        })
+    }
+
+    fn gui(&self) -> &clap_plugin_gui {
+        unsafe { self.clap_plugin_gui.as_ref() }.unwrap()
+    }
+
+    pub fn is_api_supported(&self, api: &CStr, is_floating: bool) -> bool {
+        unsafe { self.gui().is_api_supported.unwrap()(self.clap_plugin, api.as_ptr(), is_floating) }
+    }
+
+    pub fn get_preferred_api(&self) -> Option<(WindowApi, bool)> {
+        let mut api = null();
+        let mut is_floating = false;
+        unsafe {
+            self.gui().get_preferred_api.unwrap()(
+                self.clap_plugin,
+                &raw mut api,
+                &raw mut is_floating,
+            )
+        }
+        .then(|| {
+            let api = WindowApi::try_from(unsafe { CStr::from_ptr(api) }).unwrap();
+            (api, is_floating)
+        })
+    }
+
+    pub fn create(&self, api: &CStr, is_floating: bool) -> bool {
+        unsafe { self.gui().create.unwrap()(self.clap_plugin, api.as_ptr(), is_floating) }
+    }
+
+    pub fn destroy(&self) {
+        unsafe { self.gui().destroy.unwrap()(self.clap_plugin) }
+    }
+
+    pub fn set_scale(&self, scale: f64) -> bool {
+        unsafe { self.gui().set_scale.unwrap()(self.clap_plugin, scale) }
+    }
+
+    pub fn get_size(&self) -> Option<(u32, u32)> {
+        let (mut width, mut height) = (0, 0);
+        unsafe { self.gui().get_size.unwrap()(self.clap_plugin, &raw mut width, &raw mut height) }
+            .then_some((width, height))
+    }
+
+    pub fn can_resize(&self) -> bool {
+        unsafe { self.gui().can_resize.unwrap()(self.clap_plugin) }
+    }
+
+    pub fn get_resize_hints(&self) -> Option<ResizeHints> {
+        let mut hints = MaybeUninit::<clap_gui_resize_hints>::uninit();
+        unsafe { self.gui().get_resize_hints.unwrap()(self.clap_plugin, hints.as_mut_ptr()) }
+            .then(|| unsafe { hints.assume_init() }.into())
+    }
+
+    pub fn adjust_size(&self, width: u32, height: u32) -> Option<(u32, u32)> {
+        let (mut width, mut height) = (width, height);
+        unsafe {
+            self.gui().adjust_size.unwrap()(self.clap_plugin, &raw mut width, &raw mut height)
+        }
+        .then_some((width, height))
+    }
+
+    pub fn set_size(&self, width: u32, height: u32) -> bool {
+        unsafe { self.gui().set_size.unwrap()(self.clap_plugin, width, height) }
+    }
+
+    pub fn set_parent(&self, window: Window) -> bool {
+        let window = window.to_clap_window();
+        unsafe { self.gui().set_parent.unwrap()(self.clap_plugin, &raw const window) }
+    }
+
+    pub fn set_transient(&self, window: Window) -> bool {
+        let window = window.to_clap_window();
+        unsafe { self.gui().set_transient.unwrap()(self.clap_plugin, &raw const window) }
+    }
+
+    pub fn suggest_title(&self, title: &CStr) {
+        unsafe { self.gui().suggest_title.unwrap()(self.clap_plugin, title.as_ptr()) }
+    }
+
+    pub fn show(&self) -> bool {
+        unsafe { self.gui().show.unwrap()(self.clap_plugin) }
+    }
+
+    pub fn hide(&self) -> bool {
+        unsafe { self.gui().hide.unwrap()(self.clap_plugin) }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtNoteName {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_note_name: *const clap_plugin_note_name,
+}
+
+impl ExtNoteName {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension = unsafe {
+            (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_NOTE_NAME.as_ptr())
+        };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_note_name: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn count(&self) -> u32 {
+        let note_name = unsafe { self.clap_plugin_note_name.as_ref() }.unwrap();
+        unsafe { note_name.count.unwrap()(self.clap_plugin) }
+    }
+
+    pub fn get(&self, index: u32) -> Option<NoteNameInfo> {
+        let note_name = unsafe { self.clap_plugin_note_name.as_ref() }.unwrap();
+        let mut info = MaybeUninit::<clap_note_name>::uninit();
+
+        if unsafe { note_name.get.unwrap()(self.clap_plugin, index, info.as_mut_ptr()) } {
+            Some(unsafe { NoteNameInfo::try_from_unchecked(info.assume_init()) }.unwrap())
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ExtNotePorts {
     clap_plugin: *const clap_plugin,
@@ -270,6 +455,66 @@ impl ExtLatency {
     }
 }
 
+#[derive(Debug)]
+pub struct ExtParamIndication {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_param_indication: *const clap_plugin_param_indication,
+}
+
+impl ExtParamIndication {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension = unsafe {
+            (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_PARAM_INDICATION.as_ptr())
+        };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_param_indication: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn set_mapping(
+        &self,
+        param_id: u32,
+        has_mapping: bool,
+        color: Option<clap_color>,
+        label: Option<&CStr>,
+        description: Option<&CStr>,
+    ) {
+        let param_indication = unsafe { self.clap_plugin_param_indication.as_ref() }.unwrap();
+        let color = color.as_ref().map_or(null(), |c| &raw const *c);
+
+        unsafe {
+            param_indication.set_mapping.unwrap()(
+                self.clap_plugin,
+                param_id,
+                has_mapping,
+                color,
+                label.map_or(null(), CStr::as_ptr),
+                description.map_or(null(), CStr::as_ptr),
+            )
+        }
+    }
+
+    pub fn set_automation(&self, param_id: u32, automation_state: u32, color: Option<clap_color>) {
+        let param_indication = unsafe { self.clap_plugin_param_indication.as_ref() }.unwrap();
+        let color = color.as_ref().map_or(null(), |c| &raw const *c);
+
+        unsafe {
+            param_indication.set_automation.unwrap()(
+                self.clap_plugin,
+                param_id,
+                automation_state,
+                color,
+            )
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ExtParams {
     clap_plugin: *const clap_plugin,
@@ -524,3 +769,195 @@ impl ExtTail {
         unsafe { tail.get.unwrap()(self.clap_plugin) }
     }
 }
+
+#[derive(Debug)]
+pub struct ExtThreadPool {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_thread_pool: *const clap_plugin_thread_pool,
+}
+
+impl ExtThreadPool {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension = unsafe {
+            (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_THREAD_POOL.as_ptr())
+        };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_thread_pool: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn exec(&self, task_index: u32) {
+        let thread_pool = unsafe { self.clap_plugin_thread_pool.as_ref() }.unwrap();
+
+        unsafe { thread_pool.exec.unwrap()(self.clap_plugin, task_index) }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtTimerSupport {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_timer_support: *const clap_plugin_timer_support,
+}
+
+impl ExtTimerSupport {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension = unsafe {
+            (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_TIMER_SUPPORT.as_ptr())
+        };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_timer_support: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn on_timer(&self, timer_id: u32) {
+        let timer_support = unsafe { self.clap_plugin_timer_support.as_ref() }.unwrap();
+
+        unsafe { timer_support.on_timer.unwrap()(self.clap_plugin, timer_id) }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtPosixFdSupport {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_posix_fd_support: *const clap_plugin_posix_fd_support,
+}
+
+impl ExtPosixFdSupport {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension = unsafe {
+            (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_POSIX_FD_SUPPORT.as_ptr())
+        };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_posix_fd_support: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn on_fd(&self, fd: i32, flags: u32) {
+        let posix_fd_support = unsafe { self.clap_plugin_posix_fd_support.as_ref() }.unwrap();
+
+        unsafe { posix_fd_support.on_fd.unwrap()(self.clap_plugin, fd, flags) }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtRemoteControls {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_remote_controls: *const clap_plugin_remote_controls,
+}
+
+impl ExtRemoteControls {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension = unsafe {
+            (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_REMOTE_CONTROLS.as_ptr())
+        };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_remote_controls: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn count(&self) -> u32 {
+        let remote_controls = unsafe { self.clap_plugin_remote_controls.as_ref() }.unwrap();
+        unsafe { remote_controls.count.unwrap()(self.clap_plugin) }
+    }
+
+    pub fn get(&self, page_index: u32) -> Option<RemoteControlsPage> {
+        let remote_controls = unsafe { self.clap_plugin_remote_controls.as_ref() }.unwrap();
+        let mut page = MaybeUninit::<clap_remote_controls_page>::uninit();
+
+        if unsafe { remote_controls.get.unwrap()(self.clap_plugin, page_index, page.as_mut_ptr()) }
+        {
+            Some(unsafe { RemoteControlsPage::try_from_unchecked(page.assume_init()) }.unwrap())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtRender {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_render: *const clap_plugin_render,
+}
+
+impl ExtRender {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension =
+            unsafe { (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_RENDER.as_ptr()) };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_render: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn has_hard_realtime_requirement(&self) -> bool {
+        let render = unsafe { self.clap_plugin_render.as_ref() }.unwrap();
+
+        unsafe { render.has_hard_realtime_requirement.unwrap()(self.clap_plugin) }
+    }
+
+    pub fn set(&self, mode: i32) -> bool {
+        let render = unsafe { self.clap_plugin_render.as_ref() }.unwrap();
+
+        unsafe { render.set.unwrap()(self.clap_plugin, mode) }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtVoiceInfo {
+    clap_plugin: *const clap_plugin,
+    clap_plugin_voice_info: *const clap_plugin_voice_info,
+}
+
+impl ExtVoiceInfo {
+    /// # Safety
+    ///
+    /// clap_plugin must be non-null.
+    pub unsafe fn try_new_unchecked(clap_plugin: *const clap_plugin) -> Option<Self> {
+        assert!(!clap_plugin.is_null());
+        let extension = unsafe {
+            (*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_VOICE_INFO.as_ptr())
+        };
+
+        unsafe { extension.as_ref() }.map(|ext| Self {
+            clap_plugin,
+            clap_plugin_voice_info: (&raw const *ext).cast(),
+        })
+    }
+
+    pub fn get(&self) -> Option<VoiceInfoData> {
+        let voice_info = unsafe { self.clap_plugin_voice_info.as_ref() }.unwrap();
+        let mut info = MaybeUninit::<clap_voice_info>::uninit();
+
+        unsafe { voice_info.get.unwrap()(self.clap_plugin, info.as_mut_ptr()) }
+            .then(|| unsafe { info.assume_init() }.into())
+    }
+}
@@ -0,0 +1,88 @@
+use std::pin::Pin;
+
+use clap_clap::{
+    ext::event_registry,
+    host,
+    host::Error::{Callback, ExtensionNotFound},
+};
+
+use crate::host::{ExtEventRegistry, ExtEventRegistryConfig, Test, TestBed, TestConfig};
+
+struct CheckEventRegistryNotImpl {
+    error: host::Error,
+}
+
+impl Test for CheckEventRegistryNotImpl {
+    fn test(self, bed: Pin<&mut TestBed>) {
+        let host = unsafe { bed.host_mut() };
+        let err = host.get_extension().event_registry().unwrap_err();
+        assert_eq!(err, self.error);
+    }
+}
+
+#[test]
+fn event_registry_not_impl() {
+    TestConfig::default().test(CheckEventRegistryNotImpl {
+        error: ExtensionNotFound("event_registry"),
+    });
+}
+
+#[test]
+fn event_registry_null_callback() {
+    TestConfig {
+        ext_event_registry: Some(ExtEventRegistryConfig {
+            null_callback: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+    .test(CheckEventRegistryNotImpl {
+        error: Callback("query"),
+    });
+}
+
+struct CheckQuery;
+
+impl Test for CheckQuery {
+    fn test(self, mut bed: Pin<&mut TestBed>) {
+        let host = unsafe { bed.as_mut().host_mut() };
+        let event_registry = host.get_extension().event_registry().unwrap();
+
+        let space_id = event_registry.query("com.vendor.space").unwrap();
+        assert_eq!(u16::from(space_id), ExtEventRegistry::FIRST_SPACE_ID + 1);
+
+        let space_id = event_registry.query_cstr(c"com.vendor.other").unwrap();
+        assert_eq!(u16::from(space_id), ExtEventRegistry::FIRST_SPACE_ID);
+
+        assert_eq!(
+            event_registry.query("com.vendor.unknown"),
+            Err(event_registry::Error::Unsupported)
+        );
+        assert!(matches!(
+            event_registry.query("com.vendor\0space"),
+            Err(event_registry::Error::NulError(_))
+        ));
+
+        let ext = bed.ext_event_registry.as_ref().unwrap();
+        assert_eq!(
+            ext.call_query,
+            [
+                c"com.vendor.space",
+                c"com.vendor.other",
+                c"com.vendor.unknown"
+            ]
+        );
+    }
+}
+
+#[test]
+fn event_registry_query() {
+    TestConfig {
+        ext_event_registry: Some(ExtEventRegistryConfig {
+            spaces: &[c"com.vendor.other", c"com.vendor.space"],
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+    .test(CheckQuery);
+}
@@ -0,0 +1,356 @@
+mod plugin_gui {
+    use std::marker::PhantomData;
+
+    use clap_clap::{
+        Error,
+        ext::{
+            Extensions,
+            gui::{Gui, ResizeHints, Window, WindowApi},
+        },
+        plugin::Plugin,
+    };
+
+    use crate::{
+        ext::{Test, TestBed, TestConfig, TestPlugin},
+        shims::plugin::ShimPlugin,
+    };
+
+    #[derive(Debug, Default)]
+    struct CheckNoExt<P> {
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+        fn test(self, bed: &mut TestBed<P>) {
+            if P::gui().is_some() {
+                assert!(bed.ext_gui.is_some());
+            } else {
+                assert!(bed.ext_gui.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn no_gui_shim() {
+        TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+    }
+
+    #[derive(Default)]
+    struct Plug {
+        created: Option<(WindowApi, bool)>,
+        parent: Option<Window>,
+        transient: Option<Window>,
+        title: String,
+        scale: f64,
+        size: (u32, u32),
+        visible: bool,
+    }
+
+    impl Plugin for Plug {
+        type AudioThread = ();
+        const ID: &'static str = "";
+        const NAME: &'static str = "";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(())
+        }
+    }
+
+    impl TestPlugin for Plug {}
+
+    impl Extensions<Self> for Plug {
+        fn gui() -> Option<impl Gui<Self>> {
+            Some(PlugGui)
+        }
+    }
+
+    struct PlugGui;
+
+    impl Gui<Plug> for PlugGui {
+        fn is_api_supported(_: &Plug, api: WindowApi, _: bool) -> bool {
+            api == WindowApi::X11
+        }
+
+        fn get_preferred_api(_: &Plug) -> Option<(WindowApi, bool)> {
+            Some((WindowApi::X11, false))
+        }
+
+        fn create(plugin: &mut Plug, api: WindowApi, is_floating: bool) -> Result<(), Error> {
+            plugin.created = Some((api, is_floating));
+            plugin.size = (640, 480);
+            Ok(())
+        }
+
+        fn destroy(plugin: &mut Plug) {
+            plugin.created = None;
+            plugin.parent = None;
+        }
+
+        fn set_scale(plugin: &mut Plug, scale: f64) -> Result<(), Error> {
+            plugin.scale = scale;
+            Ok(())
+        }
+
+        fn get_size(plugin: &Plug) -> Option<(u32, u32)> {
+            plugin.created.is_some().then_some(plugin.size)
+        }
+
+        fn can_resize(_: &Plug) -> bool {
+            true
+        }
+
+        fn get_resize_hints(_: &Plug) -> Option<ResizeHints> {
+            Some(ResizeHints {
+                can_resize_horizontally: true,
+                can_resize_vertically: true,
+                preserve_aspect_ratio: true,
+                aspect_ratio_width: 4,
+                aspect_ratio_height: 3,
+            })
+        }
+
+        fn adjust_size(_: &Plug, width: u32, _: u32) -> Option<(u32, u32)> {
+            Some((width, width / 4 * 3))
+        }
+
+        fn set_size(plugin: &mut Plug, width: u32, height: u32) -> Result<(), Error> {
+            plugin.size = (width, height);
+            Ok(())
+        }
+
+        fn set_parent(plugin: &mut Plug, window: Window) -> Result<(), Error> {
+            plugin.parent = Some(window);
+            Ok(())
+        }
+
+        fn set_transient(plugin: &mut Plug, window: Window) -> Result<(), Error> {
+            plugin.transient = Some(window);
+            Ok(())
+        }
+
+        fn suggest_title(plugin: &mut Plug, title: &str) {
+            plugin.title = title.to_owned();
+        }
+
+        fn show(plugin: &mut Plug) -> Result<(), Error> {
+            plugin.visible = true;
+            Ok(())
+        }
+
+        fn hide(plugin: &mut Plug) -> Result<(), Error> {
+            plugin.visible = false;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn gui_plug() {
+        TestConfig::default().test::<Plug>(CheckNoExt::default());
+    }
+
+    struct CheckApiSupported;
+
+    impl Test<Plug> for CheckApiSupported {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let gui = bed.ext_gui.as_ref().unwrap();
+
+            assert!(gui.is_api_supported(c"x11", false));
+            assert!(!gui.is_api_supported(c"win32", false));
+            assert!(!gui.is_api_supported(c"cocoa", true));
+            assert!(!gui.is_api_supported(c"unknown", false));
+            assert_eq!(gui.get_preferred_api(), Some((WindowApi::X11, false)));
+        }
+    }
+
+    #[test]
+    fn plugin_gui_api_supported() {
+        TestConfig::default().test::<Plug>(CheckApiSupported);
+    }
+
+    struct CheckEmbedX11;
+
+    impl Test<Plug> for CheckEmbedX11 {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let gui = bed.ext_gui.as_ref().unwrap();
+
+            assert_eq!(gui.get_size(), None);
+            assert!(!gui.create(c"unknown", false));
+            assert!(gui.create(c"x11", false));
+            assert!(gui.set_scale(2.0));
+            assert!(gui.can_resize());
+            assert_eq!(gui.get_size(), Some((640, 480)));
+            assert_eq!(gui.adjust_size(800, 700), Some((800, 600)));
+            assert!(gui.set_size(800, 600));
+            assert!(gui.set_parent(Window::X11(0x4a00007)));
+            assert!(gui.show());
+
+            let mut handle = bed.plugin();
+            let plugin = unsafe { handle.plugin() };
+            assert_eq!(plugin.created, Some((WindowApi::X11, false)));
+            assert_eq!(plugin.parent, Some(Window::X11(0x4a00007)));
+            assert_eq!(plugin.scale, 2.0);
+            assert_eq!(plugin.size, (800, 600));
+            assert!(plugin.visible);
+
+            let gui = bed.ext_gui.as_ref().unwrap();
+            assert!(gui.hide());
+            gui.destroy();
+
+            let plugin = unsafe { handle.plugin() };
+            assert!(!plugin.visible);
+            assert_eq!(plugin.created, None);
+            assert_eq!(plugin.parent, None);
+        }
+    }
+
+    #[test]
+    fn plugin_gui_embed_x11() {
+        TestConfig::default().test::<Plug>(CheckEmbedX11);
+    }
+
+    struct CheckFloating;
+
+    impl Test<Plug> for CheckFloating {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let gui = bed.ext_gui.as_ref().unwrap();
+
+            assert!(gui.create(c"x11", true));
+            assert!(gui.set_transient(Window::X11(7)));
+            gui.suggest_title(c"Plugin Title");
+
+            let mut handle = bed.plugin();
+            let plugin = unsafe { handle.plugin() };
+            assert_eq!(plugin.created, Some((WindowApi::X11, true)));
+            assert_eq!(plugin.transient, Some(Window::X11(7)));
+            assert_eq!(plugin.title, "Plugin Title");
+        }
+    }
+
+    #[test]
+    fn plugin_gui_floating() {
+        TestConfig::default().test::<Plug>(CheckFloating);
+    }
+
+    struct CheckResizeHints;
+
+    impl Test<Plug> for CheckResizeHints {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let gui = bed.ext_gui.as_ref().unwrap();
+
+            assert_eq!(
+                gui.get_resize_hints(),
+                Some(ResizeHints {
+                    can_resize_horizontally: true,
+                    can_resize_vertically: true,
+                    preserve_aspect_ratio: true,
+                    aspect_ratio_width: 4,
+                    aspect_ratio_height: 3,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn plugin_gui_resize_hints() {
+        TestConfig::default().test::<Plug>(CheckResizeHints);
+    }
+}
+
+mod host_gui {
+    use std::pin::Pin;
+
+    use clap_clap::{
+        host,
+        host::Error::{Callback, ExtensionNotFound},
+    };
+
+    use crate::host::{ExtGuiConfig, Test, TestBed, TestConfig};
+
+    struct CheckGuiNotImpl {
+        error: host::Error,
+    }
+
+    impl Test for CheckGuiNotImpl {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let err = host.get_extension().gui().unwrap_err();
+            assert_eq!(err, self.error);
+        }
+    }
+
+    #[test]
+    fn gui_not_impl() {
+        TestConfig::default().test(CheckGuiNotImpl {
+            error: ExtensionNotFound("gui"),
+        });
+    }
+
+    #[test]
+    fn gui_null_callback() {
+        TestConfig {
+            ext_gui: Some(ExtGuiConfig {
+                null_callback: (false, true, false, false, false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckGuiNotImpl {
+            error: Callback("request_resize"),
+        });
+
+        TestConfig {
+            ext_gui: Some(ExtGuiConfig {
+                null_callback: (false, false, false, false, true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckGuiNotImpl {
+            error: Callback("closed"),
+        });
+    }
+
+    struct CheckCallbacks;
+
+    impl Test for CheckCallbacks {
+        fn test(self, mut bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.as_mut().host_mut() };
+            let gui = host.get_extension().gui().unwrap();
+
+            gui.resize_hints_changed();
+            let resized = gui.request_resize(800, 600);
+            let shown = gui.request_show();
+            let hidden = gui.request_hide();
+            gui.closed(true);
+
+            let ext = bed.ext_gui.as_ref().unwrap();
+            assert!(ext.call_resize_hints_changed);
+            assert_eq!(ext.call_request_resize, Some((800, 600)));
+            assert!(ext.call_request_show);
+            assert!(ext.call_request_hide);
+            assert_eq!(ext.call_closed, Some(true));
+
+            assert_eq!(resized, ext.accept_requests());
+            assert_eq!(shown, ext.accept_requests());
+            assert_eq!(hidden, ext.accept_requests());
+        }
+    }
+
+    #[test]
+    fn gui_call_callbacks() {
+        TestConfig {
+            ext_gui: Some(ExtGuiConfig::default()),
+            ..Default::default()
+        }
+        .test(CheckCallbacks);
+
+        TestConfig {
+            ext_gui: Some(ExtGuiConfig {
+                accept_requests: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckCallbacks);
+    }
+}
@@ -0,0 +1,187 @@
+mod plugin_note_name {
+    use std::marker::PhantomData;
+
+    use clap_clap::{
+        Error,
+        ext::{
+            Extensions,
+            note_name::{NoteName, NoteNameInfo},
+        },
+        plugin::Plugin,
+        string_sizes::CLAP_NAME_SIZE,
+    };
+
+    use crate::{
+        ext::{Test, TestBed, TestConfig, TestPlugin},
+        shims::plugin::ShimPlugin,
+    };
+
+    #[derive(Debug, Default)]
+    struct CheckNoExt<P> {
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+        fn test(self, bed: &mut TestBed<P>) {
+            if P::note_name().is_some() {
+                assert!(bed.ext_note_name.is_some());
+            } else {
+                assert!(bed.ext_note_name.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn no_note_name_shim() {
+        TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+    }
+
+    #[derive(Default)]
+    struct Plug {
+        names: Vec<NoteNameInfo>,
+    }
+
+    impl Plugin for Plug {
+        type AudioThread = ();
+        const ID: &'static str = "";
+        const NAME: &'static str = "";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(())
+        }
+    }
+
+    impl TestPlugin for Plug {}
+
+    impl Extensions<Self> for Plug {
+        fn note_name() -> Option<impl NoteName<Self>> {
+            Some(PlugNoteName)
+        }
+    }
+
+    struct PlugNoteName;
+
+    impl NoteName<Plug> for PlugNoteName {
+        fn count(plugin: &Plug) -> u32 {
+            plugin.names.len() as u32
+        }
+
+        fn get(plugin: &Plug, index: u32) -> Option<NoteNameInfo> {
+            plugin.names.get(index as usize).cloned()
+        }
+    }
+
+    #[test]
+    fn note_name_plug() {
+        TestConfig::default().test::<Plug>(CheckNoExt::default());
+    }
+
+    struct CheckNoteNames(Vec<NoteNameInfo>);
+
+    impl Test<Plug> for CheckNoteNames {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let mut handle = bed.plugin();
+            unsafe { handle.plugin() }.names = self.0.clone();
+
+            let note_name = bed.ext_note_name.as_ref().unwrap();
+            assert_eq!(note_name.count(), self.0.len() as u32);
+            for (i, info) in self.0.iter().enumerate() {
+                let mut info = info.clone();
+                info.name.truncate(CLAP_NAME_SIZE - 1);
+
+                assert_eq!(note_name.get(i as u32), Some(info));
+            }
+            assert_eq!(note_name.get(self.0.len() as u32), None);
+        }
+    }
+
+    #[test]
+    fn note_name_get() {
+        TestConfig::default()
+            .test::<Plug>(CheckNoteNames(vec![]))
+            .test::<Plug>(CheckNoteNames(vec![
+                NoteNameInfo {
+                    name: "Kick".to_string(),
+                    port: 0,
+                    key: 36,
+                    channel: 9,
+                },
+                NoteNameInfo {
+                    name: "Snare".to_string(),
+                    key: 38,
+                    ..Default::default()
+                },
+            ]));
+    }
+
+    #[test]
+    fn note_name_get_long_name() {
+        TestConfig::default().test::<Plug>(CheckNoteNames(vec![NoteNameInfo {
+            name: "x".repeat(CLAP_NAME_SIZE + 10),
+            ..Default::default()
+        }]));
+    }
+}
+
+mod host_note_name {
+    use std::pin::Pin;
+
+    use clap_clap::{
+        host,
+        host::Error::{Callback, ExtensionNotFound},
+    };
+
+    use crate::host::{ExtNoteNameConfig, Test, TestBed, TestConfig};
+
+    struct CheckNoteNameNotImpl {
+        error: host::Error,
+    }
+
+    impl Test for CheckNoteNameNotImpl {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let err = host.get_extension().note_name().unwrap_err();
+            assert_eq!(err, self.error);
+        }
+    }
+
+    #[test]
+    fn note_name_not_impl() {
+        TestConfig::default().test(CheckNoteNameNotImpl {
+            error: ExtensionNotFound("note_name"),
+        });
+    }
+
+    #[test]
+    fn note_name_no_method_changed() {
+        TestConfig {
+            ext_note_name: Some(ExtNoteNameConfig {
+                null_callback: true,
+            }),
+            ..Default::default()
+        }
+        .test(CheckNoteNameNotImpl {
+            error: Callback("changed"),
+        });
+    }
+
+    struct CheckCallChanged;
+
+    impl Test for CheckCallChanged {
+        fn test(self, mut bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.as_mut().host_mut() };
+            host.get_extension().note_name().unwrap().changed();
+
+            assert!(bed.ext_note_name.as_ref().unwrap().call_changed);
+        }
+    }
+
+    #[test]
+    fn note_name_call_changed() {
+        TestConfig {
+            ext_note_name: Some(ExtNoteNameConfig::default()),
+            ..Default::default()
+        }
+        .test(CheckCallChanged);
+    }
+}
@@ -0,0 +1,200 @@
+use std::marker::PhantomData;
+
+use clap_clap::{
+    Error,
+    color::Color,
+    ext::{
+        Extensions,
+        param_indication::{AutomationState, ParamIndication},
+    },
+    ffi::{
+        CLAP_INVALID_ID, CLAP_PARAM_INDICATION_AUTOMATION_OVERRIDING,
+        CLAP_PARAM_INDICATION_AUTOMATION_PLAYING, clap_color,
+    },
+    id::ClapId,
+    plugin::Plugin,
+};
+
+use crate::{
+    ext::{Test, TestBed, TestConfig, TestPlugin},
+    shims::plugin::ShimPlugin,
+};
+
+#[derive(Debug, Default)]
+struct CheckNoExt<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+    fn test(self, bed: &mut TestBed<P>) {
+        if P::param_indication().is_some() {
+            assert!(bed.ext_param_indication.is_some());
+        } else {
+            assert!(bed.ext_param_indication.is_none());
+        }
+    }
+}
+
+#[test]
+fn no_param_indication_shim() {
+    TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+}
+
+type Mapping = (ClapId, bool, Option<Color>, Option<String>, Option<String>);
+
+#[derive(Default)]
+struct Plug {
+    mapping: Option<Mapping>,
+    automation: Option<(ClapId, AutomationState, Option<Color>)>,
+}
+
+impl Plugin for Plug {
+    type AudioThread = ();
+    const ID: &'static str = "";
+    const NAME: &'static str = "";
+
+    fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+        Ok(())
+    }
+}
+
+impl TestPlugin for Plug {}
+
+impl Extensions<Self> for Plug {
+    fn param_indication() -> Option<impl ParamIndication<Self>> {
+        Some(PlugParamIndication)
+    }
+}
+
+struct PlugParamIndication;
+
+impl ParamIndication<Plug> for PlugParamIndication {
+    fn set_mapping(
+        plugin: &mut Plug,
+        param_id: ClapId,
+        has_mapping: bool,
+        color: Option<Color>,
+        label: Option<&str>,
+        description: Option<&str>,
+    ) {
+        plugin.mapping = Some((
+            param_id,
+            has_mapping,
+            color,
+            label.map(str::to_owned),
+            description.map(str::to_owned),
+        ));
+    }
+
+    fn set_automation(
+        plugin: &mut Plug,
+        param_id: ClapId,
+        automation_state: AutomationState,
+        color: Option<Color>,
+    ) {
+        plugin.automation = Some((param_id, automation_state, color));
+    }
+}
+
+#[test]
+fn param_indication_plug() {
+    TestConfig::default().test::<Plug>(CheckNoExt::default());
+}
+
+const RED: clap_color = clap_color {
+    alpha: 0xff,
+    red: 0xff,
+    green: 0,
+    blue: 0,
+};
+
+struct CheckSetMapping;
+
+impl Test<Plug> for CheckSetMapping {
+    fn test(self, bed: &mut TestBed<Plug>) {
+        let param_indication = bed.ext_param_indication.as_ref().unwrap();
+        param_indication.set_mapping(3, true, Some(RED), Some(c"CC 7"), Some(c"Volume knob"));
+
+        let mut handle = bed.plugin();
+        let plugin = unsafe { handle.plugin() };
+        assert_eq!(
+            plugin.mapping,
+            Some((
+                ClapId::from(3),
+                true,
+                Some(Color::rgb(0xff, 0, 0)),
+                Some("CC 7".to_owned()),
+                Some("Volume knob".to_owned())
+            ))
+        );
+
+        let param_indication = bed.ext_param_indication.as_ref().unwrap();
+        param_indication.set_mapping(3, false, None, None, None);
+
+        let plugin = unsafe { handle.plugin() };
+        assert_eq!(
+            plugin.mapping,
+            Some((ClapId::from(3), false, None, None, None))
+        );
+    }
+}
+
+#[test]
+fn param_indication_set_mapping() {
+    TestConfig::default().test::<Plug>(CheckSetMapping);
+}
+
+struct CheckSetAutomation;
+
+impl Test<Plug> for CheckSetAutomation {
+    fn test(self, bed: &mut TestBed<Plug>) {
+        let param_indication = bed.ext_param_indication.as_ref().unwrap();
+        param_indication.set_automation(1, CLAP_PARAM_INDICATION_AUTOMATION_PLAYING, Some(RED));
+
+        let mut handle = bed.plugin();
+        let plugin = unsafe { handle.plugin() };
+        assert_eq!(
+            plugin.automation,
+            Some((
+                ClapId::from(1),
+                AutomationState::Playing,
+                Some(Color::rgb(0xff, 0, 0))
+            ))
+        );
+
+        let param_indication = bed.ext_param_indication.as_ref().unwrap();
+        param_indication.set_automation(2, CLAP_PARAM_INDICATION_AUTOMATION_OVERRIDING, None);
+
+        let plugin = unsafe { handle.plugin() };
+        assert_eq!(
+            plugin.automation,
+            Some((ClapId::from(2), AutomationState::Overriding, None))
+        );
+    }
+}
+
+#[test]
+fn param_indication_set_automation() {
+    TestConfig::default().test::<Plug>(CheckSetAutomation);
+}
+
+struct CheckInvalid;
+
+impl Test<Plug> for CheckInvalid {
+    fn test(self, bed: &mut TestBed<Plug>) {
+        let param_indication = bed.ext_param_indication.as_ref().unwrap();
+        param_indication.set_mapping(CLAP_INVALID_ID, true, None, None, None);
+        param_indication.set_automation(CLAP_INVALID_ID, 0, None);
+        param_indication.set_automation(1, 99, None);
+
+        let mut handle = bed.plugin();
+        let plugin = unsafe { handle.plugin() };
+        assert_eq!(plugin.mapping, None);
+        assert_eq!(plugin.automation, None);
+    }
+}
+
+#[test]
+fn param_indication_invalid() {
+    TestConfig::default().test::<Plug>(CheckInvalid);
+}
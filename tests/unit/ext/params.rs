@@ -20,6 +20,12 @@ use crate::{
     shims::plugin::ShimPlugin,
 };
 
+mod format;
+mod modulation;
+mod smoothing;
+mod store;
+mod typed;
+
 #[test]
 fn no_impl_params() {
     let bed = TestBed::<ShimPlugin>::new(&TestConfig::default());
@@ -134,13 +140,13 @@ impl Params<Plug> for PlugParams {
         Ok(param_value_text.parse()?)
     }
 
-    fn flush_inactive(plugin: &Plug, _: &InputEvents, _: &OutputEvents) {
+    fn flush_inactive(plugin: &Plug, _: &InputEvents, _: &mut OutputEvents) {
         unsafe {
             *plugin.call_flush.get() = true;
         }
     }
 
-    fn flush(audio_thread: &<Plug as Plugin>::AudioThread, _: &InputEvents, _: &OutputEvents) {
+    fn flush(audio_thread: &<Plug as Plugin>::AudioThread, _: &InputEvents, _: &mut OutputEvents) {
         let mut call = audio_thread.call_flush.lock().unwrap();
         *call = true;
     }
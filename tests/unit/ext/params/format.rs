@@ -0,0 +1,149 @@
+use clap_clap::{
+    Error,
+    ext::{
+        self,
+        params::{
+            self, Decibels, FormatError, Hertz, Labels, Mapping, Pan, Percent, Seconds, Semitones,
+            ValueFormat,
+        },
+    },
+};
+
+#[test]
+fn decibels() {
+    assert_eq!(Decibels.to_text(1.0), "0.00 dB");
+    assert_eq!(Decibels.to_text(0.5), "-6.02 dB");
+    assert_eq!(Decibels.to_text(0.0), "-inf dB");
+    assert_eq!(Decibels.to_value("-inf dB").unwrap(), 0.0);
+    assert_eq!(Decibels.to_value("0 dB").unwrap(), 1.0);
+    assert!((Decibels.to_value("-6.02db").unwrap() - 0.5).abs() < 1e-3);
+    assert!((Decibels.to_value(" 20").unwrap() - 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn hertz() {
+    assert_eq!(Hertz.to_text(440.0), "440.0 Hz");
+    assert_eq!(Hertz.to_text(12000.0), "12.00 kHz");
+    assert_eq!(Hertz.to_value("440").unwrap(), 440.0);
+    assert_eq!(Hertz.to_value("440hz").unwrap(), 440.0);
+    assert_eq!(Hertz.to_value("1.5 kHz").unwrap(), 1500.0);
+    assert_eq!(Hertz.to_value("2K").unwrap(), 2000.0);
+}
+
+#[test]
+fn seconds() {
+    assert_eq!(Seconds.to_text(0.25), "250.0 ms");
+    assert_eq!(Seconds.to_text(1.5), "1.50 s");
+    assert_eq!(Seconds.to_value("250 ms").unwrap(), 0.25);
+    assert_eq!(Seconds.to_value("2s").unwrap(), 2.0);
+    assert_eq!(Seconds.to_value("0.1").unwrap(), 0.1);
+}
+
+#[test]
+fn percent() {
+    assert_eq!(Percent.to_text(0.5), "50.0%");
+    assert_eq!(Percent.to_value("25 %").unwrap(), 0.25);
+    assert_eq!(Percent.to_value("100").unwrap(), 1.0);
+}
+
+#[test]
+fn pan() {
+    assert_eq!(Pan.to_text(-0.5), "L50");
+    assert_eq!(Pan.to_text(0.001), "C");
+    assert_eq!(Pan.to_text(1.0), "R100");
+    assert_eq!(Pan.to_value("L50").unwrap(), -0.5);
+    assert_eq!(Pan.to_value("25 r").unwrap(), 0.25);
+    assert_eq!(Pan.to_value("center").unwrap(), 0.0);
+    assert_eq!(Pan.to_value("-100").unwrap(), -1.0);
+    assert_eq!(Pan.to_value("L"), Err(FormatError::Empty));
+}
+
+#[test]
+fn semitones() {
+    assert_eq!(Semitones.to_text(7.0), "+7 st");
+    assert_eq!(Semitones.to_text(-12.0), "-12 st");
+    assert_eq!(Semitones.to_text(0.5), "+0.50 st");
+    assert_eq!(Semitones.to_value("-12 st").unwrap(), -12.0);
+    assert_eq!(Semitones.to_value("+3 semitones").unwrap(), 3.0);
+}
+
+#[test]
+fn labels() {
+    let labels = Labels(&["Low", "Mid", "High"]);
+
+    assert_eq!(labels.to_text(0.0), "Low");
+    assert_eq!(labels.to_text(1.6), "High");
+    assert_eq!(labels.to_text(9.0), "High");
+    assert_eq!(labels.to_value(" mid ").unwrap(), 1.0);
+    assert_eq!(
+        labels.to_value("Top"),
+        Err(FormatError::UnknownLabel("Top".to_string()))
+    );
+}
+
+#[test]
+fn invalid_number() {
+    assert_eq!(
+        Hertz.to_value("loud Hz"),
+        Err(FormatError::InvalidNumber("loud".to_string()))
+    );
+    assert_eq!(Seconds.to_value("  "), Err(FormatError::Empty));
+}
+
+#[test]
+fn error_conversion() {
+    let e = Hertz.to_value("x").unwrap_err();
+
+    assert_eq!(
+        params::Error::from(e.clone()),
+        params::Error::Format(e.clone())
+    );
+    assert!(matches!(
+        Error::from(e),
+        Error::Extension(ext::Error::Params(params::Error::Format(_)))
+    ));
+}
+
+#[test]
+fn write_text() {
+    let mut buf = [0; 8];
+    Decibels.write_text(0.0, &mut buf).unwrap();
+    assert_eq!(&buf, b"-inf dB\0");
+
+    let mut short = [0; 4];
+    assert!(Decibels.write_text(0.0, &mut short).is_err());
+}
+
+#[test]
+fn mapping_linear() {
+    assert_eq!(Mapping::Linear.normalize(5.0, 0.0, 10.0), 0.5);
+    assert_eq!(Mapping::Linear.normalize(15.0, 0.0, 10.0), 1.0);
+    assert_eq!(Mapping::Linear.denormalize(0.25, -1.0, 1.0), -0.5);
+    assert_eq!(Mapping::Linear.normalize(1.0, 1.0, 1.0), 0.0);
+}
+
+#[test]
+fn mapping_skewed() {
+    let mapping = Mapping::Skewed(2.0);
+
+    assert_eq!(mapping.denormalize(0.5, 0.0, 100.0), 25.0);
+    assert_eq!(mapping.normalize(25.0, 0.0, 100.0), 0.5);
+}
+
+#[test]
+fn mapping_with_center() {
+    let mapping = Mapping::with_center(10.0, 1010.0, 110.0);
+
+    assert!((mapping.denormalize(0.5, 10.0, 1010.0) - 110.0).abs() < 1e-9);
+    assert!((mapping.normalize(110.0, 10.0, 1010.0) - 0.5).abs() < 1e-9);
+    assert_eq!(Mapping::with_center(0.0, 1.0, 2.0), Mapping::Linear);
+}
+
+#[test]
+fn mapping_logarithmic() {
+    let mapping = Mapping::Logarithmic;
+
+    assert!((mapping.denormalize(0.5, 20.0, 20000.0) - 632.455).abs() < 1e-3);
+    assert!((mapping.normalize(200.0, 20.0, 2000.0) - 0.5).abs() < 1e-12);
+    assert_eq!(mapping.denormalize(2.0, 20.0, 20000.0), 20000.0);
+}
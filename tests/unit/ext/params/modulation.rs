@@ -0,0 +1,249 @@
+use clap_clap::{
+    events::{EventBuilder, EventQueue, ParamMod, ParamModBuilder, ParamValue},
+    ext::params::{
+        InfoFlags, ParamInfo,
+        modulation::{ModRouter, NoteAddress, Voice, targets},
+    },
+    id::ClapId,
+};
+
+const CUTOFF: u16 = 1;
+const GAIN: u16 = 2;
+const SHAPE: u16 = 3;
+const LEVEL: u16 = 4;
+
+fn info(id: u16, flags: u32) -> ParamInfo {
+    ParamInfo {
+        id: ClapId::from(id),
+        flags,
+        name: format!("param {id}"),
+        module: String::new(),
+        min_value: 0.0,
+        max_value: 1.0,
+        default_value: 0.0,
+    }
+}
+
+fn router(voice_count: usize) -> ModRouter {
+    let per_note_id = InfoFlags::Modulatable as u32 | InfoFlags::ModulatablePerNoteId as u32;
+    ModRouter::new(
+        voice_count,
+        [
+            info(GAIN, per_note_id | InfoFlags::ModulatablePerKey as u32),
+            info(SHAPE, InfoFlags::Automatable as u32),
+            info(
+                CUTOFF,
+                per_note_id | InfoFlags::ModulatablePerChannel as u32,
+            ),
+            info(LEVEL, InfoFlags::Modulatable as u32),
+        ],
+    )
+}
+
+fn param_mod(id: u16, address: NoteAddress, amount: f64) -> ParamModBuilder {
+    ParamMod::build()
+        .param_id(ClapId::from(id))
+        .note_id(address.note_id)
+        .port_index(address.port_index)
+        .channel(address.channel)
+        .key(address.key)
+        .amount(amount)
+}
+
+// Voices: 0 and 2 play on channel 0, voice 1 plays on channel 1, voice 3 is
+// free.
+fn voices() -> [Option<NoteAddress>; 4] {
+    [
+        Some(NoteAddress::new(10, 0, 0, 60)),
+        Some(NoteAddress::new(11, 0, 1, 62)),
+        Some(NoteAddress::new(12, 0, 0, 64)),
+        None,
+    ]
+}
+
+#[test]
+fn address_matches() {
+    let note = NoteAddress::new(10, 0, 3, 60);
+
+    assert!(NoteAddress::ALL.matches(&note));
+    assert!(NoteAddress::ALL.is_global());
+    assert!(NoteAddress::new(-1, -1, 3, -1).matches(&note));
+    assert!(!NoteAddress::new(-1, -1, 3, 61).matches(&note));
+    assert!(!NoteAddress::new(11, -1, -1, -1).matches(&note));
+    assert!(!note.is_global());
+}
+
+#[test]
+fn address_from_param_value() {
+    let event = ParamValue::build()
+        .note_id(5)
+        .port_index(-1)
+        .channel(2)
+        .key(-1);
+
+    assert_eq!(
+        NoteAddress::from(&event.event()),
+        NoteAddress::new(5, -1, 2, -1)
+    );
+}
+
+#[test]
+fn resolve_targets() {
+    let voices = voices();
+
+    let by_channel: Vec<_> = targets(&voices, NoteAddress::new(-1, -1, 0, -1)).collect();
+    assert_eq!(by_channel, [0, 2]);
+    let by_note_id: Vec<_> = targets(&voices, NoteAddress::new(11, -1, -1, -1)).collect();
+    assert_eq!(by_note_id, [1]);
+    assert_eq!(targets(&voices, NoteAddress::ALL).count(), 3);
+}
+
+#[test]
+fn modulatable_params_only() {
+    let router = router(4);
+
+    assert_eq!(router.voice_count(), 4);
+    assert!(router.contains(ClapId::from(CUTOFF)));
+    assert!(router.contains(ClapId::from(GAIN)));
+    assert!(!router.contains(ClapId::from(SHAPE)));
+    assert!(router.contains(ClapId::from(LEVEL)));
+}
+
+#[test]
+fn per_voice_offsets() {
+    let voices = voices();
+    let mut router = router(voices.len());
+
+    let event = param_mod(CUTOFF, NoteAddress::new(-1, -1, 0, -1), 0.25);
+    router.param_mod(&voices, &event.event());
+    let event = param_mod(GAIN, NoteAddress::new(11, -1, -1, -1), -0.5);
+    router.param_mod(&voices, &event.event());
+
+    let cutoff = ClapId::from(CUTOFF);
+    let gain = ClapId::from(GAIN);
+    assert_eq!(router.offset(0, cutoff), 0.25);
+    assert_eq!(router.offset(1, cutoff), 0.0);
+    assert_eq!(router.offset(2, cutoff), 0.25);
+    assert_eq!(router.offset(1, gain), -0.5);
+    assert_eq!(router.offset(0, gain), 0.0);
+    assert_eq!(router.offset(3, gain), 0.0);
+}
+
+#[test]
+fn global_offset() {
+    let voices = voices();
+    let mut router = router(voices.len());
+
+    let event = param_mod(CUTOFF, NoteAddress::ALL, 0.1);
+    router.param_mod(&voices, &event.event());
+    let event = param_mod(CUTOFF, NoteAddress::new(10, -1, -1, -1), 0.2);
+    router.param_mod(&voices, &event.event());
+
+    let cutoff = ClapId::from(CUTOFF);
+    assert_eq!(router.global_offset(cutoff), 0.1);
+    assert_eq!(router.voice_offset(0, cutoff), 0.2);
+    assert_eq!(router.offset(0, cutoff), 0.1 + 0.2);
+    // Free voices get the global offset too.
+    assert_eq!(router.offset(3, cutoff), 0.1);
+}
+
+#[test]
+fn ignore_unknown_params_and_voices() {
+    let voices = voices();
+    let mut router = router(2);
+
+    let event = param_mod(SHAPE, NoteAddress::ALL, 1.0);
+    router.param_mod(&voices, &event.event());
+    let event = param_mod(CUTOFF, NoteAddress::new(12, -1, -1, -1), 1.0);
+    router.param_mod(&voices, &event.event());
+
+    assert_eq!(router.offset(0, ClapId::from(SHAPE)), 0.0);
+    assert_eq!(router.offset(2, ClapId::from(CUTOFF)), 0.0);
+    assert_eq!(router, self::router(2));
+}
+
+#[test]
+fn unsupported_addressing() {
+    let voices = voices();
+    let mut router = router(voices.len());
+
+    // The gain can't be modulated per channel: the event is global.
+    let event = param_mod(GAIN, NoteAddress::new(-1, -1, 1, -1), 0.5);
+    router.param_mod(&voices, &event.event());
+    // The cutoff can't be modulated per key: only the note id is matched.
+    let event = param_mod(CUTOFF, NoteAddress::new(10, -1, -1, 62), 0.25);
+    router.param_mod(&voices, &event.event());
+    // The level can't be modulated per voice at all.
+    let event = param_mod(LEVEL, NoteAddress::new(12, 0, 0, 64), 0.75);
+    router.param_mod(&voices, &event.event());
+
+    assert_eq!(router.global_offset(ClapId::from(GAIN)), 0.5);
+    assert_eq!(router.voice_offset(0, ClapId::from(CUTOFF)), 0.25);
+    assert_eq!(router.voice_offset(1, ClapId::from(CUTOFF)), 0.0);
+    assert_eq!(router.global_offset(ClapId::from(LEVEL)), 0.75);
+    assert_eq!(router.voice_offset(2, ClapId::from(LEVEL)), 0.0);
+}
+
+#[test]
+fn note_end_clears_offsets() {
+    let voices = voices();
+    let mut router = router(voices.len());
+    let mut events = EventQueue::new();
+    for note_id in [10, 11] {
+        let event = param_mod(CUTOFF, NoteAddress::new(note_id, -1, -1, -1), 0.5);
+        events.try_push(event.event()).unwrap();
+    }
+    for event in events.iter() {
+        router.apply(&voices, &event);
+    }
+
+    // The voice is freed before the plugin sends NoteEnd.
+    router.note_end(0);
+    router.note_end(voices.len());
+
+    assert_eq!(router.offset(0, ClapId::from(CUTOFF)), 0.0);
+    assert_eq!(router.offset(1, ClapId::from(CUTOFF)), 0.5);
+}
+
+#[test]
+fn clear() {
+    let voices = voices();
+    let mut router = router(voices.len());
+    let event = param_mod(GAIN, NoteAddress::ALL, 0.5);
+    router.param_mod(&voices, &event.event());
+    let event = param_mod(GAIN, NoteAddress::new(-1, -1, -1, 62), 0.5);
+    router.param_mod(&voices, &event.event());
+
+    router.clear_voice(1);
+    assert_eq!(router.offset(1, ClapId::from(GAIN)), 0.5);
+    router.clear();
+    assert_eq!(router.offset(1, ClapId::from(GAIN)), 0.0);
+}
+
+struct SynthVoice {
+    note: NoteAddress,
+    active: bool,
+}
+
+impl Voice for SynthVoice {
+    fn note(&self) -> Option<NoteAddress> {
+        self.active.then_some(self.note)
+    }
+}
+
+#[test]
+fn custom_voice() {
+    let voices = [
+        SynthVoice {
+            note: NoteAddress::new(1, 0, 0, 60),
+            active: false,
+        },
+        SynthVoice {
+            note: NoteAddress::new(2, 0, 0, 60),
+            active: true,
+        },
+    ];
+
+    let keys: Vec<_> = targets(&voices, NoteAddress::new(-1, -1, -1, 60)).collect();
+    assert_eq!(keys, [1]);
+}
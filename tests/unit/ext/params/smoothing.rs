@@ -0,0 +1,101 @@
+use clap_clap::{
+    events::{EventBuilder, ParamValue},
+    ext::params::smoothing::{Smoother, Smoothing},
+    id::ClapId,
+};
+
+#[test]
+fn steps_from_sample_rate() {
+    assert_eq!(Smoother::new(Smoothing::Linear, 0.01, 48000.0).steps(), 480);
+    assert_eq!(Smoother::new(Smoothing::Linear, 0.0, 48000.0).steps(), 0);
+}
+
+#[test]
+fn reset() {
+    let mut smoother = Smoother::new(Smoothing::Exponential, 0.1, 100.0);
+    smoother.set_target(1.0);
+    smoother.reset(0.5);
+
+    assert!(!smoother.is_smoothing());
+    assert_eq!(smoother.tick(), 0.5);
+}
+
+#[test]
+fn linear() {
+    let mut smoother = Smoother::new(Smoothing::Linear, 0.04, 100.0);
+    smoother.set_target(1.0);
+
+    let values: Vec<_> = (0..5).map(|_| smoother.tick()).collect();
+    assert_eq!(values, [0.25, 0.5, 0.75, 1.0, 1.0]);
+    assert!(!smoother.is_smoothing());
+}
+
+#[test]
+fn exponential() {
+    let mut smoother = Smoother::new(Smoothing::Exponential, 0.1, 100.0);
+    smoother.reset(1.0);
+    smoother.set_target(0.0);
+
+    let values: Vec<_> = (0..10).map(|_| smoother.tick()).collect();
+    assert!(values.windows(2).all(|w| w[1] < w[0]));
+    // Steep at first.
+    assert!(values[0] < 0.9);
+    assert!((values[8] - 0.001f64.powf(0.9)).abs() < 1e-12);
+    assert_eq!(values[9], 0.0);
+}
+
+#[test]
+fn logarithmic() {
+    let mut smoother = Smoother::new(Smoothing::Logarithmic, 0.03, 100.0);
+    smoother.reset(100.0);
+    smoother.set_target(10000.0);
+
+    assert!((smoother.tick() - 100.0 * 100.0f64.powf(1.0 / 3.0)).abs() < 1e-9);
+    assert!((smoother.tick() - 100.0 * 100.0f64.powf(2.0 / 3.0)).abs() < 1e-9);
+    assert_eq!(smoother.tick(), 10000.0);
+}
+
+#[test]
+fn logarithmic_jumps_through_zero() {
+    let mut smoother = Smoother::new(Smoothing::Logarithmic, 0.03, 100.0);
+    smoother.set_target(440.0);
+
+    assert!(!smoother.is_smoothing());
+    assert_eq!(smoother.current(), 440.0);
+}
+
+#[test]
+fn retarget() {
+    let mut smoother = Smoother::new(Smoothing::Linear, 0.04, 100.0);
+    smoother.set_target(1.0);
+    smoother.tick();
+    smoother.tick();
+    smoother.set_target(0.0);
+
+    let values: Vec<_> = (0..4).map(|_| smoother.tick()).collect();
+    assert_eq!(values, [0.375, 0.25, 0.125, 0.0]);
+}
+
+#[test]
+fn apply_param_value() {
+    let mut smoother = Smoother::new(Smoothing::Linear, 0.02, 100.0);
+    let event = ParamValue::build().param_id(ClapId::from(1)).value(2.0);
+    smoother.apply_param_value(&event.event());
+
+    assert_eq!(smoother.target(), 2.0);
+    assert_eq!(smoother.tick(), 1.0);
+}
+
+#[test]
+fn fill() {
+    let mut smoother = Smoother::new(Smoothing::Linear, 0.04, 100.0);
+    smoother.set_target(-1.0);
+
+    let mut block = [0.0; 6];
+    smoother.fill(&mut block);
+    assert_eq!(block, [-0.25, -0.5, -0.75, -1.0, -1.0, -1.0]);
+
+    let mut block = [0.0; 3];
+    smoother.fill_f64(&mut block);
+    assert_eq!(block, [-1.0; 3]);
+}
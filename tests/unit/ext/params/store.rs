@@ -0,0 +1,202 @@
+use std::{sync::Arc, thread};
+
+use clap_clap::{
+    events::{EventBuilder, EventQueue, EventRef, GestureKind, ParamValue, ParamValueBuilder},
+    ext::params::{Error, ParamInfo, ParamStore},
+    id::ClapId,
+};
+
+fn info(id: u16, default_value: f64) -> ParamInfo {
+    ParamInfo {
+        id: ClapId::from(id),
+        flags: 0,
+        name: format!("param {id}"),
+        module: String::new(),
+        min_value: 0.0,
+        max_value: 10.0,
+        default_value,
+    }
+}
+
+fn store() -> ParamStore {
+    ParamStore::new([info(5, 0.5), info(1, 1.0), info(3, 3.0)])
+}
+
+fn param_value(id: u16, value: f64) -> ParamValueBuilder {
+    ParamValue::build().param_id(ClapId::from(id)).value(value)
+}
+
+#[test]
+fn default_values() {
+    let store = store();
+
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.get(ClapId::from(1)), Some(1.0));
+    assert_eq!(store.get(ClapId::from(5)), Some(0.5));
+    assert_eq!(store.get(ClapId::from(2)), None);
+    assert!(!store.contains(ClapId::invalid_id()));
+}
+
+#[test]
+fn iter_ordered_by_id() {
+    let ids: Vec<_> = store().iter().map(|(id, _)| id).collect();
+
+    assert_eq!(ids, [1, 3, 5].map(ClapId::from));
+}
+
+#[test]
+#[should_panic(expected = "duplicate parameter id: 3")]
+fn duplicate_id() {
+    ParamStore::new([info(3, 0.0), info(1, 0.0), info(3, 1.0)]);
+}
+
+#[test]
+fn set_unknown_param() {
+    let store = store();
+
+    assert_eq!(
+        store.set(ClapId::from(2), 1.0),
+        Err(Error::UnknownParam(ClapId::from(2)))
+    );
+    assert_eq!(store.set(ClapId::from(3), 7.0), Ok(()));
+    assert_eq!(store.get(ClapId::from(3)), Some(7.0));
+    assert!(!store.is_dirty(ClapId::from(3)));
+    assert_eq!(store.pop_change(), None);
+}
+
+#[test]
+fn apply_queues_changes() {
+    let store = store();
+    let mut in_events = EventQueue::new();
+    in_events.try_push(param_value(3, 4.0).event()).unwrap();
+    in_events.try_push(param_value(2, 4.0).event()).unwrap();
+    in_events.try_push(param_value(1, 2.0).event()).unwrap();
+
+    store.apply(&in_events.input_events());
+
+    assert_eq!(store.get(ClapId::from(3)), Some(4.0));
+    assert_eq!(store.get(ClapId::from(1)), Some(2.0));
+    assert_eq!(store.pop_change(), Some((ClapId::from(3), 4.0)));
+    assert_eq!(store.pop_change(), Some((ClapId::from(1), 2.0)));
+    assert_eq!(store.pop_change(), None);
+}
+
+#[test]
+fn queue_full() {
+    let store = ParamStore::with_queue_capacity([info(1, 0.0)], 2);
+
+    for value in [1.0, 2.0, 3.0] {
+        store.apply_param_value(&param_value(1, value).event());
+    }
+
+    assert_eq!(store.get(ClapId::from(1)), Some(3.0));
+    assert_eq!(store.pop_change(), Some((ClapId::from(1), 1.0)));
+    assert_eq!(store.pop_change(), Some((ClapId::from(1), 2.0)));
+    assert_eq!(store.pop_change(), None);
+
+    store.apply_param_value(&param_value(1, 4.0).event());
+    assert_eq!(store.pop_change(), Some((ClapId::from(1), 4.0)));
+}
+
+#[test]
+fn send_gesture() {
+    let store = store();
+    store.begin_gesture(ClapId::from(5)).unwrap();
+    store.change(ClapId::from(5), 0.7).unwrap();
+    store.end_gesture(ClapId::from(5)).unwrap();
+    store.change(ClapId::from(1), 0.1).unwrap();
+    assert!(store.is_dirty(ClapId::from(5)));
+
+    let mut out_events = EventQueue::new();
+    store.send(&mut out_events.output_events()).unwrap();
+
+    let events: Vec<_> = out_events.iter().collect();
+    assert_eq!(events.len(), 4);
+    let EventRef::ParamValue(value) = events[0] else {
+        panic!("{:?}", events[0]);
+    };
+    assert_eq!((value.param_id(), value.value()), (ClapId::from(1), 0.1));
+    assert_eq!(value.note_id(), -1);
+    let EventRef::ParamGestureBegin(begin) = events[1] else {
+        panic!("{:?}", events[1]);
+    };
+    assert_eq!(begin.param_id(), ClapId::from(5));
+    let EventRef::ParamValue(value) = events[2] else {
+        panic!("{:?}", events[2]);
+    };
+    assert_eq!((value.param_id(), value.value()), (ClapId::from(5), 0.7));
+    let EventRef::ParamGestureEnd(end) = events[3] else {
+        panic!("{:?}", events[3]);
+    };
+    assert_eq!(end.kind, GestureKind::End);
+
+    assert!(!store.is_dirty(ClapId::from(5)));
+    assert_eq!(store.pop_change(), None);
+}
+
+#[test]
+fn send_failure_keeps_changes_dirty() {
+    let store = store();
+    store.begin_gesture(ClapId::from(3)).unwrap();
+    store.change(ClapId::from(3), 2.0).unwrap();
+
+    // Room for the gesture only.
+    let mut full = EventQueue::with_capacity(1024, 1);
+    assert!(store.send(&mut full.output_events()).is_err());
+    assert_eq!(full.len(), 1);
+    assert!(store.is_dirty(ClapId::from(3)));
+
+    let mut out_events = EventQueue::new();
+    store.send(&mut out_events.output_events()).unwrap();
+    assert_eq!(out_events.len(), 1);
+    assert!(matches!(
+        out_events.iter().next(),
+        Some(EventRef::ParamValue(_))
+    ));
+}
+
+#[test]
+fn flush_applies_and_sends() {
+    let store = store();
+    store.change(ClapId::from(1), 9.0).unwrap();
+    let mut in_events = EventQueue::new();
+    in_events.try_push(param_value(3, 6.0).event()).unwrap();
+
+    let mut out_events = EventQueue::new();
+    store.flush(&in_events.input_events(), &mut out_events.output_events());
+
+    assert_eq!(store.get(ClapId::from(3)), Some(6.0));
+    assert_eq!(store.pop_change(), Some((ClapId::from(3), 6.0)));
+    assert_eq!(out_events.len(), 1);
+    assert!(!store.is_dirty(ClapId::from(1)));
+}
+
+#[test]
+fn changes_across_threads() {
+    const COUNT: u32 = 10_000;
+    let store = Arc::new(ParamStore::with_queue_capacity([info(1, 0.0)], 16));
+
+    let audio_thread = {
+        let store = store.clone();
+        thread::spawn(move || {
+            for value in 0..COUNT {
+                store.apply_param_value(&param_value(1, f64::from(value)).event());
+            }
+        })
+    };
+
+    let mut last = -1.0;
+    while !audio_thread.is_finished() || last < 0.0 {
+        if let Some((id, value)) = store.pop_change() {
+            assert_eq!(id, ClapId::from(1));
+            assert!(value > last);
+            last = value;
+        }
+    }
+    audio_thread.join().unwrap();
+    while let Some((_, value)) = store.pop_change() {
+        assert!(value > last);
+        last = value;
+    }
+    assert_eq!(store.get(ClapId::from(1)), Some(f64::from(COUNT - 1)));
+}
@@ -0,0 +1,370 @@
+use std::sync::Arc;
+
+use clap_clap::{
+    Error,
+    events::{EventBuilder, EventQueue, ParamValue},
+    ext::{
+        Extensions,
+        params::{
+            BoolParam, Decibels, EnumParam, FloatParam, InfoFlags, IntParam, Param, ParamEnum,
+            ParamSet, Params, ParamsOf, Semitones,
+        },
+    },
+    id::ClapId,
+    plugin::{AudioThread, Plugin},
+    prelude::{Process, Status, Status::Continue},
+};
+
+use crate::ext::{TestBed, TestConfig, TestPlugin};
+
+#[derive(Debug, Copy, Clone, PartialEq, ParamEnum)]
+enum Shape {
+    Sine,
+    #[param(name = "Saw Up")]
+    Saw,
+    Square,
+}
+
+#[derive(Params)]
+struct SynthParams {
+    #[param(id = 7, name = "Cutoff", module = "Filter", range = 20.0..=20000.0, default = 440.0)]
+    #[param(unit = "Hz", flags = Automatable | Modulatable)]
+    cutoff: FloatParam,
+    #[param(id = 0x10, range = -24..=24, unit = "st")]
+    transpose: IntParam,
+    #[param(id = 2, default = true, flags = InfoFlags::Bypass)]
+    bypass: BoolParam,
+    #[param(id = 3, name = "Shape", default = Shape::Saw)]
+    shape: EnumParam<Shape>,
+    level: FloatParam,
+}
+
+#[test]
+fn count() {
+    assert_eq!(SynthParams::default().count(), 5);
+}
+
+#[test]
+fn info_float() {
+    let params = SynthParams::default();
+    let info = params.param(0).unwrap().info();
+
+    assert_eq!(info.id, ClapId::from(7));
+    assert_eq!(info.name, "Cutoff");
+    assert_eq!(info.module, "Filter");
+    assert_eq!(info.min_value, 20.0);
+    assert_eq!(info.max_value, 20000.0);
+    assert_eq!(info.default_value, 440.0);
+    assert_eq!(
+        info.flags,
+        InfoFlags::Automatable as u32 | InfoFlags::Modulatable as u32
+    );
+    assert_eq!(params.cutoff.get(), 440.0);
+}
+
+#[test]
+fn info_int() {
+    let params = SynthParams::default();
+    let info = params.param(1).unwrap().info();
+
+    assert_eq!(info.id, ClapId::from(16));
+    assert_eq!(info.name, "transpose");
+    assert_eq!((info.min_value, info.max_value), (-24.0, 24.0));
+    assert_eq!(info.default_value, 0.0);
+    assert!(InfoFlags::Stepped.is_set(info.flags));
+}
+
+#[test]
+fn info_bool() {
+    let params = SynthParams::default();
+    let info = params.param(2).unwrap().info();
+
+    assert_eq!((info.min_value, info.max_value), (0.0, 1.0));
+    assert_eq!(info.default_value, 1.0);
+    assert!(InfoFlags::Stepped.is_set(info.flags));
+    assert!(InfoFlags::Bypass.is_set(info.flags));
+    assert!(params.bypass.get());
+}
+
+#[test]
+fn info_enum() {
+    let params = SynthParams::default();
+    let info = params.param(3).unwrap().info();
+
+    assert_eq!((info.min_value, info.max_value), (0.0, 2.0));
+    assert_eq!(info.default_value, 1.0);
+    assert!(InfoFlags::Stepped.is_set(info.flags));
+    assert!(InfoFlags::Enum.is_set(info.flags));
+    assert_eq!(params.shape.get(), Shape::Saw);
+}
+
+#[test]
+fn hashed_id_is_stable() {
+    let id = SynthParams::default().level.info().id;
+
+    assert!(id.is_valid());
+    assert_eq!(SynthParams::default().level.info().id, id);
+    assert!(SynthParams::default().param_by_id(id).is_some());
+}
+
+#[test]
+fn param_by_id() {
+    let params = SynthParams::default();
+
+    assert_eq!(params.param_by_id(ClapId::from(3)).unwrap().value(), 1.0);
+    assert!(params.param_by_id(ClapId::from(4)).is_none());
+    assert!(params.param(5).is_none());
+}
+
+#[test]
+fn set_clamps_to_range() {
+    let params = SynthParams::default();
+
+    params.cutoff.set_value(1.0);
+    assert_eq!(params.cutoff.get(), 20.0);
+    params.transpose.set_value(2.6);
+    assert_eq!(params.transpose.get(), 3);
+    params.transpose.set(100);
+    assert_eq!(params.transpose.get(), 24);
+    params.shape.set_value(7.0);
+    assert_eq!(params.shape.get(), Shape::Square);
+    params.bypass.set_value(0.2);
+    assert!(!params.bypass.get());
+}
+
+#[test]
+#[should_panic(expected = "invalid parameter range")]
+fn float_range_reversed() {
+    let _ = FloatParam::new(ClapId::from(1), "Level").range(2.0..=0.0);
+}
+
+#[test]
+#[should_panic(expected = "invalid parameter range")]
+fn float_range_nan() {
+    let _ = FloatParam::new(ClapId::from(1), "Level").range(0.0..=f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "invalid parameter range")]
+fn int_range_reversed() {
+    let (min, max) = (4, -4);
+    let _ = IntParam::new(ClapId::from(1), "Steps").range(min..=max);
+}
+
+#[test]
+fn default_clamps_to_range() {
+    let cutoff = FloatParam::new(ClapId::from(1), "Cutoff")
+        .range(20.0..=20000.0)
+        .default_value(1.0);
+    assert_eq!(cutoff.info().default_value, 20.0);
+    assert_eq!(cutoff.get(), 20.0);
+
+    let transpose = IntParam::new(ClapId::from(2), "Transpose")
+        .range(-24..=24)
+        .default_value(100);
+    assert_eq!(transpose.info().default_value, 24.0);
+    assert_eq!(transpose.get(), 24);
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Empty {}
+
+impl ParamEnum for Empty {
+    const VARIANTS: &'static [Self] = &[];
+
+    fn index(&self) -> usize {
+        match *self {}
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {}
+    }
+}
+
+#[test]
+#[should_panic(expected = "enum must have at least one variant")]
+fn enum_without_variants() {
+    let _ = EnumParam::<Empty>::new(ClapId::from(1), "Empty");
+}
+
+fn to_text(param: &dyn Param, value: f64) -> String {
+    let mut buf = [0; 32];
+    param.value_to_text(value, &mut buf).unwrap();
+    String::from_utf8(buf.to_vec())
+        .unwrap()
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+#[test]
+fn text_float() {
+    let params = SynthParams::default();
+
+    assert_eq!(to_text(&params.cutoff, 440.0), "440.00 Hz");
+    assert_eq!(params.cutoff.text_to_value("1000 Hz").unwrap(), 1000.0);
+    assert_eq!(params.cutoff.text_to_value(" 12.5").unwrap(), 12.5);
+    assert!(params.cutoff.text_to_value("loud").is_err());
+    assert_eq!(to_text(&params.level, 0.5), "0.50");
+}
+
+#[test]
+fn text_int() {
+    let params = SynthParams::default();
+
+    assert_eq!(to_text(&params.transpose, -12.0), "-12 st");
+    assert_eq!(params.transpose.text_to_value("7 st").unwrap(), 7.0);
+}
+
+#[test]
+fn text_bool() {
+    let params = SynthParams::default();
+
+    assert_eq!(to_text(&params.bypass, 1.0), "On");
+    assert_eq!(to_text(&params.bypass, 0.0), "Off");
+    assert_eq!(params.bypass.text_to_value("off").unwrap(), 0.0);
+    assert_eq!(params.bypass.text_to_value("True").unwrap(), 1.0);
+    assert!(params.bypass.text_to_value("maybe").is_err());
+}
+
+#[test]
+fn text_enum() {
+    let params = SynthParams::default();
+
+    assert_eq!(Shape::VARIANTS, [Shape::Sine, Shape::Saw, Shape::Square]);
+    assert_eq!(to_text(&params.shape, 1.0), "Saw Up");
+    assert_eq!(params.shape.text_to_value("Square").unwrap(), 2.0);
+    assert!(params.shape.text_to_value("Saw").is_err());
+}
+
+#[test]
+fn apply_param_value_events() {
+    let params = SynthParams::default();
+    let mut events = EventQueue::new();
+    let event = |id: u16, value: f64| {
+        ParamValue::build()
+            .param_id(ClapId::from(id))
+            .value(value)
+            .note_id(-1)
+            .port_index(-1)
+            .channel(-1)
+            .key(-1)
+    };
+    events.try_push(event(7, 1000.0).event()).unwrap();
+    events.try_push(event(2, 0.0).event()).unwrap();
+    events.try_push(event(8, 5.0).event()).unwrap();
+    // Per-voice automation is not applied.
+    events.try_push(event(0x10, 12.0).key(60).event()).unwrap();
+
+    params.apply(&events.input_events());
+
+    assert_eq!(params.cutoff.get(), 1000.0);
+    assert!(!params.bypass.get());
+    assert_eq!(params.transpose.get(), 0);
+    assert_eq!(params.shape.get(), Shape::Saw);
+}
+
+#[derive(Default)]
+struct Synth {
+    params: Arc<SynthParams>,
+}
+
+impl AsRef<SynthParams> for Synth {
+    fn as_ref(&self) -> &SynthParams {
+        &self.params
+    }
+}
+
+impl Plugin for Synth {
+    type AudioThread = SynthThread;
+    const ID: &'static str = "";
+    const NAME: &'static str = "";
+
+    fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<SynthThread, Error> {
+        Ok(SynthThread {
+            params: self.params.clone(),
+        })
+    }
+}
+
+impl TestPlugin for Synth {}
+
+impl Extensions<Self> for Synth {
+    fn params() -> Option<impl Params<Self>> {
+        Some(ParamsOf::<SynthParams>::new())
+    }
+}
+
+struct SynthThread {
+    params: Arc<SynthParams>,
+}
+
+impl AsRef<SynthParams> for SynthThread {
+    fn as_ref(&self) -> &SynthParams {
+        &self.params
+    }
+}
+
+impl AudioThread<Synth> for SynthThread {
+    fn process(&mut self, _: &mut Process) -> Result<Status, Error> {
+        Ok(Continue)
+    }
+}
+
+#[test]
+fn params_of_info() {
+    let bed = TestBed::<Synth>::new(&TestConfig::default());
+    let params = bed.ext_params.as_ref().unwrap();
+
+    assert_eq!(params.count(), 5);
+    assert_eq!(
+        params.get_info(3).unwrap(),
+        *SynthParams::default().shape.info()
+    );
+    assert!(params.get_info(5).is_none());
+}
+
+#[test]
+fn params_of_value() {
+    let bed = &mut TestBed::<Synth>::new(&TestConfig::default());
+    {
+        let mut plugin = bed.plugin();
+        unsafe { plugin.plugin() }.params.cutoff.set(1000.0);
+    }
+    let params = bed.ext_params.as_ref().unwrap();
+
+    assert_eq!(params.get_value(ClapId::from(7)), Some(1000.0));
+    assert_eq!(params.get_value(ClapId::from(8)), None);
+}
+
+#[test]
+fn params_of_text() {
+    let bed = TestBed::<Synth>::new(&TestConfig::default());
+    let params = bed.ext_params.as_ref().unwrap();
+
+    let mut buf = [0; 8];
+    params
+        .value_to_text(ClapId::from(3), 0.0, &mut buf)
+        .unwrap();
+    assert_eq!(&buf, b"Sine\0\0\0\0");
+    assert_eq!(params.text_to_value(ClapId::from(16), "-3").unwrap(), -3.0);
+    assert!(params.text_to_value(ClapId::from(8), "0").is_err());
+}
+
+#[derive(Params)]
+struct Formatted {
+    #[param(id = 1, range = 0.0..=2.0, default = 1.0, format = Decibels)]
+    gain: FloatParam,
+    #[param(id = 2, range = -24..=24, format = Semitones)]
+    pitch: IntParam,
+}
+
+#[test]
+fn text_with_format() {
+    let params = Formatted::default();
+
+    assert_eq!(to_text(&params.gain, 1.0), "0.00 dB");
+    assert_eq!(params.gain.text_to_value("-inf dB").unwrap(), 0.0);
+    assert_eq!(to_text(&params.pitch, 6.7), "+7 st");
+    assert_eq!(params.pitch.text_to_value("-2.4 st").unwrap(), -2.0);
+    assert!(params.pitch.text_to_value("high").is_err());
+}
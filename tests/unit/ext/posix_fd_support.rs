@@ -0,0 +1,179 @@
+mod plugin_posix_fd_support {
+    use std::{ffi::c_int, marker::PhantomData};
+
+    use clap_clap::{
+        Error,
+        ext::{
+            Extensions,
+            posix_fd_support::{FdFlags, PosixFdSupport},
+        },
+        plugin::Plugin,
+    };
+
+    use crate::{
+        ext::{Test, TestBed, TestConfig, TestPlugin},
+        shims::plugin::ShimPlugin,
+    };
+
+    #[derive(Debug, Default)]
+    struct CheckNoExt<P> {
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+        fn test(self, bed: &mut TestBed<P>) {
+            if P::posix_fd_support().is_some() {
+                assert!(bed.ext_posix_fd_support.is_some());
+            } else {
+                assert!(bed.ext_posix_fd_support.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn no_posix_fd_support_shim() {
+        TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+    }
+
+    #[derive(Default)]
+    struct Plug {
+        events: Vec<(c_int, u32)>,
+    }
+
+    impl Plugin for Plug {
+        type AudioThread = ();
+        const ID: &'static str = "";
+        const NAME: &'static str = "";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(())
+        }
+    }
+
+    impl TestPlugin for Plug {}
+
+    impl Extensions<Self> for Plug {
+        fn posix_fd_support() -> Option<impl PosixFdSupport<Self>> {
+            Some(PlugPosixFdSupport)
+        }
+    }
+
+    struct PlugPosixFdSupport;
+
+    impl PosixFdSupport<Plug> for PlugPosixFdSupport {
+        fn on_fd(plugin: &mut Plug, fd: c_int, flags: u32) {
+            plugin.events.push((fd, flags));
+        }
+    }
+
+    #[test]
+    fn posix_fd_support_plug() {
+        TestConfig::default().test::<Plug>(CheckNoExt::default());
+    }
+
+    struct CheckOnFd;
+
+    impl Test<Plug> for CheckOnFd {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let posix_fd_support = bed.ext_posix_fd_support.as_ref().unwrap();
+            posix_fd_support.on_fd(3, FdFlags::Read.into());
+            posix_fd_support.on_fd(7, FdFlags::Write.set(FdFlags::Error as u32));
+
+            let mut handle = bed.plugin();
+            assert_eq!(
+                unsafe { handle.plugin() }.events,
+                vec![(3, 0b001), (7, 0b110)]
+            );
+        }
+    }
+
+    #[test]
+    fn posix_fd_support_on_fd() {
+        TestConfig::default().test::<Plug>(CheckOnFd);
+    }
+}
+
+mod host_posix_fd_support {
+    use std::pin::Pin;
+
+    use clap_clap::{
+        ext::posix_fd_support::{Error, FdFlags},
+        host,
+        host::Error::{Callback, ExtensionNotFound},
+    };
+
+    use crate::host::{ExtPosixFdSupportConfig, Test, TestBed, TestConfig};
+
+    struct CheckPosixFdSupportNotImpl {
+        error: host::Error,
+    }
+
+    impl Test for CheckPosixFdSupportNotImpl {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let err = host.get_extension().posix_fd_support().unwrap_err();
+            assert_eq!(err, self.error);
+        }
+    }
+
+    #[test]
+    fn posix_fd_support_not_impl() {
+        TestConfig::default().test(CheckPosixFdSupportNotImpl {
+            error: ExtensionNotFound("posix_fd_support"),
+        });
+    }
+
+    #[test]
+    fn posix_fd_support_null_callback() {
+        for (null_callback, name) in [
+            ((true, false, false), "register_fd"),
+            ((false, true, false), "modify_fd"),
+            ((false, false, true), "unregister_fd"),
+        ] {
+            TestConfig {
+                ext_posix_fd_support: Some(ExtPosixFdSupportConfig { null_callback }),
+                ..Default::default()
+            }
+            .test(CheckPosixFdSupportNotImpl {
+                error: Callback(name),
+            });
+        }
+    }
+
+    struct CheckRegisterFd;
+
+    impl Test for CheckRegisterFd {
+        fn test(self, mut bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.as_mut().host_mut() };
+            let posix_fd_support = host.get_extension().posix_fd_support().unwrap();
+
+            let read = FdFlags::Read as u32;
+            let read_write = FdFlags::Write.set(read);
+
+            posix_fd_support.register_fd(5, read_write).unwrap();
+            assert_eq!(
+                posix_fd_support.register_fd(5, read),
+                Err(Error::Register(5))
+            );
+            posix_fd_support.register_fd(6, read).unwrap();
+            posix_fd_support.modify_fd(5, read).unwrap();
+            assert_eq!(posix_fd_support.modify_fd(9, read), Err(Error::Modify(9)));
+            posix_fd_support.unregister_fd(6).unwrap();
+            assert_eq!(posix_fd_support.unregister_fd(6), Err(Error::Unregister(6)));
+
+            assert_eq!(
+                bed.ext_posix_fd_support.as_ref().unwrap().fds,
+                vec![(5, read)]
+            );
+        }
+    }
+
+    #[test]
+    fn posix_fd_support_register_fd() {
+        TestConfig {
+            ext_posix_fd_support: Some(ExtPosixFdSupportConfig::default()),
+            ..Default::default()
+        }
+        .test(CheckRegisterFd);
+    }
+}
@@ -0,0 +1,200 @@
+mod plugin_remote_controls {
+    use std::marker::PhantomData;
+
+    use clap_clap::{
+        Error,
+        ext::{
+            Extensions,
+            remote_controls::{RemoteControls, RemoteControlsPage},
+        },
+        id::ClapId,
+        plugin::Plugin,
+        string_sizes::CLAP_NAME_SIZE,
+    };
+
+    use crate::{
+        ext::{Test, TestBed, TestConfig, TestPlugin},
+        shims::plugin::ShimPlugin,
+    };
+
+    #[derive(Debug, Default)]
+    struct CheckNoExt<P> {
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+        fn test(self, bed: &mut TestBed<P>) {
+            if P::remote_controls().is_some() {
+                assert!(bed.ext_remote_controls.is_some());
+            } else {
+                assert!(bed.ext_remote_controls.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn no_remote_controls_shim() {
+        TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+    }
+
+    #[derive(Default)]
+    struct Plug {
+        pages: Vec<RemoteControlsPage>,
+    }
+
+    impl Plugin for Plug {
+        type AudioThread = ();
+        const ID: &'static str = "";
+        const NAME: &'static str = "";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(())
+        }
+    }
+
+    impl TestPlugin for Plug {}
+
+    impl Extensions<Self> for Plug {
+        fn remote_controls() -> Option<impl RemoteControls<Self>> {
+            Some(PlugRemoteControls)
+        }
+    }
+
+    struct PlugRemoteControls;
+
+    impl RemoteControls<Plug> for PlugRemoteControls {
+        fn count(plugin: &Plug) -> u32 {
+            plugin.pages.len() as u32
+        }
+
+        fn get(plugin: &Plug, page_index: u32) -> Option<RemoteControlsPage> {
+            plugin.pages.get(page_index as usize).cloned()
+        }
+    }
+
+    #[test]
+    fn remote_controls_plug() {
+        TestConfig::default().test::<Plug>(CheckNoExt::default());
+    }
+
+    struct CheckPages(Vec<RemoteControlsPage>);
+
+    impl Test<Plug> for CheckPages {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let mut handle = bed.plugin();
+            unsafe { handle.plugin() }.pages = self.0.clone();
+
+            let remote_controls = bed.ext_remote_controls.as_ref().unwrap();
+            assert_eq!(remote_controls.count(), self.0.len() as u32);
+            for (i, page) in self.0.iter().enumerate() {
+                let mut page = page.clone();
+                page.section_name.truncate(CLAP_NAME_SIZE - 1);
+                page.page_name.truncate(CLAP_NAME_SIZE - 1);
+
+                assert_eq!(remote_controls.get(i as u32), Some(page));
+            }
+            assert_eq!(remote_controls.get(self.0.len() as u32), None);
+        }
+    }
+
+    #[test]
+    fn remote_controls_get() {
+        TestConfig::default()
+            .test::<Plug>(CheckPages(vec![]))
+            .test::<Plug>(CheckPages(vec![
+                RemoteControlsPage::new(ClapId::from(0), "Oscillator")
+                    .section_name("Main")
+                    .param(0, ClapId::from(1))
+                    .param(7, ClapId::from(8)),
+                RemoteControlsPage::new(ClapId::from(1), "Preset Macros")
+                    .param(3, ClapId::from(100))
+                    .is_for_preset(true),
+            ]));
+    }
+
+    #[test]
+    fn remote_controls_get_long_names() {
+        TestConfig::default().test::<Plug>(CheckPages(vec![
+            RemoteControlsPage::new(ClapId::from(5), &"p".repeat(CLAP_NAME_SIZE + 1))
+                .section_name(&"s".repeat(CLAP_NAME_SIZE * 2)),
+        ]));
+    }
+}
+
+mod host_remote_controls {
+    use std::pin::Pin;
+
+    use clap_clap::{
+        host,
+        host::Error::{Callback, ExtensionNotFound},
+        id::ClapId,
+    };
+
+    use crate::host::{ExtRemoteControlsConfig, Test, TestBed, TestConfig};
+
+    struct CheckRemoteControlsNotImpl {
+        error: host::Error,
+    }
+
+    impl Test for CheckRemoteControlsNotImpl {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let err = host.get_extension().remote_controls().unwrap_err();
+            assert_eq!(err, self.error);
+        }
+    }
+
+    #[test]
+    fn remote_controls_not_impl() {
+        TestConfig::default().test(CheckRemoteControlsNotImpl {
+            error: ExtensionNotFound("remote_controls"),
+        });
+    }
+
+    #[test]
+    fn remote_controls_null_callback() {
+        TestConfig {
+            ext_remote_controls: Some(ExtRemoteControlsConfig {
+                null_callback: (true, false),
+            }),
+            ..Default::default()
+        }
+        .test(CheckRemoteControlsNotImpl {
+            error: Callback("changed"),
+        });
+
+        TestConfig {
+            ext_remote_controls: Some(ExtRemoteControlsConfig {
+                null_callback: (false, true),
+            }),
+            ..Default::default()
+        }
+        .test(CheckRemoteControlsNotImpl {
+            error: Callback("suggest_page"),
+        });
+    }
+
+    struct CheckCallbacks;
+
+    impl Test for CheckCallbacks {
+        fn test(self, mut bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.as_mut().host_mut() };
+            let remote_controls = host.get_extension().remote_controls().unwrap();
+            remote_controls.changed();
+            remote_controls.suggest_page(ClapId::from(3));
+
+            let ext = bed.ext_remote_controls.as_ref().unwrap();
+            assert!(ext.call_changed);
+            assert_eq!(ext.call_suggest_page, Some(3));
+        }
+    }
+
+    #[test]
+    fn remote_controls_call_callbacks() {
+        TestConfig {
+            ext_remote_controls: Some(ExtRemoteControlsConfig::default()),
+            ..Default::default()
+        }
+        .test(CheckCallbacks);
+    }
+}
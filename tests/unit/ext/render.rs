@@ -0,0 +1,102 @@
+use std::marker::PhantomData;
+
+use clap_clap::{
+    Error,
+    ext::{
+        Extensions,
+        render::{Render, RenderMode},
+    },
+    ffi::{CLAP_RENDER_OFFLINE, CLAP_RENDER_REALTIME},
+    plugin::Plugin,
+};
+
+use crate::{
+    ext::{Test, TestBed, TestConfig, TestPlugin},
+    shims::plugin::ShimPlugin,
+};
+
+#[derive(Debug, Default)]
+struct CheckNoExt<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+    fn test(self, bed: &mut TestBed<P>) {
+        if P::render().is_some() {
+            assert!(bed.ext_render.is_some());
+        } else {
+            assert!(bed.ext_render.is_none());
+        }
+    }
+}
+
+#[test]
+fn no_render_shim() {
+    TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+}
+
+#[derive(Default)]
+struct Plug {
+    mode: Option<RenderMode>,
+}
+
+impl Plugin for Plug {
+    type AudioThread = ();
+    const ID: &'static str = "";
+    const NAME: &'static str = "";
+
+    fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+        Ok(())
+    }
+}
+
+impl TestPlugin for Plug {}
+
+impl Extensions<Self> for Plug {
+    fn render() -> Option<impl Render<Self>> {
+        Some(PlugRender)
+    }
+}
+
+struct PlugRender;
+
+impl Render<Plug> for PlugRender {
+    fn has_hard_realtime_requirement(_: &Plug) -> bool {
+        true
+    }
+
+    fn set(plugin: &mut Plug, mode: RenderMode) -> Result<(), Error> {
+        plugin.mode = Some(mode);
+        Ok(())
+    }
+}
+
+#[test]
+fn render_plug() {
+    TestConfig::default().test::<Plug>(CheckNoExt::default());
+}
+
+struct CheckRenderMode;
+
+impl Test<Plug> for CheckRenderMode {
+    fn test(self, bed: &mut TestBed<Plug>) {
+        let render = bed.ext_render.as_ref().unwrap();
+        let mut handle = bed.plugin();
+
+        assert!(render.has_hard_realtime_requirement());
+
+        assert!(render.set(CLAP_RENDER_OFFLINE));
+        assert_eq!(unsafe { handle.plugin() }.mode, Some(RenderMode::Offline));
+
+        assert!(render.set(CLAP_RENDER_REALTIME));
+        assert_eq!(unsafe { handle.plugin() }.mode, Some(RenderMode::Realtime));
+
+        assert!(!render.set(2));
+        assert_eq!(unsafe { handle.plugin() }.mode, Some(RenderMode::Realtime));
+    }
+}
+
+#[test]
+fn render_set_mode() {
+    TestConfig::default().test::<Plug>(CheckRenderMode);
+}
@@ -0,0 +1,412 @@
+mod plugin_thread_pool {
+    use std::{
+        marker::PhantomData,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use clap_clap::{
+        Error,
+        ext::{Extensions, thread_pool::ThreadPool},
+        plugin::{AudioThread, Plugin},
+        process::{Process, Status, Status::Continue},
+    };
+
+    use crate::{
+        ext::{Test, TestBed, TestConfig, TestPlugin},
+        shims::plugin::ShimPlugin,
+    };
+
+    #[derive(Debug, Default)]
+    struct CheckNoExt<P> {
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+        fn test(self, bed: &mut TestBed<P>) {
+            if P::thread_pool().is_some() {
+                assert!(bed.ext_thread_pool.is_some());
+            } else {
+                assert!(bed.ext_thread_pool.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn no_thread_pool_shim() {
+        TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+    }
+
+    #[derive(Default)]
+    struct Plug;
+
+    impl Plugin for Plug {
+        type AudioThread = Audio;
+        const ID: &'static str = "";
+        const NAME: &'static str = "";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(Audio)
+        }
+    }
+
+    impl TestPlugin for Plug {}
+
+    impl Extensions<Self> for Plug {
+        fn thread_pool() -> Option<impl ThreadPool<Self>> {
+            Some(PlugThreadPool)
+        }
+    }
+
+    struct Audio;
+
+    impl AudioThread<Plug> for Audio {
+        fn process(&mut self, _: &mut Process) -> Result<Status, Error> {
+            Ok(Continue)
+        }
+    }
+
+    static TASKS: AtomicU32 = AtomicU32::new(0);
+
+    struct PlugThreadPool;
+
+    impl ThreadPool<Plug> for PlugThreadPool {
+        type Shared = ();
+
+        fn exec(_: &(), task_index: u32) {
+            TASKS.fetch_or(1 << task_index, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn thread_pool_plug() {
+        TestConfig::default().test::<Plug>(CheckNoExt::default());
+    }
+
+    struct CheckExecOutsideRequest;
+
+    impl Test<Plug> for CheckExecOutsideRequest {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            assert!(bed.activate());
+
+            // There is no shared data to execute the tasks with.
+            let thread_pool = bed.ext_thread_pool.as_ref().unwrap();
+            for i in [1, 3, 5] {
+                thread_pool.exec(i);
+            }
+            assert_eq!(TASKS.load(Ordering::Relaxed), 0);
+        }
+    }
+
+    #[test]
+    fn thread_pool_exec_outside_request() {
+        TestConfig::default().test::<Plug>(CheckExecOutsideRequest);
+    }
+}
+
+/// The host's worker threads execute the tasks concurrently, while the audio
+/// thread is processing.  Run with Miri to check for data races and aliasing
+/// violations:
+///
+/// `MIRIFLAGS=-Zmiri-ignore-leaks cargo +nightly miri test --test unit request_exec`
+mod request_exec {
+    use std::{
+        ffi::{CStr, c_char, c_void},
+        ptr::{null, null_mut},
+        sync::{
+            Arc,
+            atomic::{AtomicPtr, AtomicU32, Ordering},
+        },
+        thread,
+    };
+
+    use clap_clap::{
+        Error,
+        ext::{Extensions, thread_pool::ThreadPool},
+        factory::{Factory, FactoryHost, FactoryPluginPrototype},
+        ffi::{
+            CLAP_EXT_THREAD_POOL, CLAP_VERSION, clap_host, clap_host_thread_pool, clap_plugin,
+            clap_plugin_thread_pool,
+        },
+        host::Host,
+        plugin::{AudioThread, ClapPlugin, Plugin},
+        process::{Process, Status, Status::Continue},
+    };
+
+    use crate::process::TestProcessConfig;
+
+    const NUM_TASKS: u32 = 32;
+    const NUM_WORKERS: usize = 4;
+
+    #[derive(Default)]
+    struct Voices {
+        rendered: [AtomicU32; NUM_TASKS as usize],
+    }
+
+    #[derive(Default)]
+    struct Plug {
+        host: Option<Arc<Host>>,
+    }
+
+    impl Plugin for Plug {
+        type AudioThread = Audio;
+        const ID: &'static str = "thread.pool";
+        const NAME: &'static str = "";
+
+        fn init(&mut self, host: Arc<Host>) -> Result<(), Error> {
+            self.host = Some(host);
+            Ok(())
+        }
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(Audio {
+                host: self.host.clone().unwrap(),
+                voices: Voices::default(),
+                done: false,
+                wrong_type_done: false,
+            })
+        }
+    }
+
+    impl Extensions<Self> for Plug {
+        fn thread_pool() -> Option<impl ThreadPool<Self>> {
+            Some(PlugThreadPool)
+        }
+    }
+
+    struct Audio {
+        host: Arc<Host>,
+        voices: Voices,
+        done: bool,
+        wrong_type_done: bool,
+    }
+
+    impl AudioThread<Plug> for Audio {
+        fn process(&mut self, _: &mut Process) -> Result<Status, Error> {
+            let thread_pool = self.host.get_extension().thread_pool()?;
+            self.done = thread_pool.request_exec(&self.voices, NUM_TASKS);
+            // The request is rejected with data of another type.
+            self.wrong_type_done = thread_pool.request_exec(&0u32, NUM_TASKS);
+            // The audio thread is borrowed mutably again.
+            for voice in &mut self.voices.rendered {
+                *voice.get_mut() += 1;
+            }
+            Ok(Continue)
+        }
+    }
+
+    struct PlugThreadPool;
+
+    impl ThreadPool<Plug> for PlugThreadPool {
+        type Shared = Voices;
+
+        fn exec(voices: &Voices, task_index: u32) {
+            voices.rendered[task_index as usize].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The host data: the plugin to execute the tasks of, and the number of
+    /// requests.
+    struct HostData {
+        clap_plugin: AtomicPtr<clap_plugin>,
+        requests: AtomicU32,
+    }
+
+    static HOST_THREAD_POOL: clap_host_thread_pool = clap_host_thread_pool {
+        request_exec: Some(host_request_exec),
+    };
+
+    extern "C-unwind" fn host_get_extension(
+        _: *const clap_host,
+        id: *const c_char,
+    ) -> *const c_void {
+        if unsafe { CStr::from_ptr(id) } == CLAP_EXT_THREAD_POOL {
+            (&raw const HOST_THREAD_POOL).cast()
+        } else {
+            null()
+        }
+    }
+
+    extern "C-unwind" fn host_request(_: *const clap_host) {}
+
+    extern "C-unwind" fn host_request_exec(host: *const clap_host, num_tasks: u32) -> bool {
+        let data = unsafe { &*(*host).host_data.cast::<HostData>() };
+        data.requests.fetch_add(1, Ordering::Relaxed);
+        let clap_plugin = data.clap_plugin.load(Ordering::Acquire).cast_const();
+        let thread_pool = unsafe {
+            &*(*clap_plugin).get_extension.unwrap()(clap_plugin, CLAP_EXT_THREAD_POOL.as_ptr())
+                .cast::<clap_plugin_thread_pool>()
+        };
+        let exec = thread_pool.exec.unwrap();
+
+        let next_task = AtomicU32::new(0);
+        thread::scope(|s| {
+            for _ in 0..NUM_WORKERS {
+                s.spawn(|| {
+                    let clap_plugin = data.clap_plugin.load(Ordering::Acquire).cast_const();
+                    loop {
+                        let task_index = next_task.fetch_add(1, Ordering::Relaxed);
+                        if task_index >= num_tasks {
+                            break;
+                        }
+                        unsafe { exec(clap_plugin, task_index) };
+                    }
+                });
+            }
+        });
+        true
+    }
+
+    #[test]
+    fn exec_from_worker_threads() {
+        let host_data = HostData {
+            clap_plugin: AtomicPtr::new(null_mut()),
+            requests: AtomicU32::new(0),
+        };
+        let clap_host = clap_host {
+            clap_version: CLAP_VERSION,
+            host_data: (&raw const host_data).cast_mut().cast(),
+            name: c"".as_ptr(),
+            vendor: c"".as_ptr(),
+            url: c"".as_ptr(),
+            version: c"".as_ptr(),
+            get_extension: Some(host_get_extension),
+            request_restart: Some(host_request),
+            request_process: Some(host_request),
+            request_callback: Some(host_request),
+        };
+
+        let factory = Factory::new(vec![Box::new(
+            FactoryPluginPrototype::<Plug>::build().unwrap(),
+        )]);
+        let host = unsafe { FactoryHost::new_unchecked(&raw const clap_host) };
+        let plugin = factory.create_plugin(c"thread.pool", host).unwrap();
+        host_data
+            .clap_plugin
+            .store(plugin.cast_mut(), Ordering::Release);
+        let plugin = unsafe { &*plugin };
+
+        unsafe {
+            assert!(plugin.init.unwrap()(plugin));
+            assert!(plugin.activate.unwrap()(plugin, 48000.0, 1, 16));
+        }
+        let mut process = TestProcessConfig {
+            latency: 0,
+            steady_time: 0,
+            frames_count: 16,
+            channel_count: 0,
+            audio_inputs_count: 0,
+            audio_outputs_count: 0,
+        }
+        .build();
+        // The plugin gets a mutable reference to the process.
+        let mut clap_process = process.clap_process();
+        for _ in 0..3 {
+            unsafe { plugin.process.unwrap()(plugin, &raw mut clap_process) };
+        }
+
+        let mut wrapper = unsafe { ClapPlugin::<Plug>::new_unchecked(plugin) };
+        let audio = unsafe { wrapper.audio_thread() }.unwrap();
+        assert!(audio.done);
+        assert!(!audio.wrong_type_done);
+        // Only the requests with the plugin's shared data reached the host.
+        assert_eq!(host_data.requests.load(Ordering::Relaxed), 3);
+        for voice in &audio.voices.rendered {
+            // Three requests and three increments by the audio thread.
+            assert_eq!(voice.load(Ordering::Relaxed), 6);
+        }
+
+        unsafe {
+            plugin.deactivate.unwrap()(plugin);
+            plugin.destroy.unwrap()(plugin);
+        }
+    }
+}
+
+mod host_thread_pool {
+    use std::pin::Pin;
+
+    use clap_clap::{
+        host,
+        host::Error::{Callback, ExtensionNotFound},
+    };
+
+    use crate::host::{ExtThreadPoolConfig, Test, TestBed, TestConfig};
+
+    struct CheckThreadPoolNotImpl {
+        error: host::Error,
+    }
+
+    impl Test for CheckThreadPoolNotImpl {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let err = host.get_extension().thread_pool().unwrap_err();
+            assert_eq!(err, self.error);
+        }
+    }
+
+    #[test]
+    fn thread_pool_not_impl() {
+        TestConfig::default().test(CheckThreadPoolNotImpl {
+            error: ExtensionNotFound("thread_pool"),
+        });
+    }
+
+    #[test]
+    fn thread_pool_no_method_request_exec() {
+        TestConfig {
+            ext_thread_pool: Some(ExtThreadPoolConfig {
+                null_callback: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckThreadPoolNotImpl {
+            error: Callback("request_exec"),
+        });
+    }
+
+    struct CheckRequestExec {
+        num_tasks: u32,
+        accepted: bool,
+    }
+
+    impl Test for CheckRequestExec {
+        fn test(self, mut bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.as_mut().host_mut() };
+            let thread_pool = host.get_extension().thread_pool().unwrap();
+
+            assert_eq!(thread_pool.request_exec(&(), self.num_tasks), self.accepted);
+            assert_eq!(
+                bed.ext_thread_pool.as_ref().unwrap().call_request_exec,
+                Some(self.num_tasks)
+            );
+        }
+    }
+
+    #[test]
+    fn thread_pool_request_exec() {
+        TestConfig {
+            ext_thread_pool: Some(ExtThreadPoolConfig::default()),
+            ..Default::default()
+        }
+        .test(CheckRequestExec {
+            num_tasks: 16,
+            accepted: true,
+        });
+    }
+
+    #[test]
+    fn thread_pool_request_exec_rejected() {
+        TestConfig {
+            ext_thread_pool: Some(ExtThreadPoolConfig {
+                reject_exec: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckRequestExec {
+            num_tasks: 3,
+            accepted: false,
+        });
+    }
+}
@@ -0,0 +1,211 @@
+mod plugin_timer_support {
+    use std::marker::PhantomData;
+
+    use clap_clap::{
+        Error,
+        ext::{
+            Extensions,
+            timer_support::{TimerId, TimerSupport},
+        },
+        plugin::Plugin,
+    };
+
+    use crate::{
+        ext::{Test, TestBed, TestConfig, TestPlugin},
+        shims::plugin::ShimPlugin,
+    };
+
+    #[derive(Debug, Default)]
+    struct CheckNoExt<P> {
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+        fn test(self, bed: &mut TestBed<P>) {
+            if P::timer_support().is_some() {
+                assert!(bed.ext_timer_support.is_some());
+            } else {
+                assert!(bed.ext_timer_support.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn no_timer_support_shim() {
+        TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+    }
+
+    #[derive(Default)]
+    struct Plug {
+        fired: Vec<TimerId>,
+    }
+
+    impl Plugin for Plug {
+        type AudioThread = ();
+        const ID: &'static str = "";
+        const NAME: &'static str = "";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(())
+        }
+    }
+
+    impl TestPlugin for Plug {}
+
+    impl Extensions<Self> for Plug {
+        fn timer_support() -> Option<impl TimerSupport<Self>> {
+            Some(PlugTimerSupport)
+        }
+    }
+
+    struct PlugTimerSupport;
+
+    impl TimerSupport<Plug> for PlugTimerSupport {
+        fn on_timer(plugin: &mut Plug, timer_id: TimerId) {
+            plugin.fired.push(timer_id);
+        }
+    }
+
+    #[test]
+    fn timer_support_plug() {
+        TestConfig::default().test::<Plug>(CheckNoExt::default());
+    }
+
+    struct CheckOnTimer;
+
+    impl Test<Plug> for CheckOnTimer {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let timer_support = bed.ext_timer_support.as_ref().unwrap();
+            timer_support.on_timer(3);
+            timer_support.on_timer(u32::MAX);
+            timer_support.on_timer(0);
+
+            let mut handle = bed.plugin();
+            assert_eq!(
+                unsafe { handle.plugin() }.fired,
+                vec![TimerId::try_from(3).unwrap(), TimerId::try_from(0).unwrap()]
+            );
+        }
+    }
+
+    #[test]
+    fn timer_support_on_timer() {
+        TestConfig::default().test::<Plug>(CheckOnTimer);
+    }
+}
+
+mod host_timer_support {
+    use std::pin::Pin;
+
+    use clap_clap::{
+        ext::timer_support::{Error, TimerId},
+        host,
+        host::Error::{Callback, ExtensionNotFound},
+    };
+
+    use crate::host::{ExtTimerSupportConfig, Test, TestBed, TestConfig};
+
+    struct CheckTimerSupportNotImpl {
+        error: host::Error,
+    }
+
+    impl Test for CheckTimerSupportNotImpl {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let err = host.get_extension().timer_support().unwrap_err();
+            assert_eq!(err, self.error);
+        }
+    }
+
+    #[test]
+    fn timer_support_not_impl() {
+        TestConfig::default().test(CheckTimerSupportNotImpl {
+            error: ExtensionNotFound("timer_support"),
+        });
+    }
+
+    #[test]
+    fn timer_support_null_callback() {
+        TestConfig {
+            ext_timer_support: Some(ExtTimerSupportConfig {
+                null_callback: (true, false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckTimerSupportNotImpl {
+            error: Callback("register_timer"),
+        });
+
+        TestConfig {
+            ext_timer_support: Some(ExtTimerSupportConfig {
+                null_callback: (false, true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckTimerSupportNotImpl {
+            error: Callback("unregister_timer"),
+        });
+    }
+
+    struct CheckRegister;
+
+    impl Test for CheckRegister {
+        fn test(self, mut bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.as_mut().host_mut() };
+            let timer_support = host.get_extension().timer_support().unwrap();
+
+            let id0 = timer_support.register_timer(30).unwrap();
+            let id1 = timer_support.register_timer(100).unwrap();
+            assert_ne!(id0, id1);
+
+            timer_support.unregister_timer(id0).unwrap();
+            assert_eq!(
+                timer_support.unregister_timer(id0),
+                Err(Error::Unregister(id0))
+            );
+
+            assert_eq!(
+                bed.ext_timer_support.as_ref().unwrap().timers,
+                vec![(u32::from(id1), 100)]
+            );
+        }
+    }
+
+    #[test]
+    fn timer_support_register() {
+        TestConfig {
+            ext_timer_support: Some(ExtTimerSupportConfig::default()),
+            ..Default::default()
+        }
+        .test(CheckRegister);
+    }
+
+    struct CheckRegisterRejected;
+
+    impl Test for CheckRegisterRejected {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let timer_support = host.get_extension().timer_support().unwrap();
+
+            assert_eq!(timer_support.register_timer(16), Err(Error::Register(16)));
+            assert_eq!(
+                timer_support.unregister_timer(TimerId::try_from(0).unwrap()),
+                Err(Error::Unregister(TimerId::try_from(0).unwrap()))
+            );
+        }
+    }
+
+    #[test]
+    fn timer_support_register_rejected() {
+        TestConfig {
+            ext_timer_support: Some(ExtTimerSupportConfig {
+                reject_register: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .test(CheckRegisterRejected);
+    }
+}
@@ -0,0 +1,163 @@
+mod plugin_voice_info {
+    use std::marker::PhantomData;
+
+    use clap_clap::{
+        Error,
+        ext::{
+            Extensions,
+            voice_info::{VoiceInfo, VoiceInfoData, VoiceInfoFlags},
+        },
+        plugin::Plugin,
+    };
+
+    use crate::{
+        ext::{Test, TestBed, TestConfig, TestPlugin},
+        shims::plugin::ShimPlugin,
+    };
+
+    #[derive(Debug, Default)]
+    struct CheckNoExt<P> {
+        _marker: PhantomData<P>,
+    }
+
+    impl<P: TestPlugin + 'static> Test<P> for CheckNoExt<P> {
+        fn test(self, bed: &mut TestBed<P>) {
+            if P::voice_info().is_some() {
+                assert!(bed.ext_voice_info.is_some());
+            } else {
+                assert!(bed.ext_voice_info.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn no_voice_info_shim() {
+        TestConfig::default().test::<ShimPlugin>(CheckNoExt::default());
+    }
+
+    #[derive(Default)]
+    struct Plug {
+        voices: Option<VoiceInfoData>,
+    }
+
+    impl Plugin for Plug {
+        type AudioThread = ();
+        const ID: &'static str = "";
+        const NAME: &'static str = "";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<Self::AudioThread, Error> {
+            Ok(())
+        }
+    }
+
+    impl TestPlugin for Plug {}
+
+    impl Extensions<Self> for Plug {
+        fn voice_info() -> Option<impl VoiceInfo<Self>> {
+            Some(PlugVoiceInfo)
+        }
+    }
+
+    struct PlugVoiceInfo;
+
+    impl VoiceInfo<Plug> for PlugVoiceInfo {
+        fn get(plugin: &Plug) -> Option<VoiceInfoData> {
+            plugin.voices
+        }
+    }
+
+    #[test]
+    fn voice_info_plug() {
+        TestConfig::default().test::<Plug>(CheckNoExt::default());
+    }
+
+    struct CheckGet(Option<VoiceInfoData>);
+
+    impl Test<Plug> for CheckGet {
+        fn test(self, bed: &mut TestBed<Plug>) {
+            let mut handle = bed.plugin();
+            unsafe { handle.plugin() }.voices = self.0;
+
+            assert_eq!(bed.ext_voice_info.as_ref().unwrap().get(), self.0);
+        }
+    }
+
+    #[test]
+    fn voice_info_get() {
+        TestConfig::default()
+            .test::<Plug>(CheckGet(None))
+            .test::<Plug>(CheckGet(Some(VoiceInfoData {
+                voice_count: 8,
+                voice_capacity: 16,
+                flags: VoiceInfoFlags::SupportsOverlappingNotes.into(),
+            })))
+            .test::<Plug>(CheckGet(Some(VoiceInfoData {
+                voice_count: 1,
+                voice_capacity: 1,
+                flags: 0,
+            })));
+    }
+}
+
+mod host_voice_info {
+    use std::pin::Pin;
+
+    use clap_clap::{
+        host,
+        host::Error::{Callback, ExtensionNotFound},
+    };
+
+    use crate::host::{ExtVoiceInfoConfig, Test, TestBed, TestConfig};
+
+    struct CheckVoiceInfoNotImpl {
+        error: host::Error,
+    }
+
+    impl Test for CheckVoiceInfoNotImpl {
+        fn test(self, bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.host_mut() };
+            let err = host.get_extension().voice_info().unwrap_err();
+            assert_eq!(err, self.error);
+        }
+    }
+
+    #[test]
+    fn voice_info_not_impl() {
+        TestConfig::default().test(CheckVoiceInfoNotImpl {
+            error: ExtensionNotFound("voice_info"),
+        });
+    }
+
+    #[test]
+    fn voice_info_no_method_changed() {
+        TestConfig {
+            ext_voice_info: Some(ExtVoiceInfoConfig {
+                null_callback: true,
+            }),
+            ..Default::default()
+        }
+        .test(CheckVoiceInfoNotImpl {
+            error: Callback("changed"),
+        });
+    }
+
+    struct CheckCallChanged;
+
+    impl Test for CheckCallChanged {
+        fn test(self, mut bed: Pin<&mut TestBed>) {
+            let host = unsafe { bed.as_mut().host_mut() };
+            host.get_extension().voice_info().unwrap().changed();
+
+            assert!(bed.ext_voice_info.as_ref().unwrap().call_changed);
+        }
+    }
+
+    #[test]
+    fn voice_info_call_changed() {
+        TestConfig {
+            ext_voice_info: Some(ExtVoiceInfoConfig::default()),
+            ..Default::default()
+        }
+        .test(CheckCallChanged);
+    }
+}
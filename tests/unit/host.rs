@@ -1,5 +1,5 @@
 use std::{
-    ffi::{CStr, CString, c_char, c_void},
+    ffi::{CStr, CString, c_char, c_int, c_void},
     marker::PhantomPinned,
     pin::Pin,
     ptr::{null, null_mut},
@@ -8,10 +8,15 @@ use std::{
 
 use clap_clap::{
     ffi::{
-        CLAP_EXT_AUDIO_PORTS, CLAP_EXT_LATENCY, CLAP_EXT_LOG, CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAMS,
-        CLAP_EXT_STATE, CLAP_EXT_TAIL, clap_host, clap_host_audio_ports, clap_host_latency,
-        clap_host_log, clap_host_note_ports, clap_host_params, clap_host_state, clap_host_tail,
-        clap_id, clap_log_severity,
+        CLAP_EXT_AUDIO_PORTS, CLAP_EXT_EVENT_REGISTRY, CLAP_EXT_GUI, CLAP_EXT_LATENCY,
+        CLAP_EXT_LOG, CLAP_EXT_NOTE_NAME, CLAP_EXT_NOTE_PORTS, CLAP_EXT_PARAMS,
+        CLAP_EXT_POSIX_FD_SUPPORT, CLAP_EXT_REMOTE_CONTROLS, CLAP_EXT_STATE, CLAP_EXT_TAIL,
+        CLAP_EXT_THREAD_POOL, CLAP_EXT_TIMER_SUPPORT, CLAP_EXT_VOICE_INFO, clap_host,
+        clap_host_audio_ports, clap_host_event_registry, clap_host_gui, clap_host_latency,
+        clap_host_log, clap_host_note_name, clap_host_note_ports, clap_host_params,
+        clap_host_posix_fd_support, clap_host_remote_controls, clap_host_state, clap_host_tail,
+        clap_host_thread_pool, clap_host_timer_support, clap_host_voice_info, clap_id,
+        clap_log_severity,
     },
     host::Host,
     version::CLAP_VERSION,
@@ -29,12 +34,20 @@ pub struct TestConfig<'a> {
     pub version: &'a CStr,
 
     pub ext_audio_ports: Option<ExtAudioPortsConfig>,
+    pub ext_event_registry: Option<ExtEventRegistryConfig>,
+    pub ext_gui: Option<ExtGuiConfig>,
     pub ext_latency: Option<ExtLatencyConfig>,
     pub ext_log: Option<ExtLogConfig>,
+    pub ext_note_name: Option<ExtNoteNameConfig>,
     pub ext_note_ports: Option<ExtNotePortsConfig>,
     pub ext_params: Option<ExtParamsConfig>,
+    pub ext_posix_fd_support: Option<ExtPosixFdSupportConfig>,
+    pub ext_remote_controls: Option<ExtRemoteControlsConfig>,
     pub ext_state: Option<ExtStateConfig>,
     pub ext_tail: Option<ExtTailConfig>,
+    pub ext_thread_pool: Option<ExtThreadPoolConfig>,
+    pub ext_timer_support: Option<ExtTimerSupportConfig>,
+    pub ext_voice_info: Option<ExtVoiceInfoConfig>,
 }
 
 impl TestConfig<'_> {
@@ -60,12 +73,20 @@ pub struct TestBed<'a> {
     call_request: CallRequest,
 
     pub ext_audio_ports: Option<ExtAudioPorts>,
+    pub ext_event_registry: Option<ExtEventRegistry>,
+    pub ext_gui: Option<ExtGui>,
     pub ext_log: Option<ExtLog>,
     pub ext_latency: Option<ExtLatency>,
+    pub ext_note_name: Option<ExtNoteName>,
     pub ext_note_ports: Option<ExtNotePorts>,
     pub ext_params: Option<ExtParams>,
+    pub ext_posix_fd_support: Option<ExtPosixFdSupport>,
+    pub ext_remote_controls: Option<ExtRemoteControls>,
     pub ext_state: Option<ExtState>,
     pub ext_tail: Option<ExtTail>,
+    pub ext_thread_pool: Option<ExtThreadPool>,
+    pub ext_timer_support: Option<ExtTimerSupport>,
+    pub ext_voice_info: Option<ExtVoiceInfo>,
 
     _marker: PhantomPinned,
 }
@@ -89,12 +110,20 @@ impl<'a> TestBed<'a> {
             call_request: CallRequest::default(),
 
             ext_audio_ports: config.ext_audio_ports.map(ExtAudioPorts::new),
+            ext_event_registry: config.ext_event_registry.map(ExtEventRegistry::new),
+            ext_gui: config.ext_gui.map(ExtGui::new),
             ext_latency: config.ext_latency.map(ExtLatency::new),
             ext_log: config.ext_log.map(ExtLog::new),
+            ext_note_name: config.ext_note_name.map(ExtNoteName::new),
             ext_note_ports: config.ext_note_ports.map(ExtNotePorts::new),
             ext_params: config.ext_params.map(ExtParams::new),
+            ext_posix_fd_support: config.ext_posix_fd_support.map(ExtPosixFdSupport::new),
+            ext_remote_controls: config.ext_remote_controls.map(ExtRemoteControls::new),
             ext_state: config.ext_state.map(ExtState::new),
             ext_tail: config.ext_tail.map(ExtTail::new),
+            ext_thread_pool: config.ext_thread_pool.map(ExtThreadPool::new),
+            ext_timer_support: config.ext_timer_support.map(ExtTimerSupport::new),
+            ext_voice_info: config.ext_voice_info.map(ExtVoiceInfo::new),
 
             config,
             _marker: PhantomPinned,
@@ -134,6 +163,16 @@ extern "C-unwind" fn get_extension(
             return (&raw const ext.clap_host_audio_ports).cast();
         }
     }
+    if extension_id == CLAP_EXT_EVENT_REGISTRY {
+        if let Some(ext) = &bed.ext_event_registry {
+            return (&raw const ext.clap_host_event_registry).cast();
+        }
+    }
+    if extension_id == CLAP_EXT_GUI {
+        if let Some(ext) = &bed.ext_gui {
+            return (&raw const ext.clap_host_gui).cast();
+        }
+    }
     if extension_id == CLAP_EXT_LATENCY {
         if let Some(ext) = &bed.ext_latency {
             return (&raw const ext.clap_host_latency).cast();
@@ -144,6 +183,11 @@ extern "C-unwind" fn get_extension(
             return (&raw const ext.clap_host_log).cast();
         }
     }
+    if extension_id == CLAP_EXT_NOTE_NAME {
+        if let Some(ext) = &bed.ext_note_name {
+            return (&raw const ext.clap_host_note_name).cast();
+        }
+    }
     if extension_id == CLAP_EXT_NOTE_PORTS {
         if let Some(ext) = &bed.ext_note_ports {
             return (&raw const ext.clap_host_note_ports).cast();
@@ -154,6 +198,16 @@ extern "C-unwind" fn get_extension(
             return (&raw const ext.clap_host_params).cast();
         }
     }
+    if extension_id == CLAP_EXT_POSIX_FD_SUPPORT {
+        if let Some(ext) = &bed.ext_posix_fd_support {
+            return (&raw const ext.clap_host_posix_fd_support).cast();
+        }
+    }
+    if extension_id == CLAP_EXT_REMOTE_CONTROLS {
+        if let Some(ext) = &bed.ext_remote_controls {
+            return (&raw const ext.clap_host_remote_controls).cast();
+        }
+    }
     if extension_id == CLAP_EXT_STATE {
         if let Some(ext) = &bed.ext_state {
             return (&raw const ext.clap_host_state).cast();
@@ -164,6 +218,21 @@ extern "C-unwind" fn get_extension(
             return (&raw const ext.clap_host_tail).cast();
         }
     }
+    if extension_id == CLAP_EXT_THREAD_POOL {
+        if let Some(ext) = &bed.ext_thread_pool {
+            return (&raw const ext.clap_host_thread_pool).cast();
+        }
+    }
+    if extension_id == CLAP_EXT_TIMER_SUPPORT {
+        if let Some(ext) = &bed.ext_timer_support {
+            return (&raw const ext.clap_host_timer_support).cast();
+        }
+    }
+    if extension_id == CLAP_EXT_VOICE_INFO {
+        if let Some(ext) = &bed.ext_voice_info {
+            return (&raw const ext.clap_host_voice_info).cast();
+        }
+    }
 
     null()
 }
@@ -235,6 +304,152 @@ extern "C-unwind" fn ext_audio_ports_rescan(host: *const clap_host, flags: u32)
     }
 }
 
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtEventRegistryConfig {
+    pub null_callback: bool,
+    pub spaces: &'static [&'static CStr],
+}
+
+#[derive(Debug)]
+pub struct ExtEventRegistry {
+    config: ExtEventRegistryConfig,
+    clap_host_event_registry: clap_host_event_registry,
+    pub call_query: Vec<CString>,
+}
+
+impl ExtEventRegistry {
+    pub const FIRST_SPACE_ID: u16 = 1000;
+
+    fn new(config: ExtEventRegistryConfig) -> Self {
+        Self {
+            config,
+            clap_host_event_registry: clap_host_event_registry {
+                query: (!config.null_callback).then_some(ext_event_registry_query),
+            },
+            call_query: Vec::new(),
+        }
+    }
+}
+
+extern "C-unwind" fn ext_event_registry_query(
+    host: *const clap_host,
+    space_name: *const c_char,
+    space_id: *mut u16,
+) -> bool {
+    assert!(!host.is_null());
+    assert!(!space_name.is_null());
+    assert!(!space_id.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    let Some(ext) = &mut bed.ext_event_registry else {
+        return false;
+    };
+
+    let space_name = unsafe { CStr::from_ptr(space_name) };
+    ext.call_query.push(space_name.to_owned());
+
+    let Some(index) = ext.config.spaces.iter().position(|&s| s == space_name) else {
+        unsafe { *space_id = u16::MAX };
+        return false;
+    };
+    unsafe { *space_id = ExtEventRegistry::FIRST_SPACE_ID + index as u16 };
+    true
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtGuiConfig {
+    pub null_callback: (bool, bool, bool, bool, bool),
+    pub accept_requests: bool,
+}
+
+#[derive(Debug)]
+pub struct ExtGui {
+    config: ExtGuiConfig,
+    clap_host_gui: clap_host_gui,
+    pub call_resize_hints_changed: bool,
+    pub call_request_resize: Option<(u32, u32)>,
+    pub call_request_show: bool,
+    pub call_request_hide: bool,
+    pub call_closed: Option<bool>,
+}
+
+impl ExtGui {
+    fn new(config: ExtGuiConfig) -> Self {
+        Self {
+            config,
+            clap_host_gui: clap_host_gui {
+                resize_hints_changed: (!config.null_callback.0)
+                    .then_some(ext_gui_resize_hints_changed),
+                request_resize: (!config.null_callback.1).then_some(ext_gui_request_resize),
+                request_show: (!config.null_callback.2).then_some(ext_gui_request_show),
+                request_hide: (!config.null_callback.3).then_some(ext_gui_request_hide),
+                closed: (!config.null_callback.4).then_some(ext_gui_closed),
+            },
+            call_resize_hints_changed: false,
+            call_request_resize: None,
+            call_request_show: false,
+            call_request_hide: false,
+            call_closed: None,
+        }
+    }
+
+    pub const fn accept_requests(&self) -> bool {
+        self.config.accept_requests
+    }
+}
+
+extern "C-unwind" fn ext_gui_resize_hints_changed(host: *const clap_host) {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_gui {
+        ext.call_resize_hints_changed = true;
+    }
+}
+
+extern "C-unwind" fn ext_gui_request_resize(
+    host: *const clap_host,
+    width: u32,
+    height: u32,
+) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_gui {
+        ext.call_request_resize = Some((width, height));
+        ext.config.accept_requests
+    } else {
+        false
+    }
+}
+
+extern "C-unwind" fn ext_gui_request_show(host: *const clap_host) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_gui {
+        ext.call_request_show = true;
+        ext.config.accept_requests
+    } else {
+        false
+    }
+}
+
+extern "C-unwind" fn ext_gui_request_hide(host: *const clap_host) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_gui {
+        ext.call_request_hide = true;
+        ext.config.accept_requests
+    } else {
+        false
+    }
+}
+
+extern "C-unwind" fn ext_gui_closed(host: *const clap_host, was_destroyed: bool) {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_gui {
+        ext.call_closed = Some(was_destroyed);
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone)]
 pub struct ExtLatencyConfig {
     pub null_callback: bool,
@@ -305,6 +520,36 @@ extern "C-unwind" fn ext_log_log(
     }
 }
 
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtNoteNameConfig {
+    pub null_callback: bool,
+}
+
+#[derive(Debug)]
+pub struct ExtNoteName {
+    clap_host_note_name: clap_host_note_name,
+    pub call_changed: bool,
+}
+
+impl ExtNoteName {
+    fn new(config: ExtNoteNameConfig) -> Self {
+        Self {
+            clap_host_note_name: clap_host_note_name {
+                changed: (!config.null_callback).then_some(ext_note_name_changed),
+            },
+            call_changed: false,
+        }
+    }
+}
+
+extern "C-unwind" fn ext_note_name_changed(host: *const clap_host) {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_note_name {
+        ext.call_changed = true;
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone)]
 pub struct ExtNotePortsConfig {
     pub supported_dialects: u32,
@@ -403,6 +648,118 @@ extern "C-unwind" fn ext_params_request_flush(host: *const clap_host) {
     }
 }
 
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtPosixFdSupportConfig {
+    pub null_callback: (bool, bool, bool),
+}
+
+#[derive(Debug)]
+pub struct ExtPosixFdSupport {
+    clap_host_posix_fd_support: clap_host_posix_fd_support,
+    pub fds: Vec<(c_int, u32)>,
+}
+
+impl ExtPosixFdSupport {
+    fn new(config: ExtPosixFdSupportConfig) -> Self {
+        Self {
+            clap_host_posix_fd_support: clap_host_posix_fd_support {
+                register_fd: (!config.null_callback.0).then_some(ext_posix_fd_support_register_fd),
+                modify_fd: (!config.null_callback.1).then_some(ext_posix_fd_support_modify_fd),
+                unregister_fd: (!config.null_callback.2)
+                    .then_some(ext_posix_fd_support_unregister_fd),
+            },
+            fds: Vec::new(),
+        }
+    }
+}
+
+extern "C-unwind" fn ext_posix_fd_support_register_fd(
+    host: *const clap_host,
+    fd: c_int,
+    flags: u32,
+) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    let Some(ext) = &mut bed.ext_posix_fd_support else {
+        return false;
+    };
+    if ext.fds.iter().any(|(f, _)| *f == fd) {
+        return false;
+    }
+    ext.fds.push((fd, flags));
+    true
+}
+
+extern "C-unwind" fn ext_posix_fd_support_modify_fd(
+    host: *const clap_host,
+    fd: c_int,
+    flags: u32,
+) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    let Some(ext) = &mut bed.ext_posix_fd_support else {
+        return false;
+    };
+    if let Some(entry) = ext.fds.iter_mut().find(|(f, _)| *f == fd) {
+        entry.1 = flags;
+        true
+    } else {
+        false
+    }
+}
+
+extern "C-unwind" fn ext_posix_fd_support_unregister_fd(host: *const clap_host, fd: c_int) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    let Some(ext) = &mut bed.ext_posix_fd_support else {
+        return false;
+    };
+    let len = ext.fds.len();
+    ext.fds.retain(|(f, _)| *f != fd);
+    ext.fds.len() != len
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtRemoteControlsConfig {
+    pub null_callback: (bool, bool),
+}
+
+#[derive(Debug)]
+pub struct ExtRemoteControls {
+    clap_host_remote_controls: clap_host_remote_controls,
+    pub call_changed: bool,
+    pub call_suggest_page: Option<clap_id>,
+}
+
+impl ExtRemoteControls {
+    fn new(config: ExtRemoteControlsConfig) -> Self {
+        Self {
+            clap_host_remote_controls: clap_host_remote_controls {
+                changed: (!config.null_callback.0).then_some(ext_remote_controls_changed),
+                suggest_page: (!config.null_callback.1).then_some(ext_remote_controls_suggest_page),
+            },
+            call_changed: false,
+            call_suggest_page: None,
+        }
+    }
+}
+
+extern "C-unwind" fn ext_remote_controls_changed(host: *const clap_host) {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_remote_controls {
+        ext.call_changed = true;
+    }
+}
+
+extern "C-unwind" fn ext_remote_controls_suggest_page(host: *const clap_host, page_id: clap_id) {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_remote_controls {
+        ext.call_suggest_page = Some(page_id);
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone)]
 pub struct ExtStateConfig {
     pub null_callback: bool,
@@ -463,6 +820,139 @@ extern "C-unwind" fn ext_tail_changed(host: *const clap_host) {
     }
 }
 
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtThreadPoolConfig {
+    pub null_callback: bool,
+    pub reject_exec: bool,
+}
+
+#[derive(Debug)]
+pub struct ExtThreadPool {
+    config: ExtThreadPoolConfig,
+    clap_host_thread_pool: clap_host_thread_pool,
+    pub call_request_exec: Option<u32>,
+}
+
+impl ExtThreadPool {
+    fn new(config: ExtThreadPoolConfig) -> Self {
+        Self {
+            config,
+            clap_host_thread_pool: clap_host_thread_pool {
+                request_exec: (!config.null_callback).then_some(ext_thread_pool_request_exec),
+            },
+            call_request_exec: None,
+        }
+    }
+}
+
+extern "C-unwind" fn ext_thread_pool_request_exec(host: *const clap_host, num_tasks: u32) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_thread_pool {
+        ext.call_request_exec = Some(num_tasks);
+        !ext.config.reject_exec
+    } else {
+        false
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtTimerSupportConfig {
+    pub null_callback: (bool, bool),
+    pub reject_register: bool,
+}
+
+#[derive(Debug)]
+pub struct ExtTimerSupport {
+    config: ExtTimerSupportConfig,
+    clap_host_timer_support: clap_host_timer_support,
+    next_id: clap_id,
+    pub timers: Vec<(clap_id, u32)>,
+}
+
+impl ExtTimerSupport {
+    fn new(config: ExtTimerSupportConfig) -> Self {
+        Self {
+            config,
+            clap_host_timer_support: clap_host_timer_support {
+                register_timer: (!config.null_callback.0)
+                    .then_some(ext_timer_support_register_timer),
+                unregister_timer: (!config.null_callback.1)
+                    .then_some(ext_timer_support_unregister_timer),
+            },
+            next_id: 0,
+            timers: Vec::new(),
+        }
+    }
+}
+
+extern "C-unwind" fn ext_timer_support_register_timer(
+    host: *const clap_host,
+    period_ms: u32,
+    timer_id: *mut clap_id,
+) -> bool {
+    assert!(!host.is_null());
+    assert!(!timer_id.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    let Some(ext) = &mut bed.ext_timer_support else {
+        return false;
+    };
+    if ext.config.reject_register {
+        return false;
+    }
+
+    let id = ext.next_id;
+    ext.next_id += 1;
+    ext.timers.push((id, period_ms));
+    unsafe { *timer_id = id };
+    true
+}
+
+extern "C-unwind" fn ext_timer_support_unregister_timer(
+    host: *const clap_host,
+    timer_id: clap_id,
+) -> bool {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    let Some(ext) = &mut bed.ext_timer_support else {
+        return false;
+    };
+
+    let len = ext.timers.len();
+    ext.timers.retain(|(id, _)| *id != timer_id);
+    ext.timers.len() != len
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ExtVoiceInfoConfig {
+    pub null_callback: bool,
+}
+
+#[derive(Debug)]
+pub struct ExtVoiceInfo {
+    clap_host_voice_info: clap_host_voice_info,
+    pub call_changed: bool,
+}
+
+impl ExtVoiceInfo {
+    fn new(config: ExtVoiceInfoConfig) -> Self {
+        Self {
+            clap_host_voice_info: clap_host_voice_info {
+                changed: (!config.null_callback).then_some(ext_voice_info_changed),
+            },
+            call_changed: false,
+        }
+    }
+}
+
+extern "C-unwind" fn ext_voice_info_changed(host: *const clap_host) {
+    assert!(!host.is_null());
+    let bed: &mut TestBed = unsafe { &mut *(*host).host_data.cast() };
+    if let Some(ext) = &mut bed.ext_voice_info {
+        ext.call_changed = true;
+    }
+}
+
 struct CheckDescription;
 
 impl Test for CheckDescription {
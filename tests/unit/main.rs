@@ -6,8 +6,10 @@ mod id;
 mod plugin;
 mod plugin_features;
 mod process;
+mod render;
 mod stream;
 mod string_sizes;
+mod testing;
 mod version;
 
 mod shims;
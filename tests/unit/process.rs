@@ -1,3 +1,5 @@
+mod blocks;
+
 use std::ptr::{NonNull, null};
 
 use clap_clap::{
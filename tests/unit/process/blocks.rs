@@ -0,0 +1,245 @@
+use std::{
+    pin::Pin,
+    ptr::{NonNull, null, null_mut},
+};
+
+use clap_clap::{
+    events::{
+        Event, EventBuilder, EventQueue, EventRef, Header, InputEvents, ParamValue,
+        ParamValueBuilder,
+    },
+    ffi::{clap_event_header, clap_input_events},
+    id::ClapId,
+    process::Process,
+};
+
+use crate::process::TestProcessConfig;
+
+struct TestEvents {
+    events: Vec<ParamValueBuilder>,
+    clap_input_events: clap_input_events,
+}
+
+impl TestEvents {
+    fn new(times: &[u32]) -> Pin<Box<Self>> {
+        extern "C-unwind" fn size(list: *const clap_input_events) -> u32 {
+            let bed: &TestEvents = unsafe { &*(*list).ctx.cast() };
+            bed.events.len() as u32
+        }
+
+        extern "C-unwind" fn get(
+            list: *const clap_input_events,
+            index: u32,
+        ) -> *const clap_event_header {
+            let bed: &TestEvents = unsafe { &*(*list).ctx.cast() };
+            bed.events
+                .get(index as usize)
+                .map_or(null(), |ev| ev.event().header().as_clap_event_header())
+        }
+
+        let events = times
+            .iter()
+            .enumerate()
+            .map(|(i, &time)| {
+                ParamValue::build()
+                    .param_id(ClapId::from(i as u16))
+                    .time(time)
+            })
+            .collect();
+
+        let mut bed = Box::new(Self {
+            events,
+            clap_input_events: clap_input_events {
+                ctx: null_mut(),
+                size: Some(size),
+                get: Some(get),
+            },
+        });
+        bed.clap_input_events.ctx = (&raw mut *bed).cast();
+        Box::into_pin(bed)
+    }
+}
+
+/// Collect (frame_offset, frames_count, ids of events) for each block.
+fn split(
+    frames_count: u32,
+    times: &[u32],
+    max_block_size: Option<u32>,
+) -> Vec<(u32, u32, Vec<u32>)> {
+    let mut test_process = TestProcessConfig {
+        frames_count,
+        channel_count: 1,
+        audio_inputs_count: 1,
+        audio_outputs_count: 1,
+        ..Default::default()
+    }
+    .build();
+    let events = TestEvents::new(times);
+
+    let mut clap_process = test_process.clap_process();
+    clap_process.in_events = &events.clap_input_events;
+    let mut process =
+        unsafe { Process::new_unchecked(NonNull::new_unchecked(&raw mut clap_process)) };
+
+    let mut blocks = process.split_at_events();
+    if let Some(max_block_size) = max_block_size {
+        blocks = blocks.max_block_size(max_block_size);
+    }
+
+    let mut retrieved = Vec::new();
+    while let Some(block) = blocks.next_block() {
+        let ids = block
+            .events()
+            .map(|ev| {
+                let EventRef::ParamValue(param) = ev else {
+                    panic!()
+                };
+                param.param_id().into()
+            })
+            .collect();
+        retrieved.push((block.frame_offset(), block.frames_count(), ids));
+    }
+    retrieved
+}
+
+#[test]
+fn no_events() {
+    assert_eq!(split(16, &[], None), [(0, 16, vec![])]);
+}
+
+#[test]
+fn no_frames() {
+    assert_eq!(split(0, &[], None), [(0, 0, vec![])]);
+    assert_eq!(split(0, &[0, 3], None), [(0, 0, vec![0, 1])]);
+}
+
+#[test]
+fn split_at_events() {
+    assert_eq!(
+        split(16, &[0, 4, 4, 10], None),
+        [(0, 4, vec![0]), (4, 6, vec![1, 2]), (10, 6, vec![3])]
+    );
+    assert_eq!(split(16, &[3], None), [(0, 3, vec![]), (3, 13, vec![0])]);
+}
+
+#[test]
+fn events_past_last_frame() {
+    assert_eq!(
+        split(8, &[2, 8, 100], None),
+        [(0, 2, vec![]), (2, 6, vec![0, 1, 2])]
+    );
+}
+
+#[test]
+fn max_block_size() {
+    assert_eq!(
+        split(10, &[5], Some(4)),
+        [
+            (0, 4, vec![]),
+            (4, 1, vec![]),
+            (5, 4, vec![0]),
+            (9, 1, vec![])
+        ]
+    );
+    assert_eq!(split(8, &[], Some(8)), [(0, 8, vec![])]);
+}
+
+#[test]
+#[should_panic(expected = "block size must be greater than zero")]
+fn max_block_size_zero() {
+    split(8, &[], Some(0));
+}
+
+#[test]
+fn block_audio_buffers() {
+    const NUM_FRAMES: u32 = 12;
+    let mut test_process = TestProcessConfig {
+        frames_count: NUM_FRAMES,
+        channel_count: 2,
+        audio_inputs_count: 1,
+        audio_outputs_count: 1,
+        ..Default::default()
+    }
+    .build();
+    for i in 0..NUM_FRAMES as usize {
+        test_process.audio_inputs[0].data32[1].0[i] = i as f32;
+    }
+    let events = TestEvents::new(&[5]);
+
+    {
+        let mut clap_process = test_process.clap_process();
+        clap_process.in_events = &events.clap_input_events;
+        let mut process =
+            unsafe { Process::new_unchecked(NonNull::new_unchecked(&raw mut clap_process)) };
+
+        let mut blocks = process.split_at_events();
+        let mut gain = 1.0;
+        while let Some(mut block) = blocks.next_block() {
+            if block.events().len() > 0 {
+                gain = 2.0;
+            }
+            let input = block.audio_inputs(0).data32(1).to_vec();
+            assert_eq!(input.len(), block.frames_count() as usize);
+            assert_eq!(input[0], block.frame_offset() as f32);
+
+            let frames_count = block.frames_count();
+            let mut output = block.audio_outputs(0);
+            assert_eq!(output.frames_count(), frames_count);
+            for (out, x) in output.data32(1).iter_mut().zip(input) {
+                *out = gain * x;
+            }
+        }
+    }
+
+    for i in 0..NUM_FRAMES as usize {
+        let gain = if i < 5 { 1.0 } else { 2.0 };
+        assert_eq!(
+            test_process.audio_outputs[0].data32[1].0[i],
+            gain * i as f32
+        );
+        assert_eq!(test_process.audio_outputs[0].data32[0].0[i], 0.0);
+    }
+}
+
+#[test]
+fn block_out_events_rebased() {
+    let mut test_process = TestProcessConfig {
+        frames_count: 12,
+        ..Default::default()
+    }
+    .build();
+    let events = TestEvents::new(&[5, 9]);
+    let mut out_events = EventQueue::new();
+
+    {
+        let mut clap_process = test_process.clap_process();
+        clap_process.in_events = &events.clap_input_events;
+        clap_process.out_events = out_events.as_clap_output_events();
+        let mut process =
+            unsafe { Process::new_unchecked(NonNull::new_unchecked(&raw mut clap_process)) };
+
+        let mut blocks = process.split_at_events();
+        while let Some(block) = blocks.next_block() {
+            let mut block_out_events = block.out_events();
+            assert_eq!(block_out_events.frame_offset(), block.frame_offset());
+            let event = ParamValue::build()
+                .param_id(ClapId::from(block.frame_offset() as u16))
+                .time(1);
+            block_out_events.try_push(event).unwrap();
+        }
+    }
+
+    let times: Vec<_> = out_events.iter().map(|e| e.header().time()).collect();
+    assert_eq!(times, [1, 6, 10]);
+}
+
+#[test]
+fn events_outlive_input_events() {
+    fn first_event(list: &clap_input_events) -> &Header {
+        let input_events = unsafe { InputEvents::new_unchecked(list) };
+        input_events.get(0)
+    }
+
+    let events = TestEvents::new(&[7]);
+    assert_eq!(first_event(&events.clap_input_events).time(), 7);
+}
@@ -0,0 +1,308 @@
+mod offline {
+    use clap_clap::{
+        Error,
+        events::{Event, EventBuilder, EventQueue, EventRef, ParamValue},
+        ext::{
+            Extensions,
+            audio_ports::{AudioPorts, MonoPorts},
+        },
+        id::ClapId,
+        plugin::{AudioThread, Plugin},
+        process::{Process, Status, Status::Continue},
+        render::offline::{self, Audio, Renderer},
+    };
+
+    /// Multiply the input by a gain, set by a parameter value event at the
+    /// frame of the event. Write the block's frame offset to the second output
+    /// channel.
+    #[derive(Default)]
+    struct Gain;
+
+    impl Extensions<Self> for Gain {}
+
+    impl Plugin for Gain {
+        type AudioThread = GainThread;
+        const ID: &'static str = "clap.plugin.gain";
+        const NAME: &'static str = "Gain";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<GainThread, Error> {
+            Ok(GainThread { gain: 1.0 })
+        }
+    }
+
+    struct GainThread {
+        gain: f32,
+    }
+
+    impl AudioThread<Gain> for GainThread {
+        fn process(&mut self, process: &mut Process) -> Result<Status, Error> {
+            let n = process.frames_count() as usize;
+            let input = if process.audio_inputs_count() > 0 {
+                process.audio_inputs(0).data32(0)[..n].to_vec()
+            } else {
+                vec![1.0; n]
+            };
+            let events: Vec<_> = process
+                .in_events()
+                .iter()
+                .filter_map(|event| match event {
+                    EventRef::ParamValue(param) => Some((param.header().time(), param.value())),
+                    _ => None,
+                })
+                .collect();
+            let steady_time = process.steady_time() as f32;
+
+            let mut output = process.audio_outputs(0);
+            let mut events = events.into_iter().peekable();
+            for (i, (o, x)) in output.data32(0).iter_mut().zip(input).enumerate() {
+                while let Some((_, gain)) = events.next_if(|(time, _)| *time as usize <= i) {
+                    self.gain = gain as f32;
+                }
+                *o = self.gain * x;
+            }
+            if output.channel_count() > 1 {
+                output.data32(1)[..n].fill(steady_time);
+            }
+            Ok(Continue)
+        }
+    }
+
+    /// An instrument with a mono output.
+    #[derive(Default)]
+    struct Mono;
+
+    impl Extensions<Self> for Mono {
+        fn audio_ports() -> Option<impl AudioPorts<Self>> {
+            Some(MonoPorts::<0, 1>)
+        }
+    }
+
+    impl Plugin for Mono {
+        type AudioThread = ();
+        const ID: &'static str = "clap.plugin.mono";
+        const NAME: &'static str = "Mono";
+
+        fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    fn ramp(frames_count: u32) -> Audio {
+        let channel = (0..frames_count).map(|i| i as f32).collect();
+        Audio::from_channels(48000, vec![channel])
+    }
+
+    #[test]
+    fn audio_new() {
+        let audio = Audio::new(44100, 2, 10);
+
+        assert_eq!(audio.sample_rate(), 44100);
+        assert_eq!(audio.channel_count(), 2);
+        assert_eq!(audio.frames_count(), 10);
+        assert_eq!(audio.channel(1), [0.0; 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "channels must be of equal length")]
+    fn audio_from_channels_unequal() {
+        let _ = Audio::from_channels(44100, vec![vec![0.0; 2], vec![0.0; 3]]);
+    }
+
+    #[test]
+    fn wav_round_trip() {
+        let audio =
+            Audio::from_channels(44100, vec![vec![0.0, 0.5, -1.0], vec![0.25, 1.0, -0.125]]);
+
+        let mut bytes = Vec::new();
+        audio.write_wav(&mut bytes).unwrap();
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+        assert_eq!(bytes.len(), 58 + 3 * 2 * 4);
+
+        assert_eq!(Audio::read_wav(bytes.as_slice()).unwrap(), audio);
+    }
+
+    fn pcm16_wav(samples: &[i16], channel_count: u16) -> Vec<u8> {
+        let data_size = (samples.len() * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + 12 + data_size).to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channel_count.to_le_bytes());
+        bytes.extend_from_slice(&22050u32.to_le_bytes());
+        bytes.extend_from_slice(&(22050 * 2 * u32::from(channel_count)).to_le_bytes());
+        bytes.extend_from_slice(&(2 * channel_count).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        // An unknown chunk of odd size, padded.
+        bytes.extend_from_slice(b"junk");
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3, 0]);
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn wav_read_pcm16() {
+        let bytes = pcm16_wav(&[0, 16384, -32768, 8192], 2);
+
+        let audio = Audio::read_wav(bytes.as_slice()).unwrap();
+        assert_eq!(audio.sample_rate(), 22050);
+        assert_eq!(audio.channel_count(), 2);
+        assert_eq!(audio.channel(0), [0.0, -1.0]);
+        assert_eq!(audio.channel(1), [0.5, 0.25]);
+    }
+
+    #[test]
+    fn wav_read_invalid() {
+        let err = Audio::read_wav(&b"RIFF\0\0\0\0WAVX"[..]).unwrap_err();
+        assert!(matches!(err, offline::Error::Wav(_)));
+
+        let mut bytes = pcm16_wav(&[0, 1], 1);
+        bytes.truncate(bytes.len() - 4);
+        let err = Audio::read_wav(bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, offline::Error::Wav(_)));
+    }
+
+    #[test]
+    fn render_blocks() {
+        let input = ramp(10);
+
+        let output = Renderer::new(48000, 4)
+            .output_channels(2)
+            .render::<Gain>(&input)
+            .unwrap();
+
+        assert_eq!(output.sample_rate(), 48000);
+        assert_eq!(output.frames_count(), 10);
+        assert_eq!(output.channel(0), input.channel(0));
+        assert_eq!(
+            output.channel(1),
+            [0.0, 0.0, 0.0, 0.0, 4.0, 4.0, 4.0, 4.0, 8.0, 8.0]
+        );
+    }
+
+    #[test]
+    fn render_tail() {
+        let output = Renderer::new(48000, 4)
+            .tail(3)
+            .render::<Gain>(&ramp(6))
+            .unwrap();
+
+        assert_eq!(output.frames_count(), 9);
+        assert_eq!(
+            output.channel(0),
+            [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 0.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn render_events() {
+        let mut timeline = EventQueue::new();
+        for (time, value) in [(5, 2.0), (2, -1.0), (8, 0.0)] {
+            let event = ParamValue::build()
+                .param_id(ClapId::from(0))
+                .value(value)
+                .time(time);
+            timeline.try_push(event.event()).unwrap();
+        }
+
+        let output = Renderer::new(48000, 4)
+            .events(timeline)
+            .render::<Gain>(&ramp(10))
+            .unwrap();
+
+        assert_eq!(
+            output.channel(0),
+            [0.0, 1.0, -2.0, -3.0, -4.0, 10.0, 12.0, 14.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn render_event_after_end() {
+        let mut timeline = EventQueue::new();
+        let event = ParamValue::build().param_id(ClapId::from(0)).time(12);
+        timeline.try_push(event.event()).unwrap();
+
+        let err = Renderer::new(48000, 4)
+            .tail(2)
+            .events(timeline)
+            .render::<Gain>(&ramp(10))
+            .unwrap_err();
+
+        assert!(matches!(err, offline::Error::EventTime(12)));
+    }
+
+    #[test]
+    fn wav_write_sample_rate_too_high() {
+        let audio = Audio::new(u32::MAX, 2, 1);
+
+        let err = audio.write_wav(Vec::new()).unwrap_err();
+        assert!(matches!(err, offline::Error::Wav("sample rate too high")));
+    }
+
+    #[test]
+    fn render_no_inputs() {
+        let output = Renderer::new(48000, 8)
+            .output_channels(1)
+            .render::<Gain>(&Audio::new(48000, 0, 5))
+            .unwrap();
+
+        assert_eq!(output.channel(0), [1.0; 5]);
+    }
+
+    #[test]
+    fn render_no_inputs_default_channels() {
+        let output = Renderer::new(48000, 8)
+            .render::<Gain>(&Audio::new(48000, 0, 5))
+            .unwrap();
+
+        assert_eq!(output.channel_count(), 2);
+    }
+
+    #[test]
+    fn render_main_output_port_channels() {
+        let output = Renderer::new(48000, 8)
+            .render::<Mono>(&Audio::new(48000, 0, 5))
+            .unwrap();
+
+        assert_eq!(output.channel_count(), 1);
+    }
+
+    #[test]
+    fn render_sample_rate_mismatch() {
+        let err = Renderer::new(44100, 8)
+            .render::<Gain>(&ramp(4))
+            .unwrap_err();
+
+        assert!(matches!(err, offline::Error::SampleRate(48000)));
+    }
+
+    #[test]
+    #[should_panic(expected = "block size must be greater than zero")]
+    fn renderer_zero_block_size() {
+        let _ = Renderer::new(48000, 0);
+    }
+
+    #[test]
+    fn render_wav_files() {
+        let dir = std::env::temp_dir().join(format!("clap-clap-render-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (input_path, output_path) = (dir.join("input.wav"), dir.join("output.wav"));
+
+        ramp(7).save(&input_path).unwrap();
+        Renderer::new(48000, 3)
+            .render_wav::<Gain>(&input_path, &output_path)
+            .unwrap();
+        let output = Audio::open(&output_path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(output, ramp(7));
+    }
+}
@@ -0,0 +1,324 @@
+use std::sync::Arc;
+
+use clap_clap::{
+    Error,
+    events::{Event, EventBuilder, EventRef, Note, NoteKind},
+    ext::{Extensions, log::Severity},
+    host::Host,
+    plugin::{AudioThread, Plugin},
+    process::{Process, Status, Status::Continue},
+    testing::{self, TestAudioBuffer, TestHost},
+};
+
+#[derive(Default)]
+struct Echo {
+    host: Option<Arc<Host>>,
+    fail_activate: bool,
+    deactivated: u32,
+    on_main_thread: u32,
+}
+
+impl Extensions<Self> for Echo {}
+
+impl Plugin for Echo {
+    type AudioThread = EchoThread;
+    const ID: &'static str = "clap.plugin.echo";
+    const NAME: &'static str = "Echo";
+
+    fn init(&mut self, host: Arc<Host>) -> Result<(), Error> {
+        host.get_extension().log()?.info("init")?;
+        host.request_callback();
+        self.host = Some(host);
+        Ok(())
+    }
+
+    fn activate(&mut self, sample_rate: f64, _: u32, _: u32) -> Result<EchoThread, Error> {
+        if self.fail_activate {
+            return Err(testing::Error::Activate.into());
+        }
+        let host = self.host.clone().unwrap();
+        host.request_restart();
+        Ok(EchoThread {
+            host,
+            sample_rate,
+            steady_time: Vec::new(),
+            reset: 0,
+        })
+    }
+
+    fn on_main_thread(&mut self) {
+        self.on_main_thread += 1;
+    }
+}
+
+struct EchoThread {
+    host: Arc<Host>,
+    sample_rate: f64,
+    steady_time: Vec<i64>,
+    reset: u32,
+}
+
+impl AudioThread<Echo> for EchoThread {
+    fn process(&mut self, process: &mut Process) -> Result<Status, Error> {
+        self.steady_time.push(process.steady_time());
+        self.host.request_process();
+
+        let n = process.frames_count() as usize;
+        for ch in 0..2 {
+            let input = process.audio_inputs(0).data32(ch)[..n].to_vec();
+            let mut output = process.audio_outputs(0);
+            for (o, i) in output.data32(ch).iter_mut().zip(input) {
+                *o = 2.0 * i;
+            }
+        }
+
+        let in_events = process.in_events();
+        let notes: Vec<_> = in_events
+            .iter()
+            .filter_map(|event| match event {
+                EventRef::NoteOn(note) => Some(note.update().key(note.key() + 12)),
+                _ => None,
+            })
+            .collect();
+        let mut out_events = process.out_events();
+        for note in notes {
+            out_events.try_push(note.event())?;
+        }
+
+        Ok(if n == 0 { Status::Sleep } else { Continue })
+    }
+
+    fn reset(&mut self) {
+        self.reset += 1;
+    }
+
+    fn deactivate(self, plugin: &mut Echo) {
+        plugin.deactivated += 1;
+    }
+}
+
+fn processing() -> testing::PluginInstance<Echo> {
+    let host = TestHost::new();
+    let mut instance = host.instantiate::<Echo>().unwrap();
+    instance.set_audio_ports(&[2], &[2]);
+    instance.init().unwrap();
+    instance.activate(44100.0, 1, 16).unwrap();
+    instance.start_processing().unwrap();
+    instance
+}
+
+#[test]
+fn host_description() {
+    let host = TestHost::new();
+    let mut instance = host.instantiate::<Echo>().unwrap();
+    instance.init().unwrap();
+
+    let plugin_host = instance.plugin().host.clone().unwrap();
+    assert_eq!(plugin_host.name(), "clap-clap test host");
+    assert!(plugin_host.get_extension().params().is_err());
+}
+
+#[test]
+fn init_log_and_callback() {
+    let host = TestHost::new();
+    let mut instance = host.instantiate::<Echo>().unwrap();
+    assert_eq!(host.request_callback_count(), 0);
+
+    instance.init().unwrap();
+    assert_eq!(host.request_callback_count(), 1);
+    assert_eq!(host.log_messages(), [(Severity::Info, "init".to_string())]);
+
+    instance.on_main_thread();
+    assert_eq!(instance.plugin().on_main_thread, 1);
+
+    host.clear();
+    assert_eq!(host.request_callback_count(), 0);
+    assert!(host.log_messages().is_empty());
+}
+
+#[test]
+fn init_twice() {
+    let mut instance = TestHost::new().instantiate::<Echo>().unwrap();
+
+    instance.init().unwrap();
+    assert_eq!(instance.init(), Err(testing::Error::AlreadyInitialized));
+}
+
+#[test]
+fn lifecycle_out_of_order() {
+    let mut instance = TestHost::new().instantiate::<Echo>().unwrap();
+
+    assert_eq!(
+        instance.activate(48000.0, 1, 16),
+        Err(testing::Error::NotInitialized)
+    );
+    instance.init().unwrap();
+    assert_eq!(instance.start_processing(), Err(testing::Error::NotActive));
+    assert_eq!(instance.process(0), Err(testing::Error::NotProcessing));
+    assert_eq!(instance.deactivate(), Err(testing::Error::NotActive));
+    assert_eq!(instance.reset(), Err(testing::Error::NotActive));
+
+    instance.activate(48000.0, 1, 16).unwrap();
+    assert_eq!(
+        instance.activate(48000.0, 1, 16),
+        Err(testing::Error::AlreadyActive)
+    );
+    assert_eq!(instance.process(0), Err(testing::Error::NotProcessing));
+    assert_eq!(
+        instance.stop_processing(),
+        Err(testing::Error::NotProcessing)
+    );
+
+    instance.start_processing().unwrap();
+    assert_eq!(
+        instance.start_processing(),
+        Err(testing::Error::AlreadyProcessing)
+    );
+}
+
+#[test]
+fn activate_failed() {
+    let mut instance = TestHost::new().instantiate::<Echo>().unwrap();
+    instance.init().unwrap();
+    instance.plugin().fail_activate = true;
+
+    assert_eq!(
+        instance.activate(48000.0, 1, 16),
+        Err(testing::Error::Activate)
+    );
+    assert!(!instance.is_active());
+    assert!(instance.audio_thread().is_none());
+}
+
+#[test]
+fn activate() {
+    let instance = processing();
+
+    assert!(instance.is_active());
+    assert!(instance.is_processing());
+    assert_eq!(instance.host().request_restart_count(), 1);
+    assert_eq!(instance.audio_input(0).frames_count(), 16);
+    assert_eq!(instance.audio_output(0).channel_count(), 2);
+}
+
+#[test]
+fn audio_thread() {
+    let mut instance = processing();
+
+    assert_eq!(instance.audio_thread().unwrap().sample_rate, 44100.0);
+}
+
+#[test]
+fn process_audio() {
+    let mut instance = processing();
+
+    instance.audio_input_mut(0).channel_mut(0).fill(1.0);
+    instance.audio_input_mut(0).channel_mut(1).fill(-0.5);
+
+    assert_eq!(instance.process(8), Ok(Continue));
+    assert_eq!(instance.host().request_process_count(), 1);
+
+    let output = instance.audio_output(0);
+    assert_eq!(output.channel(0)[..8], [2.0; 8]);
+    assert_eq!(output.channel(0)[8..], [0.0; 8]);
+    assert_eq!(output.channel(1)[..8], [-1.0; 8]);
+}
+
+#[test]
+fn process_status() {
+    let mut instance = processing();
+
+    assert_eq!(instance.process(0), Ok(Status::Sleep));
+}
+
+#[test]
+fn process_steady_time() {
+    let mut instance = processing();
+
+    instance.process(16).unwrap();
+    instance.process(3).unwrap();
+    instance.process(5).unwrap();
+
+    assert_eq!(instance.audio_thread().unwrap().steady_time, [0, 16, 19]);
+}
+
+#[test]
+#[should_panic(expected = "frames_count must not be greater than max_frames_count")]
+fn process_too_many_frames() {
+    let mut instance = processing();
+
+    let _ = instance.process(17);
+}
+
+#[test]
+fn process_events() {
+    let mut instance = processing();
+
+    let note = Note::build(NoteKind::On).key(60).time(4);
+    instance.in_events().try_push(note.event()).unwrap();
+    instance.process(8).unwrap();
+
+    assert!(instance.in_events().is_empty());
+    assert_eq!(instance.out_events().len(), 1);
+    let note = instance.out_events().get(0).unwrap().note().unwrap();
+    assert_eq!(note.key(), 72);
+    assert_eq!(note.header().time(), 4);
+
+    instance.process(8).unwrap();
+    assert!(instance.out_events().is_empty());
+}
+
+#[test]
+fn reset() {
+    let mut instance = processing();
+
+    instance.reset().unwrap();
+    assert_eq!(instance.audio_thread().unwrap().reset, 1);
+}
+
+#[test]
+fn deactivate() {
+    let mut instance = processing();
+
+    instance.deactivate().unwrap();
+    assert!(!instance.is_active());
+    assert!(!instance.is_processing());
+    assert_eq!(instance.plugin().deactivated, 1);
+
+    instance.activate(48000.0, 1, 32).unwrap();
+    assert_eq!(instance.audio_output(0).frames_count(), 32);
+}
+
+#[test]
+fn drop_active() {
+    let instance = processing();
+    let host = instance.host().clone();
+    drop(instance);
+
+    // The recorded data outlives the instance.
+    assert_eq!(host.request_restart_count(), 1);
+}
+
+#[test]
+fn set_audio_ports() {
+    let mut instance = processing();
+
+    instance.set_audio_ports(&[1, 3], &[]);
+    assert_eq!(instance.audio_inputs_count(), 2);
+    assert_eq!(instance.audio_outputs_count(), 0);
+    assert_eq!(instance.audio_input(1).channel_count(), 3);
+    assert_eq!(instance.audio_input(1).frames_count(), 16);
+}
+
+#[test]
+fn test_audio_buffer() {
+    let mut buffer = TestAudioBuffer::new(2, 4);
+    assert_eq!(buffer.channel_count(), 2);
+    assert_eq!(buffer.frames_count(), 4);
+
+    buffer.channel_mut(1).copy_from_slice(&[1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(buffer.channel(1), [1.0, 2.0, 3.0, 4.0]);
+
+    buffer.clear();
+    assert_eq!(buffer.channel(1), [0.0; 4]);
+}
@@ -0,0 +1,302 @@
+use std::{
+    io::{Read, Write},
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use clap_clap::{
+    Error,
+    events::{Event, EventBuilder, EventRef, InputEvents, Note, NoteKind, OutputEvents},
+    ext::{
+        Extensions,
+        audio_ports::{AudioPorts, StereoPorts},
+        note_ports::{NoteDialect, NotePortInfo, NotePorts},
+        params::{ParamInfo, Params},
+        state::State,
+    },
+    hosting::Bundle,
+    id::ClapId,
+    plugin::{AudioThread, Plugin},
+    plugin_features::{INSTRUMENT, STEREO},
+    process::{Process, Status, Status::Continue},
+    stream::{IStream, OStream},
+    validate::{self, Outcome, Report},
+};
+
+/// A plugin generic over its defect, so that each defect is checked in
+/// isolation.
+#[derive(Default)]
+struct Synth<const D: u8> {
+    gain: Arc<AtomicU64>,
+    saved: AtomicU64,
+}
+
+const GOOD: u8 = 0;
+const NO_CATEGORY: u8 = 1;
+const BAD_PARAM_RANGE: u8 = 2;
+const BAD_STATE: u8 = 3;
+const LATE_EVENTS: u8 = 4;
+const UNORDERED_EVENTS: u8 = 5;
+
+impl<const D: u8> Synth<D> {
+    fn gain(&self) -> f64 {
+        f64::from_bits(self.gain.load(Ordering::Acquire))
+    }
+}
+
+impl<const D: u8> Extensions<Self> for Synth<D> {
+    fn audio_ports() -> Option<impl AudioPorts<Self>> {
+        Some(StereoPorts::<0, 1>)
+    }
+
+    fn note_ports() -> Option<impl NotePorts<Self>> {
+        Some(SynthNotePorts)
+    }
+
+    fn params() -> Option<impl Params<Self>> {
+        Some(SynthParams)
+    }
+
+    fn state() -> Option<impl State<Self>> {
+        Some(SynthState)
+    }
+}
+
+impl<const D: u8> Plugin for Synth<D> {
+    type AudioThread = SynthThread;
+    const ID: &'static str = [
+        "good",
+        "no-category",
+        "bad-param-range",
+        "bad-state",
+        "late",
+        "unordered",
+    ][D as usize];
+    const NAME: &'static str = "Synth";
+
+    fn features() -> impl Iterator<Item = &'static str> {
+        let category = if D == NO_CATEGORY {
+            None
+        } else {
+            Some(INSTRUMENT)
+        };
+        category.into_iter().chain([STEREO])
+    }
+
+    fn activate(&mut self, _: f64, _: u32, _: u32) -> Result<SynthThread, Error> {
+        Ok(SynthThread {
+            late_events: D == LATE_EVENTS,
+            unordered_events: D == UNORDERED_EVENTS,
+        })
+    }
+}
+
+struct SynthThread {
+    late_events: bool,
+    unordered_events: bool,
+}
+
+impl<const D: u8> AudioThread<Synth<D>> for SynthThread {
+    fn process(&mut self, process: &mut Process) -> Result<Status, Error> {
+        let frames_count = process.frames_count();
+        let keys: Vec<_> = process
+            .in_events()
+            .iter()
+            .filter_map(|event| match event {
+                EventRef::NoteOn(note) => Some((note.header().time(), note.key())),
+                _ => None,
+            })
+            .collect();
+
+        let mut out_events = process.out_events();
+        for (time, key) in keys {
+            let time = if self.late_events { frames_count } else { time };
+            let note = Note::build(NoteKind::End).key(key).time(time);
+            out_events.try_push(note.event())?;
+        }
+        if self.unordered_events && frames_count > 5 {
+            for time in [5, 2] {
+                let note = Note::build(NoteKind::End).key(60).time(time);
+                // SAFETY: The output list of the validator accepts events out
+                // of order, which is the defect checked.
+                unsafe { out_events.try_push_unchecked(note.event()) }?;
+            }
+        }
+
+        let mut output = process.audio_outputs(0);
+        for channel in 0..2 {
+            output.data32(channel)[..frames_count as usize].fill(0.25);
+        }
+        Ok(Continue)
+    }
+}
+
+struct SynthNotePorts;
+
+impl<const D: u8> NotePorts<Synth<D>> for SynthNotePorts {
+    fn count(_: &Synth<D>, is_input: bool) -> u32 {
+        if is_input { 1 } else { 0 }
+    }
+
+    fn get(_: &Synth<D>, index: u32, is_input: bool) -> Option<NotePortInfo> {
+        (is_input && index == 0).then(|| NotePortInfo {
+            id: ClapId::from(0),
+            supported_dialects: NoteDialect::Clap as u32,
+            preferred_dialect: NoteDialect::Clap as u32,
+            name: "Notes".to_string(),
+        })
+    }
+}
+
+struct SynthParams;
+
+impl<const D: u8> Params<Synth<D>> for SynthParams {
+    fn count(_: &Synth<D>) -> u32 {
+        1
+    }
+
+    fn get_info(_: &Synth<D>, param_index: u32) -> Option<ParamInfo> {
+        (param_index == 0).then(|| ParamInfo {
+            id: ClapId::from(1),
+            flags: 0,
+            name: "Gain".to_string(),
+            module: String::new(),
+            min_value: 0.0,
+            max_value: 1.0,
+            default_value: if D == BAD_PARAM_RANGE { 2.0 } else { 0.0 },
+        })
+    }
+
+    fn get_value(plugin: &Synth<D>, param_id: ClapId) -> Option<f64> {
+        (param_id == ClapId::from(1)).then(|| plugin.gain())
+    }
+
+    fn value_to_text(
+        _: &Synth<D>,
+        _: ClapId,
+        value: f64,
+        mut out_buf: &mut [u8],
+    ) -> Result<(), Error> {
+        Ok(write!(out_buf, "{:.1} dB", 20.0 * value.log10())?)
+    }
+
+    fn text_to_value(_: &Synth<D>, _: ClapId, text: &str) -> Result<f64, Error> {
+        let db: f64 = text.trim_end_matches(" dB").parse()?;
+        Ok(10f64.powf(db / 20.0))
+    }
+
+    fn flush_inactive(_: &Synth<D>, _: &InputEvents, _: &mut OutputEvents) {}
+
+    fn flush(_: &SynthThread, _: &InputEvents, _: &mut OutputEvents) {}
+}
+
+struct SynthState;
+
+impl<const D: u8> State<Synth<D>> for SynthState {
+    fn save(plugin: &Synth<D>, stream: &mut OStream) -> Result<(), Error> {
+        let mut bytes = plugin.gain().to_le_bytes().to_vec();
+        if D == BAD_STATE {
+            // Save how many times the state has been saved.
+            bytes.extend(plugin.saved.fetch_add(1, Ordering::AcqRel).to_le_bytes());
+        }
+        Ok(stream.write_all(&bytes)?)
+    }
+
+    fn load(plugin: &Synth<D>, stream: &mut IStream) -> Result<(), Error> {
+        let mut buf = [0; 8];
+        stream.read_exact(&mut buf)?;
+        plugin
+            .gain
+            .store(f64::from_le_bytes(buf).to_bits(), Ordering::Release);
+        Ok(())
+    }
+}
+
+type Good = Synth<GOOD>;
+type NoCategory = Synth<NO_CATEGORY>;
+type BadParamRange = Synth<BAD_PARAM_RANGE>;
+type BadState = Synth<BAD_STATE>;
+type LateEvents = Synth<LATE_EVENTS>;
+type UnorderedEvents = Synth<UNORDERED_EVENTS>;
+
+clap_clap::entry!(
+    Good,
+    NoCategory,
+    BadParamRange,
+    BadState,
+    LateEvents,
+    UnorderedEvents
+);
+
+static REPORT: LazyLock<Report> = LazyLock::new(|| {
+    // SAFETY: The entry is initialized only here.
+    let bundle = unsafe { Bundle::from_entry(&_clap_entry::clap_entry, "synth.clap") }.unwrap();
+    validate::validate(&bundle)
+});
+
+fn outcome(plugin_id: &str, name: &str) -> &'static Outcome {
+    &REPORT.get(Some(plugin_id), name).unwrap().outcome
+}
+
+fn assert_fail(plugin_id: &str, name: &str, msg: &str) {
+    match outcome(plugin_id, name) {
+        Outcome::Fail(e) => assert!(e.contains(msg), "{e}"),
+        outcome => panic!("{plugin_id} {name}: {outcome:?}"),
+    }
+}
+
+#[test]
+fn report_layout() {
+    assert_eq!(REPORT.get(None, "factory").unwrap().outcome, Outcome::Pass);
+    assert_eq!(REPORT.checks.len(), 1 + 6 * 7);
+    assert!(!REPORT.passed());
+    assert_eq!(REPORT.failures().count(), 5);
+}
+
+#[test]
+fn good_plugin_passes() {
+    for check in REPORT
+        .checks
+        .iter()
+        .filter(|c| c.plugin_id.as_deref().is_none_or(|id| id == "good"))
+    {
+        assert_eq!(check.outcome, Outcome::Pass, "{}", check.name);
+    }
+}
+
+#[test]
+fn no_main_category() {
+    assert_fail("no-category", "descriptor", "no main category");
+    assert_eq!(*outcome("no-category", "process"), Outcome::Pass);
+}
+
+#[test]
+fn bad_param_range() {
+    assert_fail("bad-param-range", "params", "invalid range");
+}
+
+#[test]
+fn bad_state() {
+    assert_fail("bad-state", "state", "state differs");
+}
+
+#[test]
+fn late_events() {
+    assert_fail("late", "process", "out of block");
+}
+
+#[test]
+fn unordered_events() {
+    assert_fail("unordered", "process", "out of order");
+}
+
+#[test]
+fn report_display() {
+    let text = REPORT.to_string();
+
+    assert!(text.contains("PASS * factory\n"));
+    assert!(text.contains("PASS good process\n"));
+    assert!(text.contains("FAIL bad-state state: "));
+}